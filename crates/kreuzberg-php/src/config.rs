@@ -34,6 +34,7 @@ pub struct ExtractionConfig {
     pub postprocessor: Option<PostProcessorConfig>,
     pub max_concurrent_extractions: Option<usize>,
     pub pages: Option<PageConfig>,
+    pub html_options: Option<HtmlConfig>,
 }
 
 #[php_impl]
@@ -54,6 +55,7 @@ impl ExtractionConfig {
             postprocessor: None,
             max_concurrent_extractions: None,
             pages: None,
+            html_options: None,
         }
     }
 
@@ -73,6 +75,20 @@ impl ExtractionConfig {
             .unwrap_or_default();
         Ok(Self::from_rust(rust_config))
     }
+
+    /// Serialize this configuration to a TOML string.
+    pub fn to_toml(&self) -> PhpResult<String> {
+        self.to_rust()
+            .to_toml()
+            .map_err(|e| format!("Failed to serialize config: {}", e).into())
+    }
+
+    /// Serialize this configuration and write it to `path`.
+    pub fn to_file(&self, path: String) -> PhpResult<()> {
+        self.to_rust()
+            .to_file(&path)
+            .map_err(|e| format!("Failed to write config: {}", e).into())
+    }
 }
 
 impl ExtractionConfig {
@@ -90,7 +106,7 @@ impl ExtractionConfig {
             language_detection: self.language_detection.as_ref().map(|c| c.to_rust()),
             keywords: self.keywords.as_ref().map(|c| c.to_rust()),
             postprocessor: self.postprocessor.as_ref().map(|c| c.to_rust()),
-            html_options: None, // Not exposed in PHP bindings yet
+            html_options: self.html_options.as_ref().map(|c| c.to_rust()),
             max_concurrent_extractions: self.max_concurrent_extractions,
             pages: self.pages.as_ref().map(|c| c.to_rust()),
         }
@@ -112,6 +128,69 @@ impl ExtractionConfig {
             postprocessor: config.postprocessor.map(PostProcessorConfig::from_rust),
             max_concurrent_extractions: config.max_concurrent_extractions,
             pages: config.pages.map(PageConfig::from_rust),
+            html_options: config.html_options.map(HtmlConfig::from_rust),
+        }
+    }
+}
+
+/// HTML-to-text conversion configuration.
+///
+/// Controls how the HTML extractor turns markup into extracted content:
+/// link and table handling, whitespace normalization, and tag filtering.
+///
+/// # Example
+///
+/// ```php
+/// $html = new HtmlConfig();
+/// $html->preserve_links = true;
+/// $html->denied_tags = ["script", "style", "nav"];
+/// ```
+#[php_class]
+#[derive(Clone)]
+pub struct HtmlConfig {
+    /// Render `<a href>` as Markdown links instead of discarding the URL.
+    pub preserve_links: bool,
+    /// Extract `<table>` elements into structured tables.
+    pub extract_tables: bool,
+    /// Collapse runs of whitespace into a single space.
+    pub normalize_whitespace: bool,
+    /// When non-empty, only these tags are processed; all others are skipped.
+    pub allowed_tags: Option<Vec<String>>,
+    /// Tags whose content is dropped entirely, e.g. `script`/`style`.
+    pub denied_tags: Option<Vec<String>>,
+}
+
+#[php_impl]
+impl HtmlConfig {
+    pub fn __construct() -> Self {
+        Self {
+            preserve_links: true,
+            extract_tables: true,
+            normalize_whitespace: true,
+            allowed_tags: None,
+            denied_tags: None,
+        }
+    }
+}
+
+impl HtmlConfig {
+    pub fn to_rust(&self) -> kreuzberg::HtmlConfig {
+        kreuzberg::HtmlConfig {
+            preserve_links: self.preserve_links,
+            extract_tables: self.extract_tables,
+            normalize_whitespace: self.normalize_whitespace,
+            allowed_tags: self.allowed_tags.clone(),
+            denied_tags: self.denied_tags.clone(),
+        }
+    }
+
+    pub fn from_rust(config: kreuzberg::HtmlConfig) -> Self {
+        Self {
+            preserve_links: config.preserve_links,
+            extract_tables: config.extract_tables,
+            normalize_whitespace: config.normalize_whitespace,
+            allowed_tags: config.allowed_tags,
+            denied_tags: config.denied_tags,
         }
     }
 }
@@ -455,6 +534,7 @@ pub struct PostProcessorConfig {
     pub enabled: bool,
     pub enabled_processors: Option<Vec<String>>,
     pub disabled_processors: Option<Vec<String>>,
+    pub gazetteer: Option<GazetteerConfig>,
 }
 
 #[php_impl]
@@ -464,6 +544,7 @@ impl PostProcessorConfig {
             enabled: true,
             enabled_processors: None,
             disabled_processors: None,
+            gazetteer: None,
         }
     }
 }
@@ -485,6 +566,7 @@ impl PostProcessorConfig {
             disabled_processors: self.disabled_processors.clone(),
             enabled_set,
             disabled_set,
+            gazetteer: self.gazetteer.as_ref().map(|c| c.to_rust()),
         }
     }
 
@@ -493,6 +575,76 @@ impl PostProcessorConfig {
             enabled: config.enabled,
             enabled_processors: config.enabled_processors,
             disabled_processors: config.disabled_processors,
+            gazetteer: config.gazetteer.map(GazetteerConfig::from_rust),
+        }
+    }
+}
+
+/// Gazetteer (Aho-Corasick dictionary matching) configuration.
+///
+/// Scans extracted text against a user-supplied dictionary of terms (PII
+/// markers, product names, taxonomy keywords, secret prefixes, ...) in a
+/// single pass, surfacing every hit with byte offsets and the matched term's
+/// label. This complements YAKE/RAKE keyword extraction with deterministic
+/// known-term matching.
+///
+/// # Example
+///
+/// ```php
+/// $gazetteer = new GazetteerConfig();
+/// $gazetteer->terms = ["api-key" => ["sk-live"], "company" => ["Acme Corp", "Acme Corp."]];
+/// $gazetteer->redaction_replacement = "[REDACTED]";
+/// ```
+#[php_class]
+#[derive(Clone)]
+pub struct GazetteerConfig {
+    pub enabled: bool,
+    /// Map of label => patterns matched under that label. A label may list
+    /// more than one pattern (e.g. several ways of spelling the same PII
+    /// marker), matching the Rust side's `Vec<(String, String)>` pairs.
+    pub terms: std::collections::HashMap<String, Vec<String>>,
+    pub case_insensitive: bool,
+    /// When set, matched spans are replaced with this string.
+    pub redaction_replacement: Option<String>,
+}
+
+#[php_impl]
+impl GazetteerConfig {
+    pub fn __construct() -> Self {
+        Self {
+            enabled: false,
+            terms: std::collections::HashMap::new(),
+            case_insensitive: true,
+            redaction_replacement: None,
+        }
+    }
+}
+
+impl GazetteerConfig {
+    pub fn to_rust(&self) -> kreuzberg::postprocessors::gazetteer::GazetteerConfig {
+        kreuzberg::postprocessors::gazetteer::GazetteerConfig {
+            enabled: self.enabled,
+            terms: self
+                .terms
+                .iter()
+                .flat_map(|(label, patterns)| patterns.iter().map(move |pattern| (label.clone(), pattern.clone())))
+                .collect(),
+            case_insensitive: self.case_insensitive,
+            redaction_replacement: self.redaction_replacement.clone(),
+        }
+    }
+
+    pub fn from_rust(config: kreuzberg::postprocessors::gazetteer::GazetteerConfig) -> Self {
+        let mut terms: std::collections::HashMap<String, Vec<String>> = std::collections::HashMap::new();
+        for (label, pattern) in config.terms {
+            terms.entry(label).or_default().push(pattern);
+        }
+
+        Self {
+            enabled: config.enabled,
+            terms,
+            case_insensitive: config.case_insensitive,
+            redaction_replacement: config.redaction_replacement,
         }
     }
 }
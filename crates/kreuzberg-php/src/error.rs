@@ -4,62 +4,295 @@
 
 use ext_php_rs::exception::PhpException;
 use ext_php_rs::prelude::*;
+use ext_php_rs::types::{Zval, ZendCallable};
+use std::cell::RefCell;
+
+thread_local! {
+    /// User-registered factory consulted before the built-in exception mapping.
+    ///
+    /// Stored thread-local rather than behind a global `Mutex` because PHP
+    /// requests (even under ZTS) each run on their own thread with their own
+    /// Zend executor state, so a `Zval` must never cross threads anyway.
+    static EXCEPTION_FACTORY: RefCell<Option<Zval>> = const { RefCell::new(None) };
+}
+
+/// API surface for registering a custom exception-proxy factory.
+///
+/// # Example
+///
+/// ```php
+/// Kreuzberg::setExceptionFactory(function (string $kind, string $message, array $context) {
+///     // $context carries the same structured fields the built-in exception
+///     // classes expose, e.g. ["origin" => "plugin", "plugin_name" => "my-plugin"].
+///     if ($kind === "plugin" && ($context["plugin_name"] ?? null) === "my-plugin") {
+///         return new MyApp\MyPluginError($message);
+///     }
+///     return null; // fall back to the built-in mapping
+/// });
+/// ```
+#[php_class]
+pub struct Kreuzberg;
+
+#[php_impl]
+impl Kreuzberg {
+    /// Register a callable invoked for every thrown Kreuzberg exception.
+    ///
+    /// The callable receives `(string $variantKind, string $message, array $context)`
+    /// and should return a `Throwable` to throw instead of the built-in
+    /// mapping, or `null`/nothing to fall back to it.
+    #[php_static_method]
+    pub fn set_exception_factory(factory: Zval) {
+        EXCEPTION_FACTORY.with(|cell| {
+            *cell.borrow_mut() = Some(factory);
+        });
+    }
+
+    /// Clear a previously registered exception factory, restoring the
+    /// built-in class mapping for every error variant.
+    #[php_static_method]
+    pub fn clear_exception_factory() {
+        EXCEPTION_FACTORY.with(|cell| {
+            *cell.borrow_mut() = None;
+        });
+    }
+}
+
+/// Invoke the user-registered factory, if any, for the given error variant.
+///
+/// `context` carries the same structured, per-variant fields (`origin`,
+/// `source_detail`, `plugin_name`) that end up on the built-in exception
+/// classes, so a custom factory can make the same distinctions the built-in
+/// mapping does instead of only seeing the flattened message string.
+///
+/// Returns `None` (falling back to the built-in mapping) when no factory is
+/// registered, the callable isn't actually callable, or it returns PHP
+/// `null`.
+fn try_custom_exception(variant_kind: &str, message: &str, context: &[(&str, String)]) -> Option<PhpException> {
+    EXCEPTION_FACTORY.with(|cell| {
+        let borrowed = cell.borrow();
+        let factory_zval = borrowed.as_ref()?;
+        let callable = ZendCallable::try_from(factory_zval).ok()?;
+
+        let result = callable.try_call(vec![&variant_kind, &message, &context]).ok()?;
+
+        if result.is_null() {
+            return None;
+        }
+
+        result
+            .object()
+            .map(|obj| PhpException::from_object(obj.to_owned()))
+    })
+}
+
+/// Build the structured context passed to a user-registered exception
+/// factory: every per-variant field the built-in exception classes expose
+/// (`origin`, `source_detail`, `plugin_name`), so a custom factory sees the
+/// same structured data the built-in mapping does rather than just the kind
+/// and flattened message.
+fn error_context(origin: &str, source_detail: &Option<String>, plugin_name: Option<&str>) -> Vec<(&'static str, String)> {
+    let mut context = vec![("origin", origin.to_string())];
+
+    if let Some(detail) = source_detail {
+        context.push(("source_detail", detail.clone()));
+    }
+
+    if let Some(name) = plugin_name {
+        context.push(("plugin_name", name.to_string()));
+    }
+
+    context
+}
 
 /// ValidationException - Raised when validation fails
 #[php_class]
 #[extends(ext_php_rs::exception::PhpException)]
-pub struct ValidationException;
+pub struct ValidationException {
+    origin: String,
+    source_detail: Option<String>,
+}
 
 #[php_impl]
-impl ValidationException {}
+impl ValidationException {
+    /// The originating Rust module, e.g. `"validation"`.
+    pub fn get_origin(&self) -> String {
+        self.origin.clone()
+    }
+
+    /// The underlying source error's own message, if one was attached.
+    pub fn get_source_detail(&self) -> Option<String> {
+        self.source_detail.clone()
+    }
+}
 
 /// ParsingException - Raised when document parsing fails
 #[php_class]
 #[extends(ext_php_rs::exception::PhpException)]
-pub struct ParsingException;
+pub struct ParsingException {
+    origin: String,
+    source_detail: Option<String>,
+}
 
 #[php_impl]
-impl ParsingException {}
+impl ParsingException {
+    /// The originating Rust module, e.g. `"parsing"`.
+    pub fn get_origin(&self) -> String {
+        self.origin.clone()
+    }
+
+    /// The underlying source error's own message, if one was attached.
+    pub fn get_source_detail(&self) -> Option<String> {
+        self.source_detail.clone()
+    }
+}
 
 /// OcrException - Raised when OCR processing fails
 #[php_class]
 #[extends(ext_php_rs::exception::PhpException)]
-pub struct OcrException;
+pub struct OcrException {
+    origin: String,
+    source_detail: Option<String>,
+}
 
 #[php_impl]
-impl OcrException {}
+impl OcrException {
+    /// The originating Rust module, e.g. `"ocr"`.
+    pub fn get_origin(&self) -> String {
+        self.origin.clone()
+    }
+
+    /// The underlying source error's own message, if one was attached.
+    pub fn get_source_detail(&self) -> Option<String> {
+        self.source_detail.clone()
+    }
+}
 
 /// MissingDependencyException - Raised when required dependency is missing
 #[php_class]
 #[extends(ext_php_rs::exception::PhpException)]
-pub struct MissingDependencyException;
+pub struct MissingDependencyException {
+    origin: String,
+}
 
 #[php_impl]
-impl MissingDependencyException {}
+impl MissingDependencyException {
+    /// The originating Rust module, e.g. `"missing_dependency"`.
+    pub fn get_origin(&self) -> String {
+        self.origin.clone()
+    }
+}
 
 /// CacheException - Raised when cache operations fail
 #[php_class]
 #[extends(ext_php_rs::exception::PhpException)]
-pub struct CacheException;
+pub struct CacheException {
+    origin: String,
+    source_detail: Option<String>,
+}
 
 #[php_impl]
-impl CacheException {}
+impl CacheException {
+    /// The originating Rust module, e.g. `"cache"`.
+    pub fn get_origin(&self) -> String {
+        self.origin.clone()
+    }
+
+    /// The underlying source error's own message, if one was attached.
+    pub fn get_source_detail(&self) -> Option<String> {
+        self.source_detail.clone()
+    }
+}
 
 /// ImageProcessingException - Raised when image processing fails
 #[php_class]
 #[extends(ext_php_rs::exception::PhpException)]
-pub struct ImageProcessingException;
+pub struct ImageProcessingException {
+    origin: String,
+    source_detail: Option<String>,
+}
 
 #[php_impl]
-impl ImageProcessingException {}
+impl ImageProcessingException {
+    /// The originating Rust module, e.g. `"image_processing"`.
+    pub fn get_origin(&self) -> String {
+        self.origin.clone()
+    }
+
+    /// The underlying source error's own message, if one was attached.
+    pub fn get_source_detail(&self) -> Option<String> {
+        self.source_detail.clone()
+    }
+}
 
 /// PluginException - Raised when plugin operations fail
 #[php_class]
 #[extends(ext_php_rs::exception::PhpException)]
-pub struct PluginException;
+pub struct PluginException {
+    origin: String,
+    plugin_name: String,
+}
 
 #[php_impl]
-impl PluginException {}
+impl PluginException {
+    /// The originating Rust module, always `"plugin"`.
+    pub fn get_origin(&self) -> String {
+        self.origin.clone()
+    }
+
+    /// The name of the plugin that raised the error.
+    pub fn get_plugin_name(&self) -> String {
+        self.plugin_name.clone()
+    }
+}
+
+/// Stable numeric codes for every `KreuzbergError` variant (and notable
+/// sub-kinds), exposed via `Throwable::getCode()`.
+///
+/// These values are part of the public API: once assigned, a code is never
+/// reused or renumbered, so PHP callers can `switch ($e->getCode())` instead
+/// of matching on class names or (locale-dependent) message text.
+#[repr(i64)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCode {
+    Validation = 1000,
+    UnsupportedFormat = 1001,
+    Parsing = 2000,
+    Io = 2001,
+    Ocr = 3000,
+    Plugin = 4000,
+    LockPoisoned = 4001,
+    Cache = 5000,
+    ImageProcessing = 6000,
+    Serialization = 7000,
+    MissingDependency = 8000,
+    Other = 9000,
+}
+
+impl ErrorCode {
+    /// The numeric code, as stored on the thrown exception's `$code` property.
+    pub fn as_i64(self) -> i64 {
+        self as i64
+    }
+}
+
+fn error_code(error: &kreuzberg::KreuzbergError) -> ErrorCode {
+    use kreuzberg::KreuzbergError;
+
+    match error {
+        KreuzbergError::Validation { .. } => ErrorCode::Validation,
+        KreuzbergError::UnsupportedFormat(_) => ErrorCode::UnsupportedFormat,
+        KreuzbergError::Parsing { .. } => ErrorCode::Parsing,
+        KreuzbergError::Io(_) => ErrorCode::Io,
+        KreuzbergError::Ocr { .. } => ErrorCode::Ocr,
+        KreuzbergError::Plugin { .. } => ErrorCode::Plugin,
+        KreuzbergError::LockPoisoned(_) => ErrorCode::LockPoisoned,
+        KreuzbergError::Cache { .. } => ErrorCode::Cache,
+        KreuzbergError::ImageProcessing { .. } => ErrorCode::ImageProcessing,
+        KreuzbergError::Serialization { .. } => ErrorCode::Serialization,
+        KreuzbergError::MissingDependency(_) => ErrorCode::MissingDependency,
+        KreuzbergError::Other(_) => ErrorCode::Other,
+    }
+}
 
 /// Convert Rust KreuzbergError to PHP exception.
 ///
@@ -76,24 +309,122 @@ impl PluginException {}
 /// - `Serialization` → `ParsingException`
 /// - `MissingDependency` → `MissingDependencyException`
 /// - `Other` → PHP's RuntimeException
+///
+/// Every thrown exception also carries a stable [`ErrorCode`] via
+/// `getCode()`, so callers can branch on the numeric code instead of the
+/// class name or message text.
 pub fn to_php_exception(error: kreuzberg::KreuzbergError) -> PhpException {
     use kreuzberg::KreuzbergError;
 
     let message = format_error_message(&error);
+    let origin = variant_kind(&error).to_string();
+    let source_detail = source_message(&error);
+    let plugin_name = match &error {
+        KreuzbergError::Plugin { plugin_name, .. } => Some(plugin_name.as_str()),
+        _ => None,
+    };
 
-    match error {
-        KreuzbergError::Validation { .. } => PhpException::from_class::<ValidationException>(message),
-        KreuzbergError::UnsupportedFormat(_) => PhpException::from_class::<ValidationException>(message),
-        KreuzbergError::Parsing { .. } => PhpException::from_class::<ParsingException>(message),
+    let context = error_context(&origin, &source_detail, plugin_name);
+    if let Some(custom) = try_custom_exception(variant_kind(&error), &message, &context) {
+        return custom;
+    }
+
+    let previous = source_message(&error).map(PhpException::default);
+    let code = error_code(&error).as_i64();
+
+    let exception = match error {
+        KreuzbergError::Validation { .. } => build_exception(
+            message,
+            ValidationException { origin, source_detail },
+        ),
+        KreuzbergError::UnsupportedFormat(_) => build_exception(
+            message,
+            ValidationException { origin, source_detail },
+        ),
+        KreuzbergError::Parsing { .. } => build_exception(
+            message,
+            ParsingException { origin, source_detail },
+        ),
         KreuzbergError::Io(_) => PhpException::default(message),
-        KreuzbergError::Ocr { .. } => PhpException::from_class::<OcrException>(message),
-        KreuzbergError::Plugin { .. } => PhpException::from_class::<PluginException>(message),
+        KreuzbergError::Ocr { .. } => build_exception(message, OcrException { origin, source_detail }),
+        KreuzbergError::Plugin { plugin_name, .. } => {
+            build_exception(message, PluginException { origin, plugin_name })
+        }
         KreuzbergError::LockPoisoned(_) => PhpException::default(format!("Lock poisoned: {}", message)),
-        KreuzbergError::Cache { .. } => PhpException::from_class::<CacheException>(message),
-        KreuzbergError::ImageProcessing { .. } => PhpException::from_class::<ImageProcessingException>(message),
-        KreuzbergError::Serialization { .. } => PhpException::from_class::<ParsingException>(message),
-        KreuzbergError::MissingDependency(_) => PhpException::from_class::<MissingDependencyException>(message),
+        KreuzbergError::Cache { .. } => build_exception(message, CacheException { origin, source_detail }),
+        KreuzbergError::ImageProcessing { .. } => build_exception(
+            message,
+            ImageProcessingException { origin, source_detail },
+        ),
+        KreuzbergError::Serialization { .. } => build_exception(
+            message,
+            ParsingException { origin, source_detail },
+        ),
+        KreuzbergError::MissingDependency(_) => {
+            build_exception(message, MissingDependencyException { origin })
+        }
         KreuzbergError::Other(_) => PhpException::default(message),
+    };
+
+    let exception = exception.with_code(code);
+
+    match previous {
+        Some(previous) => exception.with_previous(previous),
+        None => exception,
+    }
+}
+
+/// Wrap a populated exception struct (carrying structured, PHP-visible
+/// fields such as `origin`/`sourceDetail`/`pluginName`) as a [`PhpException`]
+/// with the given top-level message.
+fn build_exception<T>(message: String, instance: T) -> PhpException
+where
+    T: ext_php_rs::class::RegisteredClass,
+{
+    let mut obj = ext_php_rs::types::ZendClassObject::new(instance);
+    let _ = obj.set_property("message", message);
+    PhpException::from_object(obj.into_zend_object())
+}
+
+/// Extract the source error's own message, if the variant carries one.
+///
+/// Used to build the `$previous` exception in the chain rather than
+/// flattening it into the outer message, so PHP code can walk
+/// `getPrevious()` to reconstruct the full cause chain.
+fn source_message(error: &kreuzberg::KreuzbergError) -> Option<String> {
+    use kreuzberg::KreuzbergError;
+
+    match error {
+        KreuzbergError::Validation { source, .. }
+        | KreuzbergError::Parsing { source, .. }
+        | KreuzbergError::Ocr { source, .. }
+        | KreuzbergError::Cache { source, .. }
+        | KreuzbergError::ImageProcessing { source, .. }
+        | KreuzbergError::Serialization { source, .. } => source.as_ref().map(|src| src.to_string()),
+        _ => None,
+    }
+}
+
+/// Short, stable string identifying the `KreuzbergError` variant.
+///
+/// Passed to the user-registered exception factory so it can dispatch
+/// without matching on the (locale-dependent) message text.
+fn variant_kind(error: &kreuzberg::KreuzbergError) -> &'static str {
+    use kreuzberg::KreuzbergError;
+
+    match error {
+        KreuzbergError::Validation { .. } => "validation",
+        KreuzbergError::UnsupportedFormat(_) => "unsupported_format",
+        KreuzbergError::Parsing { .. } => "parsing",
+        KreuzbergError::Io(_) => "io",
+        KreuzbergError::Ocr { .. } => "ocr",
+        KreuzbergError::Plugin { .. } => "plugin",
+        KreuzbergError::LockPoisoned(_) => "lock_poisoned",
+        KreuzbergError::Cache { .. } => "cache",
+        KreuzbergError::ImageProcessing { .. } => "image_processing",
+        KreuzbergError::Serialization { .. } => "serialization",
+        KreuzbergError::MissingDependency(_) => "missing_dependency",
+        KreuzbergError::Other(_) => "other",
     }
 }
 
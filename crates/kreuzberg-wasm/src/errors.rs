@@ -23,6 +23,11 @@ use wasm_bindgen::prelude::*;
 /// - `LockPoisoned` → Lock poisoning (internal error)
 /// - `UnsupportedFormat` → Unsupported MIME type
 /// - `Other` → Generic error
+///
+/// A `Validation` error produced by [`kreuzberg::pdf::error::PdfError::PasswordRequired`]
+/// or `IncorrectPassword` is special-cased to `"PasswordRequiredError"`
+/// instead of the generic `"ValidationError"`, so JavaScript callers can
+/// detect it and prompt for a password without string-matching the message.
 pub fn convert_error(err: KreuzbergError) -> JsValue {
     use kreuzberg::KreuzbergError;
 
@@ -33,6 +38,12 @@ pub fn convert_error(err: KreuzbergError) -> JsValue {
 
         KreuzbergError::Ocr { message, .. } => ("OCRError", format!("OCR error: {}", message)),
 
+        KreuzbergError::Validation { message, .. }
+            if message.starts_with("Password required") || message.starts_with("Incorrect password") =>
+        {
+            ("PasswordRequiredError", message)
+        }
+
         KreuzbergError::Validation { message, .. } => ("ValidationError", format!("Validation error: {}", message)),
 
         KreuzbergError::Cache { message, .. } => ("CacheError", format!("Cache error: {}", message)),
@@ -126,6 +137,22 @@ mod tests {
         assert!(!result.is_null());
     }
 
+    #[wasm_bindgen_test]
+    fn test_convert_error_password_required_returns_jsvalue() {
+        let err: KreuzbergError = kreuzberg::pdf::error::PdfError::PasswordRequired.into();
+        let result = convert_error(err);
+
+        assert!(!result.is_null());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_convert_error_incorrect_password_returns_jsvalue() {
+        let err: KreuzbergError = kreuzberg::pdf::error::PdfError::IncorrectPassword("bad password".to_string()).into();
+        let result = convert_error(err);
+
+        assert!(!result.is_null());
+    }
+
     #[wasm_bindgen_test]
     fn test_convert_error_cache_error_returns_jsvalue() {
         let err = KreuzbergError::Cache {
@@ -5,8 +5,98 @@
 //! file extensions, and normalizing MIME type strings.
 
 use js_sys::Array;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::Mutex;
 use wasm_bindgen::prelude::*;
 
+/// User-registered MIME type → extensions mappings, keyed by normalized MIME
+/// type. Consulted before the built-in static tables by both
+/// [`get_mime_from_extension`] and [`get_extensions_for_mime`], so
+/// applications can teach this module vendor or domain-specific types at
+/// runtime without forking the crate.
+static CUSTOM_MIME_REGISTRY: Lazy<Mutex<HashMap<String, Vec<String>>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Register a custom MIME type with the file extensions that map to it.
+///
+/// Extensions are stored without a leading dot, lowercased. Registering a
+/// MIME type that's already registered replaces its extension list.
+/// Registered types take priority over the built-in tables: a registered
+/// extension shadows a built-in mapping for the same extension, and
+/// [`get_extensions_for_mime`] merges registered extensions with any
+/// built-in ones for the same MIME type.
+///
+/// # JavaScript Parameters
+///
+/// * `mimeType: string` - The MIME type to register, e.g. "application/vnd.api+json"
+/// * `extensions: string[]` - Extensions that should resolve to this MIME type
+///
+/// # Example
+///
+/// ```javascript
+/// import { registerMimeType, getMimeFromExtension } from '@kreuzberg/wasm';
+///
+/// registerMimeType('application/vnd.api+json', ['json-api']);
+/// console.log(getMimeFromExtension('json-api')); // "application/vnd.api+json"
+/// ```
+#[wasm_bindgen(js_name = registerMimeType)]
+pub fn register_mime_type(mime_type: String, extensions: Vec<String>) {
+    let normalized = normalize_mime_type(mime_type);
+    let extensions = extensions.into_iter().map(|ext| ext.trim_start_matches('.').to_lowercase()).collect();
+    CUSTOM_MIME_REGISTRY.lock().unwrap().insert(normalized, extensions);
+}
+
+/// Remove a MIME type previously registered with [`register_mime_type`].
+/// Lookups for it revert to the built-in tables (or to no match, if it has
+/// no built-in entry either). A no-op if `mimeType` was never registered.
+///
+/// # JavaScript Parameters
+///
+/// * `mimeType: string` - The MIME type to unregister
+#[wasm_bindgen(js_name = unregisterMimeType)]
+pub fn unregister_mime_type(mime_type: String) {
+    let normalized = normalize_mime_type(mime_type);
+    CUSTOM_MIME_REGISTRY.lock().unwrap().remove(&normalized);
+}
+
+/// Clear every custom MIME type registered via [`register_mime_type`],
+/// reverting all lookups to the built-in tables.
+#[wasm_bindgen(js_name = resetMimeRegistry)]
+pub fn reset_mime_registry() {
+    CUSTOM_MIME_REGISTRY.lock().unwrap().clear();
+}
+
+/// Extensions registered for `mime_type` (by its normalized essence string),
+/// or an empty `Vec` if nothing is registered for it.
+fn registered_extensions_for_mime(mime_type: &str) -> Vec<String> {
+    let normalized = normalize_mime_type(mime_type.to_string());
+    CUSTOM_MIME_REGISTRY.lock().unwrap().get(&normalized).cloned().unwrap_or_default()
+}
+
+/// MIME type registered for `ext` (case-insensitive, no leading dot), or
+/// `None` if no registered entry claims it.
+fn registered_mime_for_extension(ext: &str) -> Option<String> {
+    CUSTOM_MIME_REGISTRY
+        .lock()
+        .unwrap()
+        .iter()
+        .find(|(_, extensions)| extensions.iter().any(|candidate| candidate == ext))
+        .map(|(mime_type, _)| mime_type.clone())
+}
+
+/// Extensions for `mime_type`, merging registered custom extensions (first)
+/// with the built-in [`kreuzberg::get_extensions_for_mime`] table, without
+/// duplicates.
+fn extensions_for_mime(mime_type: &str) -> Vec<String> {
+    let mut merged = registered_extensions_for_mime(mime_type);
+    for ext in kreuzberg::get_extensions_for_mime(mime_type).unwrap_or_default() {
+        if !merged.contains(&ext) {
+            merged.push(ext);
+        }
+    }
+    merged
+}
+
 /// Detect MIME type from raw file bytes.
 ///
 /// Uses magic byte signatures and content analysis to detect the MIME type of
@@ -38,7 +128,256 @@ use wasm_bindgen::prelude::*;
 #[wasm_bindgen(js_name = detectMimeFromBytes)]
 pub fn detect_mime_from_bytes(data: js_sys::Uint8Array) -> Result<String, JsValue> {
     let bytes = data.to_vec();
-    kreuzberg::detect_mime_type_from_bytes(&bytes).map_err(|e| JsValue::from_str(&e.to_string()))
+    let initial =
+        kreuzberg::detect_mime_type_from_bytes(&bytes).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    Ok(refine_container_mime(&bytes, &initial))
+}
+
+/// Result of comparing a file's declared extension against its actual
+/// content, returned by [`detect_mime_mismatch`].
+#[wasm_bindgen]
+pub struct MimeMismatch {
+    declared_mime: Option<String>,
+    detected_mime: String,
+    matches: bool,
+    recommended_extension: Option<String>,
+}
+
+#[wasm_bindgen]
+impl MimeMismatch {
+    /// MIME type implied by the file's extension, or `null` when the
+    /// extension is unrecognized or absent.
+    #[wasm_bindgen(getter, js_name = declaredMime)]
+    pub fn declared_mime(&self) -> Option<String> {
+        self.declared_mime.clone()
+    }
+
+    /// MIME type detected from the file's actual content.
+    #[wasm_bindgen(getter, js_name = detectedMime)]
+    pub fn detected_mime(&self) -> String {
+        self.detected_mime.clone()
+    }
+
+    /// Whether the declared and detected MIME types agree.
+    #[wasm_bindgen(getter)]
+    pub fn matches(&self) -> bool {
+        self.matches
+    }
+
+    /// First extension registered for the detected MIME type, to suggest as
+    /// a rename target. `null` when the types already match or the detected
+    /// type has no registered extension.
+    #[wasm_bindgen(getter, js_name = recommendedExtension)]
+    pub fn recommended_extension(&self) -> Option<String> {
+        self.recommended_extension.clone()
+    }
+}
+
+/// Detect whether a file's declared extension matches its actual content.
+///
+/// Combines [`get_mime_from_extension`] on `filename`'s extension with
+/// content-based [`detect_mime_from_bytes`] on `data`, normalizes both MIME
+/// types to their essence string (see [`normalize_mime_type`]), and compares
+/// them. When they differ, looks up [`get_extensions_for_mime`] for the
+/// content-derived type and returns its first extension as the recommended
+/// rename target.
+///
+/// # JavaScript Parameters
+///
+/// * `filename: string` - The file's name, used only for its extension
+/// * `data: Uint8Array` - The raw file bytes
+///
+/// # Returns
+///
+/// `{ declaredMime, detectedMime, matches, recommendedExtension }` - A
+/// [`MimeMismatch`] describing the comparison
+///
+/// # Throws
+///
+/// Throws an error if the content-based MIME type cannot be determined.
+///
+/// # Example
+///
+/// ```javascript
+/// import { detectMimeMismatch } from '@kreuzberg/wasm';
+/// import { readFileSync } from 'fs';
+///
+/// const bytes = readFileSync('report.txt');
+/// const mismatch = detectMimeMismatch('report.txt', new Uint8Array(bytes));
+/// if (!mismatch.matches) {
+///   console.log(`rename to .${mismatch.recommendedExtension}`);
+/// }
+/// ```
+#[wasm_bindgen(js_name = detectMimeMismatch)]
+pub fn detect_mime_mismatch(filename: String, data: js_sys::Uint8Array) -> Result<MimeMismatch, JsValue> {
+    let bytes = data.to_vec();
+    mime_mismatch(&filename, &bytes).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Pure-Rust core of [`detect_mime_mismatch`], kept free of `js_sys` types so
+/// it can be exercised with plain `#[test]`s.
+fn mime_mismatch(filename: &str, bytes: &[u8]) -> Result<MimeMismatch, kreuzberg::KreuzbergError> {
+    let initial = kreuzberg::detect_mime_type_from_bytes(bytes)?;
+    let detected_mime = refine_container_mime(bytes, &initial);
+
+    let declared_mime = std::path::Path::new(filename)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .and_then(|ext| get_mime_from_extension(ext.to_string()));
+
+    let matches = declared_mime
+        .as_deref()
+        .map(|declared| normalize_mime_type(declared.to_string()) == normalize_mime_type(detected_mime.clone()))
+        .unwrap_or(false);
+
+    let recommended_extension = if matches { None } else { extensions_for_mime(&detected_mime).into_iter().next() };
+
+    Ok(MimeMismatch { declared_mime, detected_mime, matches, recommended_extension })
+}
+
+/// Bytes scanned from the start of the buffer for an `<svg` root element
+/// when promoting a `text/xml`/`application/xml` match to `image/svg+xml`.
+const XML_SVG_SCAN_LIMIT: usize = 8192;
+
+/// Reclassify a "container" MIME type match (`application/zip`,
+/// `text/xml`/`application/xml`) to the most specific type the buffer's
+/// contents actually support, falling back to `initial` unchanged when
+/// nothing more specific is found.
+///
+/// [`detect_mime_type_from_bytes`](kreuzberg::detect_mime_type_from_bytes)'s
+/// single magic-byte pass can't tell apart the many formats that are really
+/// a ZIP (DOCX/XLSX/PPTX/ODT/ODS/EPUB/JAR) or an XML document (SVG) at the
+/// container level, so this second stage inspects the ZIP central directory
+/// or scans further into the XML for a deciding signal.
+fn refine_container_mime(bytes: &[u8], initial: &str) -> String {
+    match initial {
+        "application/zip" => refine_zip_mime(bytes).unwrap_or_else(|| initial.to_string()),
+        "text/xml" | "application/xml" if is_svg_root(bytes) => "image/svg+xml".to_string(),
+        _ => initial.to_string(),
+    }
+}
+
+/// Scan the leading [`XML_SVG_SCAN_LIMIT`] bytes for an `<svg` root element.
+fn is_svg_root(bytes: &[u8]) -> bool {
+    let limit = bytes.len().min(XML_SVG_SCAN_LIMIT);
+    String::from_utf8_lossy(&bytes[..limit]).contains("<svg")
+}
+
+/// One entry from a ZIP central directory, enough to reclassify the archive
+/// type: its member name, compression method, and the offset of its local
+/// file header (needed to read a stored/uncompressed member's raw bytes).
+struct ZipEntry {
+    name: String,
+    compression: u16,
+    local_header_offset: usize,
+}
+
+const ZIP_EOCD_SIGNATURE: [u8; 4] = [0x50, 0x4b, 0x05, 0x06];
+const ZIP_CENTRAL_DIR_SIGNATURE: [u8; 4] = [0x50, 0x4b, 0x01, 0x02];
+const ZIP_LOCAL_FILE_SIGNATURE: [u8; 4] = [0x50, 0x4b, 0x03, 0x04];
+
+/// Reclassify a ZIP archive by its member names and (for EPUB/ODF's
+/// stored-uncompressed `mimetype` entry) content, without decompressing
+/// anything: OOXML formats are told apart by a distinctive part's path
+/// (`word/document.xml`, `xl/workbook.xml`, any `ppt/` part), while
+/// EPUB/ODT/ODS declare their real MIME type as the literal content of a
+/// `mimetype` member stored without compression.
+fn refine_zip_mime(bytes: &[u8]) -> Option<String> {
+    let entries = zip_central_directory_entries(bytes)?;
+
+    if entries.iter().any(|e| e.name == "word/document.xml") {
+        return Some("application/vnd.openxmlformats-officedocument.wordprocessingml.document".to_string());
+    }
+    if entries.iter().any(|e| e.name == "xl/workbook.xml") {
+        return Some("application/vnd.openxmlformats-officedocument.spreadsheetml.sheet".to_string());
+    }
+    if entries.iter().any(|e| e.name.starts_with("ppt/")) {
+        return Some("application/vnd.openxmlformats-officedocument.presentationml.presentation".to_string());
+    }
+
+    let mimetype_entry = entries.iter().find(|e| e.name == "mimetype")?;
+    if mimetype_entry.compression != 0 {
+        return None;
+    }
+    let content = read_stored_entry(bytes, mimetype_entry)?;
+    let content = content.trim();
+    if content == "application/epub+zip" || content.starts_with("application/vnd.oasis.opendocument.") {
+        Some(content.to_string())
+    } else {
+        None
+    }
+}
+
+/// Locate the End Of Central Directory record and parse every central
+/// directory file header that follows it into a [`ZipEntry`]. Returns
+/// `None` if `bytes` isn't a well-formed ZIP (no EOCD signature, or a
+/// truncated/corrupt central directory).
+fn zip_central_directory_entries(bytes: &[u8]) -> Option<Vec<ZipEntry>> {
+    let eocd = find_eocd(bytes)?;
+    let cd_size = u32::from_le_bytes(bytes[eocd + 12..eocd + 16].try_into().ok()?) as usize;
+    let cd_offset = u32::from_le_bytes(bytes[eocd + 16..eocd + 20].try_into().ok()?) as usize;
+    let cd_end = cd_offset.checked_add(cd_size)?;
+    if cd_end > bytes.len() {
+        return None;
+    }
+
+    let mut entries = Vec::new();
+    let mut pos = cd_offset;
+    while pos + 46 <= cd_end {
+        if bytes[pos..pos + 4] != ZIP_CENTRAL_DIR_SIGNATURE {
+            break;
+        }
+        let compression = u16::from_le_bytes(bytes[pos + 10..pos + 12].try_into().ok()?);
+        let name_len = u16::from_le_bytes(bytes[pos + 28..pos + 30].try_into().ok()?) as usize;
+        let extra_len = u16::from_le_bytes(bytes[pos + 30..pos + 32].try_into().ok()?) as usize;
+        let comment_len = u16::from_le_bytes(bytes[pos + 32..pos + 34].try_into().ok()?) as usize;
+        let local_header_offset = u32::from_le_bytes(bytes[pos + 42..pos + 46].try_into().ok()?) as usize;
+
+        let name_start = pos + 46;
+        let name_end = name_start.checked_add(name_len)?;
+        if name_end > bytes.len() {
+            break;
+        }
+        let name = String::from_utf8_lossy(&bytes[name_start..name_end]).to_string();
+
+        entries.push(ZipEntry { name, compression, local_header_offset });
+
+        pos = name_end + extra_len + comment_len;
+    }
+
+    Some(entries)
+}
+
+/// Search backward from the end of `bytes` for the ZIP End Of Central
+/// Directory signature, within the maximum possible archive comment length
+/// (64KB) plus the fixed 22-byte record.
+fn find_eocd(bytes: &[u8]) -> Option<usize> {
+    if bytes.len() < 22 {
+        return None;
+    }
+    let search_start = bytes.len().saturating_sub(22 + 65_535);
+    (search_start..=bytes.len() - 22).rev().find(|&i| bytes[i..i + 4] == ZIP_EOCD_SIGNATURE)
+}
+
+/// Read a stored (uncompressed) ZIP member's raw bytes via its local file
+/// header, decoded as UTF-8 (lossy). Returns `None` if `entry`'s local
+/// header offset doesn't actually point at a local file header, or the
+/// declared data runs past the end of `bytes`.
+fn read_stored_entry(bytes: &[u8], entry: &ZipEntry) -> Option<String> {
+    let lh = entry.local_header_offset;
+    if bytes.len() < lh + 30 || bytes[lh..lh + 4] != ZIP_LOCAL_FILE_SIGNATURE {
+        return None;
+    }
+    let compressed_size = u32::from_le_bytes(bytes[lh + 18..lh + 22].try_into().ok()?) as usize;
+    let name_len = u16::from_le_bytes(bytes[lh + 26..lh + 28].try_into().ok()?) as usize;
+    let extra_len = u16::from_le_bytes(bytes[lh + 28..lh + 30].try_into().ok()?) as usize;
+
+    let data_start = lh + 30 + name_len + extra_len;
+    let data_end = data_start.checked_add(compressed_size)?;
+    if data_end > bytes.len() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&bytes[data_start..data_end]).to_string())
 }
 
 /// Get MIME type from file extension.
@@ -78,7 +417,17 @@ pub fn get_mime_from_extension(extension: String) -> Option<String> {
 
     let ext_lower = ext.to_lowercase();
 
-    match ext_lower.as_str() {
+    if let Some(mime_type) = registered_mime_for_extension(&ext_lower) {
+        return Some(mime_type);
+    }
+
+    builtin_mime_from_extension(&ext_lower)
+}
+
+/// Built-in extension → MIME type table, consulted after the custom
+/// [`CUSTOM_MIME_REGISTRY`] by [`get_mime_from_extension`].
+fn builtin_mime_from_extension(ext_lower: &str) -> Option<String> {
+    match ext_lower {
         "txt" => Some("text/plain".to_string()),
         "md" | "markdown" => Some("text/markdown".to_string()),
         "pdf" => Some("application/pdf".to_string()),
@@ -166,15 +515,16 @@ pub fn get_mime_from_extension(extension: String) -> Option<String> {
 /// ```
 #[wasm_bindgen(js_name = getExtensionsForMime)]
 pub fn get_extensions_for_mime(mime_type: String) -> Result<Array, JsValue> {
-    kreuzberg::get_extensions_for_mime(&mime_type)
-        .map_err(|e| JsValue::from_str(&e.to_string()))
-        .map(|extensions| {
-            let array = Array::new();
-            for ext in extensions {
-                array.push(&JsValue::from_str(&ext));
-            }
-            array
-        })
+    let extensions = extensions_for_mime(&mime_type);
+    if extensions.is_empty() {
+        return Err(JsValue::from_str(&format!("Unrecognized MIME type: {}", mime_type)));
+    }
+
+    let array = Array::new();
+    for ext in extensions {
+        array.push(&JsValue::from_str(&ext));
+    }
+    Ok(array)
 }
 
 /// Normalize a MIME type string.
@@ -216,6 +566,115 @@ pub fn normalize_mime_type(mime_type: String) -> String {
     }
 }
 
+/// RFC 6838/6839 structured breakdown of a MIME type, returned by
+/// [`parse_mime_type`]: its top-level `type`, `subtype` (with any `+suffix`
+/// split off), and `key=value` parameters following `;` (e.g. `charset`).
+/// Unlike [`normalize_mime_type`], nothing is discarded.
+#[wasm_bindgen]
+pub struct ParsedMimeType {
+    mime_type: String,
+    subtype: String,
+    suffix: Option<String>,
+    parameters: HashMap<String, String>,
+}
+
+#[wasm_bindgen]
+impl ParsedMimeType {
+    /// Top-level type, e.g. `"application"` in `application/vnd.api+json`.
+    #[wasm_bindgen(getter)]
+    pub fn r#type(&self) -> String {
+        self.mime_type.clone()
+    }
+
+    /// Subtype with any `+suffix` removed, e.g. `"vnd.api"` in `application/vnd.api+json`.
+    #[wasm_bindgen(getter)]
+    pub fn subtype(&self) -> String {
+        self.subtype.clone()
+    }
+
+    /// Structured syntax suffix (RFC 6839), e.g. `"json"` in
+    /// `application/vnd.api+json` or `"xml"` in `image/svg+xml`. `None` when
+    /// the subtype has no `+suffix`.
+    #[wasm_bindgen(getter)]
+    pub fn suffix(&self) -> Option<String> {
+        self.suffix.clone()
+    }
+
+    /// `key=value` parameters following `;` (e.g. `charset`), as a plain JS object.
+    #[wasm_bindgen(getter)]
+    pub fn parameters(&self) -> js_sys::Object {
+        let object = js_sys::Object::new();
+        for (key, value) in &self.parameters {
+            let _ = js_sys::Reflect::set(&object, &JsValue::from_str(key), &JsValue::from_str(value));
+        }
+        object
+    }
+}
+
+/// Parse a MIME type into its structured components.
+///
+/// Splits `type/subtype`, detects a trailing `+suffix` on the subtype (RFC
+/// 6839 structured syntax, e.g. `+json`, `+xml`), and parses any
+/// `key=value` parameters following `;` into a map. Preserves information
+/// [`normalize_mime_type`] discards, such as `charset`.
+///
+/// # JavaScript Parameters
+///
+/// * `mimeType: string` - The MIME type string to parse
+///
+/// # Returns
+///
+/// `{ type, subtype, suffix, parameters }` - A [`ParsedMimeType`]
+///
+/// # Example
+///
+/// ```javascript
+/// import { parseMimeType } from '@kreuzberg/wasm';
+///
+/// const parsed = parseMimeType('application/vnd.api+json; charset=utf-8');
+/// console.log(parsed.type);       // "application"
+/// console.log(parsed.subtype);    // "vnd.api"
+/// console.log(parsed.suffix);     // "json"
+/// console.log(parsed.parameters); // { charset: "utf-8" }
+///
+/// const svg = parseMimeType('image/svg+xml');
+/// console.log(svg.suffix); // "xml"
+/// ```
+#[wasm_bindgen(js_name = parseMimeType)]
+pub fn parse_mime_type(mime_type: String) -> ParsedMimeType {
+    parse_mime_type_parts(&mime_type)
+}
+
+/// Pure-Rust core of [`parse_mime_type`], kept free of `js_sys` types so it
+/// can be exercised with plain `#[test]`s.
+fn parse_mime_type_parts(mime_type: &str) -> ParsedMimeType {
+    let trimmed = mime_type.trim();
+    let (essence, params_str) = match trimmed.split_once(';') {
+        Some((essence, params)) => (essence.trim(), Some(params)),
+        None => (trimmed, None),
+    };
+
+    let (mime_type, subtype_part) = match essence.split_once('/') {
+        Some((t, s)) => (t.to_lowercase(), s.to_lowercase()),
+        None => (essence.to_lowercase(), String::new()),
+    };
+
+    let (subtype, suffix) = match subtype_part.rsplit_once('+') {
+        Some((base, suffix)) if !base.is_empty() => (base.to_string(), Some(suffix.to_string())),
+        _ => (subtype_part, None),
+    };
+
+    let mut parameters = HashMap::new();
+    for pair in params_str.into_iter().flat_map(|params| params.split(';')) {
+        let pair = pair.trim();
+        if let Some((key, value)) = pair.split_once('=') {
+            parameters.insert(key.trim().to_lowercase(), value.trim().trim_matches('"').to_string());
+        }
+    }
+
+    ParsedMimeType { mime_type, subtype, suffix, parameters }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -268,4 +727,269 @@ mod tests {
         assert_eq!(get_mime_from_extension("unknown".to_string()), None);
         assert_eq!(get_mime_from_extension("xyz".to_string()), None);
     }
+
+    /// Build a minimal valid ZIP archive (local headers + central directory
+    /// + EOCD) containing each of `entries` stored without compression, for
+    /// exercising [`refine_zip_mime`] without a real `zip` dependency.
+    fn build_test_zip(entries: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut out = Vec::new();
+        let mut local_offsets = Vec::new();
+
+        for (name, data) in entries {
+            local_offsets.push(out.len() as u32);
+            out.extend_from_slice(&ZIP_LOCAL_FILE_SIGNATURE);
+            out.extend_from_slice(&20u16.to_le_bytes()); // version needed
+            out.extend_from_slice(&0u16.to_le_bytes()); // flags
+            out.extend_from_slice(&0u16.to_le_bytes()); // compression: stored
+            out.extend_from_slice(&0u16.to_le_bytes()); // mod time
+            out.extend_from_slice(&0u16.to_le_bytes()); // mod date
+            out.extend_from_slice(&0u32.to_le_bytes()); // crc32
+            out.extend_from_slice(&(data.len() as u32).to_le_bytes()); // compressed size
+            out.extend_from_slice(&(data.len() as u32).to_le_bytes()); // uncompressed size
+            out.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            out.extend_from_slice(&0u16.to_le_bytes()); // extra len
+            out.extend_from_slice(name.as_bytes());
+            out.extend_from_slice(data);
+        }
+
+        let cd_start = out.len();
+        for ((name, data), &local_offset) in entries.iter().zip(&local_offsets) {
+            out.extend_from_slice(&ZIP_CENTRAL_DIR_SIGNATURE);
+            out.extend_from_slice(&20u16.to_le_bytes()); // version made by
+            out.extend_from_slice(&20u16.to_le_bytes()); // version needed
+            out.extend_from_slice(&0u16.to_le_bytes()); // flags
+            out.extend_from_slice(&0u16.to_le_bytes()); // compression: stored
+            out.extend_from_slice(&0u16.to_le_bytes()); // mod time
+            out.extend_from_slice(&0u16.to_le_bytes()); // mod date
+            out.extend_from_slice(&0u32.to_le_bytes()); // crc32
+            out.extend_from_slice(&(data.len() as u32).to_le_bytes()); // compressed size
+            out.extend_from_slice(&(data.len() as u32).to_le_bytes()); // uncompressed size
+            out.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            out.extend_from_slice(&0u16.to_le_bytes()); // extra len
+            out.extend_from_slice(&0u16.to_le_bytes()); // comment len
+            out.extend_from_slice(&0u16.to_le_bytes()); // disk number start
+            out.extend_from_slice(&0u16.to_le_bytes()); // internal attrs
+            out.extend_from_slice(&0u32.to_le_bytes()); // external attrs
+            out.extend_from_slice(&local_offset.to_le_bytes());
+            out.extend_from_slice(name.as_bytes());
+        }
+        let cd_size = (out.len() - cd_start) as u32;
+
+        out.extend_from_slice(&ZIP_EOCD_SIGNATURE);
+        out.extend_from_slice(&0u16.to_le_bytes()); // disk number
+        out.extend_from_slice(&0u16.to_le_bytes()); // disk with cd
+        out.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+        out.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+        out.extend_from_slice(&cd_size.to_le_bytes());
+        out.extend_from_slice(&(cd_start as u32).to_le_bytes());
+        out.extend_from_slice(&0u16.to_le_bytes()); // comment len
+
+        out
+    }
+
+    #[test]
+    fn test_refine_zip_mime_detects_docx_by_member_name() {
+        let zip = build_test_zip(&[("word/document.xml", b"<xml/>"), ("[Content_Types].xml", b"<xml/>")]);
+        assert_eq!(
+            refine_zip_mime(&zip),
+            Some("application/vnd.openxmlformats-officedocument.wordprocessingml.document".to_string())
+        );
+    }
+
+    #[test]
+    fn test_refine_zip_mime_detects_xlsx_by_member_name() {
+        let zip = build_test_zip(&[("xl/workbook.xml", b"<xml/>")]);
+        assert_eq!(
+            refine_zip_mime(&zip),
+            Some("application/vnd.openxmlformats-officedocument.spreadsheetml.sheet".to_string())
+        );
+    }
+
+    #[test]
+    fn test_refine_zip_mime_detects_pptx_by_member_prefix() {
+        let zip = build_test_zip(&[("ppt/presentation.xml", b"<xml/>")]);
+        assert_eq!(
+            refine_zip_mime(&zip),
+            Some("application/vnd.openxmlformats-officedocument.presentationml.presentation".to_string())
+        );
+    }
+
+    #[test]
+    fn test_refine_zip_mime_detects_epub_from_stored_mimetype_entry() {
+        let zip = build_test_zip(&[("mimetype", b"application/epub+zip"), ("META-INF/container.xml", b"<xml/>")]);
+        assert_eq!(refine_zip_mime(&zip), Some("application/epub+zip".to_string()));
+    }
+
+    #[test]
+    fn test_refine_zip_mime_detects_odt_from_stored_mimetype_entry() {
+        let zip = build_test_zip(&[("mimetype", b"application/vnd.oasis.opendocument.text")]);
+        assert_eq!(refine_zip_mime(&zip), Some("application/vnd.oasis.opendocument.text".to_string()));
+    }
+
+    #[test]
+    fn test_refine_zip_mime_falls_back_to_none_for_plain_zip() {
+        let zip = build_test_zip(&[("readme.txt", b"hello")]);
+        assert_eq!(refine_zip_mime(&zip), None);
+    }
+
+    #[test]
+    fn test_refine_container_mime_falls_back_to_initial_when_no_member_matches() {
+        let zip = build_test_zip(&[("readme.txt", b"hello")]);
+        assert_eq!(refine_container_mime(&zip, "application/zip"), "application/zip");
+    }
+
+    #[test]
+    fn test_refine_container_mime_reclassifies_docx() {
+        let zip = build_test_zip(&[("word/document.xml", b"<xml/>")]);
+        assert_eq!(
+            refine_container_mime(&zip, "application/zip"),
+            "application/vnd.openxmlformats-officedocument.wordprocessingml.document"
+        );
+    }
+
+    #[test]
+    fn test_refine_container_mime_promotes_xml_with_svg_root_to_svg() {
+        let svg = br#"<?xml version="1.0"?><svg xmlns="http://www.w3.org/2000/svg"></svg>"#;
+        assert_eq!(refine_container_mime(svg, "text/xml"), "image/svg+xml");
+    }
+
+    #[test]
+    fn test_refine_container_mime_leaves_plain_xml_unchanged() {
+        let xml = br#"<?xml version="1.0"?><root></root>"#;
+        assert_eq!(refine_container_mime(xml, "application/xml"), "application/xml");
+    }
+
+    #[test]
+    fn test_is_svg_root_ignores_svg_mention_past_scan_limit() {
+        let mut bytes = vec![b' '; XML_SVG_SCAN_LIMIT];
+        bytes.extend_from_slice(b"<svg");
+        assert!(!is_svg_root(&bytes));
+    }
+
+    #[test]
+    fn test_mime_mismatch_reports_match_when_extension_agrees_with_content() {
+        let mismatch = mime_mismatch("document.pdf", b"%PDF-1.7").unwrap();
+        assert!(mismatch.matches());
+        assert_eq!(mismatch.declared_mime(), Some("application/pdf".to_string()));
+        assert_eq!(mismatch.detected_mime(), "application/pdf");
+        assert_eq!(mismatch.recommended_extension(), None);
+    }
+
+    #[test]
+    fn test_mime_mismatch_suggests_rename_when_content_disagrees_with_extension() {
+        let mismatch = mime_mismatch("report.txt", b"%PDF-1.7").unwrap();
+        assert!(!mismatch.matches());
+        assert_eq!(mismatch.declared_mime(), Some("text/plain".to_string()));
+        assert_eq!(mismatch.detected_mime(), "application/pdf");
+        assert_eq!(mismatch.recommended_extension(), Some("pdf".to_string()));
+    }
+
+    #[test]
+    fn test_mime_mismatch_has_no_declared_mime_for_unknown_extension() {
+        let mismatch = mime_mismatch("archive.xyz", b"%PDF-1.7").unwrap();
+        assert_eq!(mismatch.declared_mime(), None);
+        assert!(!mismatch.matches());
+    }
+
+    #[test]
+    fn test_register_mime_type_is_consulted_by_get_mime_from_extension() {
+        reset_mime_registry();
+        register_mime_type("application/vnd.api+json".to_string(), vec!["json-api".to_string()]);
+
+        assert_eq!(
+            get_mime_from_extension("json-api".to_string()),
+            Some("application/vnd.api+json".to_string())
+        );
+        assert_eq!(
+            get_mime_from_extension(".JSON-API".to_string()),
+            Some("application/vnd.api+json".to_string())
+        );
+
+        reset_mime_registry();
+    }
+
+    #[test]
+    fn test_registered_extensions_merge_with_builtin_for_same_mime_type() {
+        reset_mime_registry();
+        register_mime_type("application/json".to_string(), vec!["jsonl".to_string()]);
+
+        let mut extensions = extensions_for_mime("application/json");
+        extensions.sort();
+        assert_eq!(extensions, vec!["json".to_string(), "jsonl".to_string()]);
+
+        reset_mime_registry();
+    }
+
+    #[test]
+    fn test_unregister_mime_type_reverts_to_builtin_lookup() {
+        reset_mime_registry();
+        register_mime_type("application/json".to_string(), vec!["jsonl".to_string()]);
+        unregister_mime_type("application/json".to_string());
+
+        assert_eq!(extensions_for_mime("application/json"), vec!["json".to_string()]);
+
+        reset_mime_registry();
+    }
+
+    #[test]
+    fn test_reset_mime_registry_clears_all_custom_entries() {
+        register_mime_type("application/vnd.api+json".to_string(), vec!["json-api".to_string()]);
+        reset_mime_registry();
+
+        assert_eq!(get_mime_from_extension("json-api".to_string()), None);
+    }
+
+    #[test]
+    fn test_parse_mime_type_splits_type_and_subtype() {
+        let parsed = parse_mime_type_parts("text/plain");
+        assert_eq!(parsed.r#type(), "text");
+        assert_eq!(parsed.subtype(), "plain");
+        assert_eq!(parsed.suffix(), None);
+        assert!(parsed.parameters.is_empty());
+    }
+
+    #[test]
+    fn test_parse_mime_type_detects_structured_suffix() {
+        let parsed = parse_mime_type_parts("application/vnd.api+json");
+        assert_eq!(parsed.r#type(), "application");
+        assert_eq!(parsed.subtype(), "vnd.api");
+        assert_eq!(parsed.suffix(), Some("json".to_string()));
+    }
+
+    #[test]
+    fn test_parse_mime_type_detects_svg_suffix() {
+        let parsed = parse_mime_type_parts("image/svg+xml");
+        assert_eq!(parsed.subtype(), "svg");
+        assert_eq!(parsed.suffix(), Some("xml".to_string()));
+    }
+
+    #[test]
+    fn test_parse_mime_type_parses_charset_parameter() {
+        let parsed = parse_mime_type_parts("text/html; charset=utf-8");
+        assert_eq!(parsed.r#type(), "text");
+        assert_eq!(parsed.subtype(), "html");
+        assert_eq!(parsed.parameters.get("charset"), Some(&"utf-8".to_string()));
+    }
+
+    #[test]
+    fn test_parse_mime_type_parses_multiple_parameters_and_quoted_values() {
+        let parsed = parse_mime_type_parts(r#"text/plain; charset="us-ascii"; boundary=xyz"#);
+        assert_eq!(parsed.parameters.get("charset"), Some(&"us-ascii".to_string()));
+        assert_eq!(parsed.parameters.get("boundary"), Some(&"xyz".to_string()));
+    }
+
+    #[test]
+    fn test_parse_mime_type_lowercases_type_subtype_and_parameter_keys() {
+        let parsed = parse_mime_type_parts("Text/HTML; Charset=UTF-8");
+        assert_eq!(parsed.r#type(), "text");
+        assert_eq!(parsed.subtype(), "html");
+        assert_eq!(parsed.parameters.get("charset"), Some(&"UTF-8".to_string()));
+    }
+
+    #[test]
+    fn test_parse_mime_type_handles_opendocument_subtype_without_suffix() {
+        let parsed = parse_mime_type_parts("application/vnd.oasis.opendocument.text");
+        assert_eq!(parsed.subtype(), "vnd.oasis.opendocument.text");
+        assert_eq!(parsed.suffix(), None);
+    }
 }
@@ -0,0 +1,219 @@
+//! PDF page rendering and security analysis for WASM bindings.
+//!
+//! Wraps [`kreuzberg::pdf::render::render_pdf_pages`] so browser consumers
+//! can generate page previews/thumbnails without shelling out to an
+//! external renderer, and [`kreuzberg::pdf::security::analyze_pdf_security`]
+//! so they can get a lightweight active-content signal before trusting a
+//! document.
+
+use crate::errors::convert_error;
+use js_sys::{Array, Uint8Array};
+use kreuzberg::pdf::render::{RenderEncoding, RenderOptions, RenderRotation, render_pdf_pages};
+use kreuzberg::pdf::security::analyze_pdf_security;
+use wasm_bindgen::prelude::*;
+
+/// Build a [`RenderOptions`] from the primitive, JS-friendly parameters
+/// accepted by [`render_pdf_pages_wasm`]. Kept free of `js_sys` types so it
+/// can be unit tested natively.
+fn build_render_options(
+    target_width: Option<u32>,
+    maximum_height: Option<u32>,
+    dpi: Option<f32>,
+    first_page: Option<u32>,
+    last_page: Option<u32>,
+    rotation_degrees: Option<u16>,
+    jpeg_quality: Option<u8>,
+    password: Option<String>,
+) -> RenderOptions {
+    let rotation = match rotation_degrees.unwrap_or(0) {
+        90 => RenderRotation::Rotate90,
+        180 => RenderRotation::Rotate180,
+        270 => RenderRotation::Rotate270,
+        _ => RenderRotation::None,
+    };
+
+    let encoding = match jpeg_quality {
+        Some(quality) => RenderEncoding::Jpeg { quality },
+        None => RenderEncoding::Png,
+    };
+
+    let page_range = match (first_page, last_page) {
+        (None, None) => None,
+        (first, last) => Some(first.unwrap_or(0) as u16..last.map_or(u16::MAX, |v| v as u16)),
+    };
+
+    RenderOptions {
+        target_width: target_width.map(|v| v as i32),
+        maximum_height: maximum_height.map(|v| v as i32),
+        dpi,
+        page_range,
+        rotation,
+        encoding,
+        password,
+    }
+}
+
+/// Render a PDF's pages to images and return them as a JS array of `Uint8Array`.
+///
+/// # JavaScript Parameters
+///
+/// * `data: Uint8Array` - The PDF document's bytes
+/// * `targetWidth?: number` - Target width in pixels (default 1024, ignored if `dpi` is set)
+/// * `maximumHeight?: number` - Maximum height in pixels
+/// * `dpi?: number` - Render at a fixed DPI instead of a pixel target
+/// * `firstPage?: number` - First zero-based page index to render (default 0)
+/// * `lastPage?: number` - One past the last page index to render (default: last page)
+/// * `rotationDegrees?: number` - Page rotation: 0, 90, 180, or 270
+/// * `jpegQuality?: number` - Encode as JPEG at this quality (1-100) instead of PNG
+/// * `password?: string` - Password for an encrypted document. A missing or
+///   incorrect password for an encrypted document rejects with a
+///   `"PasswordRequiredError"` so callers can prompt and retry.
+#[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
+pub fn render_pdf_pages_wasm(
+    data: Uint8Array,
+    target_width: Option<u32>,
+    maximum_height: Option<u32>,
+    dpi: Option<f32>,
+    first_page: Option<u32>,
+    last_page: Option<u32>,
+    rotation_degrees: Option<u16>,
+    jpeg_quality: Option<u8>,
+    password: Option<String>,
+) -> Result<Array, JsValue> {
+    let bytes = data.to_vec();
+    let options = build_render_options(
+        target_width,
+        maximum_height,
+        dpi,
+        first_page,
+        last_page,
+        rotation_degrees,
+        jpeg_quality,
+        password,
+    );
+
+    let pages = render_pdf_pages(&bytes, &options).map_err(|e| convert_error(e.into()))?;
+
+    let result = Array::new();
+    for page in pages {
+        result.push(&Uint8Array::from(page.bytes.as_slice()));
+    }
+
+    Ok(result)
+}
+
+/// Active-content indicators found in a PDF, returned by
+/// [`analyze_pdf_security_wasm`].
+#[wasm_bindgen]
+pub struct PdfSecurityReport {
+    has_javascript: bool,
+    has_open_action: bool,
+    embedded_files: u32,
+    launch_actions: u32,
+    scripts: Vec<String>,
+}
+
+#[wasm_bindgen]
+impl PdfSecurityReport {
+    /// Whether the document contains a `/JavaScript` name-tree entry or a `/JS` action.
+    #[wasm_bindgen(getter)]
+    pub fn has_javascript(&self) -> bool {
+        self.has_javascript
+    }
+
+    /// Whether the document's catalog has an `/OpenAction` entry.
+    #[wasm_bindgen(getter)]
+    pub fn has_open_action(&self) -> bool {
+        self.has_open_action
+    }
+
+    /// Number of `/EmbeddedFile` markers found.
+    #[wasm_bindgen(getter)]
+    pub fn embedded_files(&self) -> u32 {
+        self.embedded_files
+    }
+
+    /// Number of `/Launch` actions found.
+    #[wasm_bindgen(getter)]
+    pub fn launch_actions(&self) -> u32 {
+        self.launch_actions
+    }
+
+    /// Extracted `/JS` action script bodies, as a JS array of strings.
+    #[wasm_bindgen(getter)]
+    pub fn scripts(&self) -> Array {
+        self.scripts.iter().map(|s| JsValue::from_str(s)).collect()
+    }
+}
+
+/// Analyze a PDF document for active-content indicators (embedded
+/// JavaScript, auto-run open actions, launch actions, embedded files).
+///
+/// # JavaScript Parameters
+///
+/// * `data: Uint8Array` - The PDF document's bytes
+/// * `password?: string` - Password for an encrypted document
+#[wasm_bindgen]
+pub fn analyze_pdf_security_wasm(data: Uint8Array, password: Option<String>) -> Result<PdfSecurityReport, JsValue> {
+    let bytes = data.to_vec();
+    let report = analyze_pdf_security(&bytes, password.as_deref()).map_err(|e| convert_error(e.into()))?;
+
+    Ok(PdfSecurityReport {
+        has_javascript: report.has_javascript,
+        has_open_action: report.has_open_action,
+        embedded_files: report.embedded_files as u32,
+        launch_actions: report.launch_actions as u32,
+        scripts: report.scripts,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_render_options_defaults_to_png_and_full_page_range() {
+        let options = build_render_options(None, None, None, None, None, None, None, None);
+
+        assert_eq!(options.target_width, None);
+        assert_eq!(options.page_range, None);
+        assert_eq!(options.rotation, RenderRotation::None);
+        assert_eq!(options.encoding, RenderEncoding::Png);
+        assert_eq!(options.password, None);
+    }
+
+    #[test]
+    fn test_build_render_options_passes_through_password() {
+        let options = build_render_options(None, None, None, None, None, None, None, Some("hunter2".to_string()));
+        assert_eq!(options.password.as_deref(), Some("hunter2"));
+    }
+
+    #[test]
+    fn test_build_render_options_maps_rotation_degrees() {
+        assert_eq!(
+            build_render_options(None, None, None, None, None, Some(90), None, None).rotation,
+            RenderRotation::Rotate90
+        );
+        assert_eq!(
+            build_render_options(None, None, None, None, None, Some(270), None, None).rotation,
+            RenderRotation::Rotate270
+        );
+        assert_eq!(
+            build_render_options(None, None, None, None, None, Some(45), None, None).rotation,
+            RenderRotation::None
+        );
+    }
+
+    #[test]
+    fn test_build_render_options_sets_jpeg_encoding_with_quality() {
+        let options = build_render_options(None, None, None, None, None, None, Some(75), None);
+        assert_eq!(options.encoding, RenderEncoding::Jpeg { quality: 75 });
+    }
+
+    #[test]
+    fn test_build_render_options_builds_page_range_from_first_and_last() {
+        let options = build_render_options(None, None, None, Some(2), Some(5), None, None, None);
+        assert_eq!(options.page_range, Some(2..5));
+    }
+}
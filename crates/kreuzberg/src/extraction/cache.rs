@@ -0,0 +1,376 @@
+//! Content-addressed cache for extraction results.
+//!
+//! Keyed on a hash of the input bytes plus a caller-supplied fingerprint of
+//! whatever in the effective [`crate::core::config::ExtractionConfig`]
+//! would change the output (Lua filters, output format, and so on), so
+//! re-running [`crate::extraction::pandoc::PandocExtractor`] over the same
+//! document under the same config is a cache hit instead of another
+//! `pandoc` invocation. The determinism the RTF parity tests already
+//! assert (`extract_file` run twice on the same input produces identical
+//! output) is exactly what makes this safe to cache at all.
+
+use crate::types::{ExtractionResult, Metadata};
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+/// FNV-1a, 64-bit: a small, dependency-free, non-cryptographic hash.
+/// Good enough for a cache key - a collision just means a stale-looking
+/// cache hit, not a security issue - without pulling in a hashing crate
+/// just for this.
+fn fnv1a_64(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+    let mut hash = OFFSET_BASIS;
+    for &byte in bytes {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(PRIME);
+    }
+    hash
+}
+
+/// A content-addressed cache key: the input bytes hashed together with a
+/// fingerprint of the config that affects how they're extracted. Two calls
+/// with an equal key are expected to produce an equal [`ExtractionResult`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CacheKey(String);
+
+impl CacheKey {
+    /// Build a key from raw input bytes plus a config fingerprint (any
+    /// string that changes whenever something relevant to the output
+    /// changes - `format!("{config:?}")` is the easiest choice for configs
+    /// that derive `Debug`).
+    pub fn new(content: &[u8], config_fingerprint: &str) -> Self {
+        let content_hash = fnv1a_64(content);
+        let config_hash = fnv1a_64(config_fingerprint.as_bytes());
+        Self(format!("{:016x}-{:016x}", content_hash, config_hash))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Where an [`ExtractionCache`] stores and retrieves cached results.
+/// Implementations must be safe to share across extraction calls running
+/// concurrently.
+pub trait ExtractionCacheStore: Send + Sync {
+    fn get(&self, key: &CacheKey) -> Option<ExtractionResult>;
+    fn put(&self, key: CacheKey, result: ExtractionResult);
+    fn invalidate(&self, key: &CacheKey);
+    fn clear(&self);
+}
+
+/// Front door for extraction caching: wraps a pluggable [`ExtractionCacheStore`]
+/// with a bypass switch, so callers can disable caching for one call (or
+/// globally, e.g. while debugging) without tearing down the store.
+pub struct ExtractionCache {
+    store: Box<dyn ExtractionCacheStore>,
+    bypassed: AtomicBool,
+}
+
+impl std::fmt::Debug for ExtractionCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExtractionCache").field("bypassed", &self.is_bypassed()).finish()
+    }
+}
+
+impl ExtractionCache {
+    pub fn new(store: impl ExtractionCacheStore + 'static) -> Self {
+        Self { store: Box::new(store), bypassed: AtomicBool::new(false) }
+    }
+
+    /// Disable (or re-enable) reads and writes without losing the store's
+    /// existing contents.
+    pub fn set_bypass(&self, bypassed: bool) {
+        self.bypassed.store(bypassed, Ordering::Relaxed);
+    }
+
+    pub fn is_bypassed(&self) -> bool {
+        self.bypassed.load(Ordering::Relaxed)
+    }
+
+    pub fn get(&self, key: &CacheKey) -> Option<ExtractionResult> {
+        if self.is_bypassed() {
+            return None;
+        }
+        self.store.get(key)
+    }
+
+    pub fn put(&self, key: CacheKey, result: ExtractionResult) {
+        if self.is_bypassed() {
+            return;
+        }
+        self.store.put(key, result);
+    }
+
+    pub fn invalidate(&self, key: &CacheKey) {
+        self.store.invalidate(key);
+    }
+
+    pub fn clear(&self) {
+        self.store.clear();
+    }
+}
+
+struct LruState {
+    map: HashMap<CacheKey, ExtractionResult>,
+    order: VecDeque<CacheKey>,
+}
+
+/// Bounded in-memory LRU [`ExtractionCacheStore`]. Eviction drops the
+/// least-recently-used entry once `capacity` is exceeded.
+pub struct InMemoryCacheStore {
+    capacity: usize,
+    state: Mutex<LruState>,
+}
+
+impl InMemoryCacheStore {
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity, state: Mutex::new(LruState { map: HashMap::new(), order: VecDeque::new() }) }
+    }
+
+    pub fn len(&self) -> usize {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl ExtractionCacheStore for InMemoryCacheStore {
+    fn get(&self, key: &CacheKey) -> Option<ExtractionResult> {
+        let mut state = self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let result = state.map.get(key).cloned()?;
+        state.order.retain(|k| k != key);
+        state.order.push_back(key.clone());
+        Some(result)
+    }
+
+    fn put(&self, key: CacheKey, result: ExtractionResult) {
+        let mut state = self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if state.map.contains_key(&key) {
+            state.order.retain(|k| k != &key);
+        } else if state.map.len() >= self.capacity
+            && let Some(oldest) = state.order.pop_front()
+        {
+            state.map.remove(&oldest);
+        }
+        state.order.push_back(key.clone());
+        state.map.insert(key, result);
+    }
+
+    fn invalidate(&self, key: &CacheKey) {
+        let mut state = self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        state.map.remove(key);
+        state.order.retain(|k| k != key);
+    }
+
+    fn clear(&self) {
+        let mut state = self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        state.map.clear();
+        state.order.clear();
+    }
+}
+
+/// On-disk [`ExtractionCacheStore`]: one JSON file per cache entry, named
+/// after the [`CacheKey`], in a fixed directory. Unbounded - the caller
+/// owns pruning the directory if it grows too large.
+pub struct DirectoryCacheStore {
+    dir: PathBuf,
+}
+
+impl DirectoryCacheStore {
+    pub fn new(dir: impl Into<PathBuf>) -> std::io::Result<Self> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+
+    fn entry_path(&self, key: &CacheKey) -> PathBuf {
+        self.dir.join(format!("{}.json", key.as_str()))
+    }
+}
+
+impl ExtractionCacheStore for DirectoryCacheStore {
+    fn get(&self, key: &CacheKey) -> Option<ExtractionResult> {
+        let bytes = std::fs::read(self.entry_path(key)).ok()?;
+        let value: Value = serde_json::from_slice(&bytes).ok()?;
+        extraction_result_from_json(&value)
+    }
+
+    fn put(&self, key: CacheKey, result: ExtractionResult) {
+        let value = extraction_result_to_json(&result);
+        if let Ok(bytes) = serde_json::to_vec(&value) {
+            let _ = std::fs::write(self.entry_path(&key), bytes);
+        }
+    }
+
+    fn invalidate(&self, key: &CacheKey) {
+        let _ = std::fs::remove_file(self.entry_path(key));
+    }
+
+    fn clear(&self) {
+        let Ok(entries) = std::fs::read_dir(&self.dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let _ = std::fs::remove_file(entry.path());
+        }
+    }
+}
+
+/// Hand-rolled (de)serialization for [`ExtractionResult`], since it isn't
+/// itself `serde`-derived: just enough to round-trip every field the
+/// Pandoc extraction path populates.
+fn extraction_result_to_json(result: &ExtractionResult) -> Value {
+    serde_json::json!({
+        "content": result.content,
+        "mime_type": result.mime_type,
+        "metadata_additional": result.metadata.additional,
+        "metadata_date": result.metadata.date,
+        "tables": result.tables,
+        "detected_languages": result.detected_languages,
+        "chunks": result.chunks,
+        "images": result.images,
+    })
+}
+
+fn extraction_result_from_json(value: &Value) -> Option<ExtractionResult> {
+    let content = value.get("content")?.as_str()?.to_string();
+    let mime_type = value.get("mime_type")?.as_str()?.to_string();
+    let additional = value
+        .get("metadata_additional")
+        .and_then(Value::as_object)
+        .map(|obj| obj.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
+        .unwrap_or_default();
+    let date = value.get("metadata_date").and_then(Value::as_str).map(str::to_string);
+    let tables = value.get("tables").and_then(Value::as_array).cloned().unwrap_or_default();
+    let detected_languages = value.get("detected_languages").and_then(Value::as_array).map(|langs| {
+        langs.iter().filter_map(|v| v.as_str().map(str::to_string)).collect()
+    });
+    let chunks = value.get("chunks").and_then(Value::as_array).cloned();
+    let images = value.get("images").and_then(Value::as_array).cloned();
+
+    Some(ExtractionResult {
+        content,
+        mime_type,
+        metadata: Metadata { additional, date, ..Default::default() },
+        tables,
+        detected_languages,
+        chunks,
+        images,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_key_is_stable_for_same_inputs() {
+        let a = CacheKey::new(b"hello", "config-v1");
+        let b = CacheKey::new(b"hello", "config-v1");
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_cache_key_differs_by_content() {
+        let a = CacheKey::new(b"hello", "config-v1");
+        let b = CacheKey::new(b"goodbye", "config-v1");
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_cache_key_differs_by_config_fingerprint() {
+        let a = CacheKey::new(b"hello", "config-v1");
+        let b = CacheKey::new(b"hello", "config-v2");
+        assert_ne!(a, b);
+    }
+
+    fn sample_result(content: &str) -> ExtractionResult {
+        ExtractionResult {
+            content: content.to_string(),
+            mime_type: "text/plain".to_string(),
+            metadata: Metadata::default(),
+            tables: vec![],
+            detected_languages: None,
+            chunks: None,
+            images: None,
+        }
+    }
+
+    #[test]
+    fn test_in_memory_cache_store_round_trips() {
+        let store = InMemoryCacheStore::new(10);
+        let key = CacheKey::new(b"doc", "cfg");
+        assert!(store.get(&key).is_none());
+
+        store.put(key.clone(), sample_result("hello"));
+        assert_eq!(store.get(&key).unwrap().content, "hello");
+    }
+
+    #[test]
+    fn test_in_memory_cache_store_evicts_least_recently_used() {
+        let store = InMemoryCacheStore::new(2);
+        let a = CacheKey::new(b"a", "cfg");
+        let b = CacheKey::new(b"b", "cfg");
+        let c = CacheKey::new(b"c", "cfg");
+
+        store.put(a.clone(), sample_result("a"));
+        store.put(b.clone(), sample_result("b"));
+        store.get(&a); // touch `a` so `b` becomes least-recently-used
+        store.put(c.clone(), sample_result("c"));
+
+        assert!(store.get(&a).is_some());
+        assert!(store.get(&b).is_none());
+        assert!(store.get(&c).is_some());
+    }
+
+    #[test]
+    fn test_in_memory_cache_store_invalidate_and_clear() {
+        let store = InMemoryCacheStore::new(10);
+        let key = CacheKey::new(b"doc", "cfg");
+        store.put(key.clone(), sample_result("hello"));
+
+        store.invalidate(&key);
+        assert!(store.get(&key).is_none());
+
+        store.put(key.clone(), sample_result("hello again"));
+        store.clear();
+        assert!(store.is_empty());
+    }
+
+    #[test]
+    fn test_extraction_cache_bypass_disables_get_and_put() {
+        let cache = ExtractionCache::new(InMemoryCacheStore::new(10));
+        let key = CacheKey::new(b"doc", "cfg");
+
+        cache.set_bypass(true);
+        cache.put(key.clone(), sample_result("hello"));
+        assert!(cache.get(&key).is_none());
+
+        cache.set_bypass(false);
+        cache.put(key.clone(), sample_result("hello"));
+        assert_eq!(cache.get(&key).unwrap().content, "hello");
+    }
+
+    #[test]
+    fn test_directory_cache_store_round_trips() {
+        let dir = std::env::temp_dir().join(format!("kreuzberg-cache-test-{}", std::process::id()));
+        let store = DirectoryCacheStore::new(&dir).expect("should create cache dir");
+        let key = CacheKey::new(b"doc", "cfg");
+
+        assert!(store.get(&key).is_none());
+        store.put(key.clone(), sample_result("hello"));
+        assert_eq!(store.get(&key).unwrap().content, "hello");
+
+        store.invalidate(&key);
+        assert!(store.get(&key).is_none());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
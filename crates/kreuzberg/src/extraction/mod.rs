@@ -0,0 +1,9 @@
+//! Extraction-pipeline infrastructure shared across document extractors.
+//!
+//! This is distinct from [`crate::extractors`], which holds the
+//! [`crate::plugins::DocumentExtractor`] implementations themselves: this
+//! module holds the shared machinery some of those extractors (and the
+//! Pandoc fallback that sits underneath the native ones) depend on.
+
+pub mod cache;
+pub mod pandoc;
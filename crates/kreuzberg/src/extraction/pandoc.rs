@@ -0,0 +1,1650 @@
+//! Pandoc-backed extraction.
+//!
+//! Several formats (RTF, DOCX, ODT, and others added over time) are
+//! extracted by shelling out to [Pandoc](https://pandoc.org) and walking its
+//! output rather than hand-rolling a parser. Earlier revisions of this
+//! extractor asked Pandoc for its plain Markdown writer (`-t markdown`),
+//! which is lossy for structured content: Pandoc's Markdown table writer in
+//! particular drops cell content it can't render as a clean grid.
+//!
+//! This module instead asks Pandoc for its native JSON AST (`-t json`) and
+//! walks that directly, so constructs the Markdown writer mangles - tables
+//! above all - survive intact.
+//!
+//! The native Rust extractors in [`crate::extractors`] (RTF, EPUB, LaTeX)
+//! take priority over [`PandocExtractor`] for the formats they cover; this
+//! extractor exists for everything else Pandoc can read, and as an explicit
+//! fallback for formats that do have a native extractor (see
+//! `RtfExtractor`'s `priority` of 50 vs. this extractor's 40).
+
+use crate::Result;
+use crate::core::config::ExtractionConfig;
+use crate::plugins::{DocumentExtractor, Plugin};
+use crate::types::{ExtractionResult, Metadata};
+use crate::KreuzbergError;
+use async_trait::async_trait;
+use serde_json::Value;
+use std::path::PathBuf;
+use std::process::Stdio;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+/// Per-invocation options for the Pandoc extraction path: filters to apply
+/// between the reader and writer (in order), the reader extensions that
+/// control how input is parsed, the Markdown extensions Pandoc's Markdown
+/// *writer* should honor when producing `content` in Markdown form (see
+/// [`MarkdownExtensions`]), and the line-ending convention the final text
+/// should be normalized to.
+///
+/// Surfaced on `ExtractionConfig` as `config.pandoc`, so callers can
+/// normalize output (e.g. dropping footnotes, rewriting links) without
+/// forking the crate.
+#[derive(Debug, Clone, Default)]
+pub struct PandocConfig {
+    /// Filters applied to the AST between the reader and writer, in order.
+    pub filters: Vec<PandocFilter>,
+    /// Reader-side extensions (e.g. `smart`, `raw_tex`) controlling how
+    /// Pandoc parses the input.
+    pub reader_extensions: ReaderExtensions,
+    /// Markdown writer extensions controlling how `content` is rendered
+    /// when the Markdown (rather than JSON-AST/table) path is used.
+    pub markdown_extensions: MarkdownExtensions,
+    /// Line-ending convention the final extracted text is normalized to.
+    pub line_ending: LineEnding,
+    /// Form the `content` field of the extraction result takes.
+    pub output_format: OutputFormat,
+    /// When set, footnotes (`Note` AST nodes) and citations (`Cite` AST
+    /// nodes) are pulled out of the body text and surfaced as structured
+    /// data instead (see [`collect_footnotes_and_citations`]), with an
+    /// in-text `[^N]` marker left in `content` in their place.
+    pub extract_footnotes: bool,
+    /// Formats with a native Rust extractor (currently just RTF, via
+    /// `RtfExtractor`) use it by default and never shell out to Pandoc.
+    /// Setting this routes those formats through Pandoc instead - useful to
+    /// diff the two backends, or to pick up a Pandoc-only feature (Lua
+    /// filters, citeproc) this crate's native parser doesn't implement.
+    pub prefer_for_rtf: bool,
+    /// Optional content-addressed cache (see [`crate::extraction::cache`]).
+    /// When set, [`PandocExtractor`] checks it before invoking Pandoc and
+    /// populates it afterwards, keyed on the input bytes plus this config.
+    pub cache: Option<std::sync::Arc<crate::extraction::cache::ExtractionCache>>,
+}
+
+impl PandocConfig {
+    /// Check that every configured filter file exists, so a typo'd path
+    /// fails fast with a clear message instead of as an opaque Pandoc error.
+    fn validate_filters(&self) -> Result<()> {
+        for filter in &self.filters {
+            let path = filter.path();
+            if !path.is_file() {
+                return Err(KreuzbergError::Validation {
+                    message: format!("Pandoc filter not found: {}", path.display()),
+                    source: None,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Append this config's filters to `command` in order, each as
+    /// `--lua-filter <path>` or `--filter <path>` depending on its kind.
+    fn apply_filters(&self, command: &mut Command) {
+        for filter in &self.filters {
+            match filter {
+                PandocFilter::Lua(path) => {
+                    command.arg("--lua-filter").arg(path);
+                }
+                PandocFilter::External(path) => {
+                    command.arg("--filter").arg(path);
+                }
+            }
+        }
+    }
+}
+
+/// A post-parse filter applied to the Pandoc AST between the reader and
+/// writer: either a Lua script (`--lua-filter`) or an external executable
+/// reading/writing the JSON AST on stdin/stdout (`--filter`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PandocFilter {
+    Lua(PathBuf),
+    External(PathBuf),
+}
+
+impl PandocFilter {
+    fn path(&self) -> &PathBuf {
+        match self {
+            Self::Lua(path) | Self::External(path) => path,
+        }
+    }
+}
+
+/// Line-ending convention the final extracted text should use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineEnding {
+    /// Leave whatever line endings the extraction pipeline produced as-is
+    /// (in practice, `\n`, since that's what Pandoc emits). Default.
+    #[default]
+    Native,
+    Lf,
+    Crlf,
+}
+
+impl LineEnding {
+    /// Normalize `text` to this line-ending convention. `Native` is a no-op.
+    pub fn normalize(self, text: &str) -> String {
+        match self {
+            Self::Native => text.to_string(),
+            Self::Lf => text.replace("\r\n", "\n"),
+            Self::Crlf => {
+                let lf_normalized = text.replace("\r\n", "\n");
+                lf_normalized.replace('\n', "\r\n")
+            }
+        }
+    }
+}
+
+/// Form the `content` field of a Pandoc-backed extraction result takes.
+///
+/// `PlainText` and `Markdown` both flatten the document, just through
+/// different writers (this crate's own AST walker vs. Pandoc's Markdown
+/// writer - see the module docs for why the former exists at all).
+/// `StructuredAst` instead keeps the full Pandoc JSON AST (headings, lists,
+/// tables, footnotes) as `content`'s raw JSON text, for callers that want
+/// to preserve structure `PlainText`/`Markdown` necessarily lose.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    /// This crate's own AST walker (see [`walk_document`]); the default,
+    /// and the form every test in this crate predates the other two.
+    #[default]
+    PlainText,
+    /// Pandoc's own Markdown writer (see [`convert_to_markdown_with_options`]).
+    Markdown,
+    /// The raw Pandoc JSON AST, serialized as `content`.
+    StructuredAst,
+}
+
+/// A single togglable Pandoc *reader* extension, e.g. `smart` (typographic
+/// quotes/dashes) or `raw_tex` (pass through raw TeX the reader encounters).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[allow(non_camel_case_types)]
+pub enum ReaderExtension {
+    Smart,
+    RawTex,
+}
+
+impl ReaderExtension {
+    fn token(self) -> &'static str {
+        match self {
+            Self::Smart => "smart",
+            Self::RawTex => "raw_tex",
+        }
+    }
+}
+
+/// The set of reader extensions enabled/disabled for whatever `-f <reader>`
+/// Pandoc is invoked with. Empty by default, matching Pandoc's own
+/// per-reader defaults unchanged.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReaderExtensions {
+    enabled: std::collections::HashSet<ReaderExtension>,
+    disabled: std::collections::HashSet<ReaderExtension>,
+}
+
+impl ReaderExtensions {
+    pub fn enable(mut self, extension: ReaderExtension) -> Self {
+        self.disabled.remove(&extension);
+        self.enabled.insert(extension);
+        self
+    }
+
+    pub fn disable(mut self, extension: ReaderExtension) -> Self {
+        self.enabled.remove(&extension);
+        self.disabled.insert(extension);
+        self
+    }
+
+    /// Build the `-f` reader suffix for these toggles, e.g. `"+smart-raw_tex"`.
+    /// Empty when no extensions are toggled, so `format!("{base}{suffix}")`
+    /// reproduces the bare reader name unchanged.
+    pub fn suffix(&self) -> String {
+        const ORDER: &[ReaderExtension] = &[ReaderExtension::Smart, ReaderExtension::RawTex];
+
+        let mut suffix = String::new();
+        for ext in ORDER {
+            if self.enabled.contains(ext) {
+                suffix.push('+');
+                suffix.push_str(ext.token());
+            } else if self.disabled.contains(ext) {
+                suffix.push('-');
+                suffix.push_str(ext.token());
+            }
+        }
+        suffix
+    }
+}
+
+/// A single togglable Pandoc Markdown extension, e.g. `+footnotes` or
+/// `-raw_html`. Named after (and spelled like) the extension names Pandoc
+/// itself uses on the command line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[allow(non_camel_case_types)]
+pub enum MarkdownExtension {
+    Footnotes,
+    PipeTables,
+    TexMathDollars,
+    RawHtml,
+    Smart,
+    Strikeout,
+    Superscript,
+    Subscript,
+}
+
+impl MarkdownExtension {
+    /// The token Pandoc expects after the `+`/`-` sign, e.g. `"pipe_tables"`.
+    fn token(self) -> &'static str {
+        match self {
+            Self::Footnotes => "footnotes",
+            Self::PipeTables => "pipe_tables",
+            Self::TexMathDollars => "tex_math_dollars",
+            Self::RawHtml => "raw_html",
+            Self::Smart => "smart",
+            Self::Strikeout => "strikeout",
+            Self::Superscript => "superscript",
+            Self::Subscript => "subscript",
+        }
+    }
+}
+
+/// The set of Markdown extensions enabled/disabled for Pandoc's Markdown
+/// writer. Defaults match Pandoc's own `markdown` format defaults plus
+/// explicit `footnotes`/`pipe_tables`, since those are the two this crate's
+/// tests rely on most (footnote references and reconstructed tables).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarkdownExtensions {
+    enabled: std::collections::HashSet<MarkdownExtension>,
+    disabled: std::collections::HashSet<MarkdownExtension>,
+}
+
+impl Default for MarkdownExtensions {
+    fn default() -> Self {
+        Self {
+            enabled: [MarkdownExtension::Footnotes, MarkdownExtension::PipeTables].into_iter().collect(),
+            disabled: std::collections::HashSet::new(),
+        }
+    }
+}
+
+impl MarkdownExtensions {
+    /// Start from no explicit toggles at all (Pandoc's bare `markdown` defaults).
+    pub fn empty() -> Self {
+        Self { enabled: std::collections::HashSet::new(), disabled: std::collections::HashSet::new() }
+    }
+
+    /// Enable `extension`, removing any prior disabling of it.
+    pub fn enable(mut self, extension: MarkdownExtension) -> Self {
+        self.disabled.remove(&extension);
+        self.enabled.insert(extension);
+        self
+    }
+
+    /// Disable `extension`, removing any prior enabling of it.
+    pub fn disable(mut self, extension: MarkdownExtension) -> Self {
+        self.enabled.remove(&extension);
+        self.disabled.insert(extension);
+        self
+    }
+
+    /// Build the `-t` target string Pandoc expects, e.g.
+    /// `"markdown+footnotes+pipe_tables-raw_html"`. Extensions are emitted
+    /// in a fixed, deterministic order so the generated command is stable
+    /// across runs (important for the cache key in
+    /// [`crate::extraction::pandoc`]'s content-addressed cache).
+    pub fn target_string(&self) -> String {
+        const ORDER: &[MarkdownExtension] = &[
+            MarkdownExtension::Footnotes,
+            MarkdownExtension::PipeTables,
+            MarkdownExtension::TexMathDollars,
+            MarkdownExtension::RawHtml,
+            MarkdownExtension::Smart,
+            MarkdownExtension::Strikeout,
+            MarkdownExtension::Superscript,
+            MarkdownExtension::Subscript,
+        ];
+
+        let mut target = String::from("markdown");
+        for ext in ORDER {
+            if self.enabled.contains(ext) {
+                target.push('+');
+                target.push_str(ext.token());
+            } else if self.disabled.contains(ext) {
+                target.push('-');
+                target.push_str(ext.token());
+            }
+        }
+        target
+    }
+}
+
+/// Pandoc-backed extractor: converts bytes to Pandoc's JSON AST and walks it
+/// to reconstruct content (and tables) with much less loss than Pandoc's own
+/// Markdown writer.
+pub struct PandocExtractor;
+
+impl PandocExtractor {
+    /// Create a new Pandoc-backed extractor.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for PandocExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Plugin for PandocExtractor {
+    fn name(&self) -> &str {
+        "pandoc-extractor"
+    }
+
+    fn version(&self) -> String {
+        env!("CARGO_PKG_VERSION").to_string()
+    }
+
+    fn initialize(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn shutdown(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn description(&self) -> &str {
+        "Extracts content from documents Pandoc can read, via its JSON AST"
+    }
+
+    fn author(&self) -> &str {
+        "Kreuzberg Team"
+    }
+}
+
+/// Check that a `pandoc` binary is on `PATH` and report its version string
+/// (the first line of `pandoc --version`, e.g. `"pandoc 3.1.11"`).
+///
+/// Tests and callers that want to skip gracefully when Pandoc isn't
+/// installed should treat an `Err` here as "not available" rather than a
+/// hard failure.
+pub async fn validate_pandoc_version() -> Result<String> {
+    let output = Command::new("pandoc")
+        .arg("--version")
+        .output()
+        .await
+        .map_err(|_| KreuzbergError::MissingDependency("pandoc".to_string()))?;
+
+    if !output.status.success() {
+        return Err(KreuzbergError::MissingDependency("pandoc".to_string()));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let first_line = stdout.lines().next().unwrap_or("").trim().to_string();
+    if first_line.is_empty() {
+        return Err(KreuzbergError::MissingDependency("pandoc".to_string()));
+    }
+    Ok(first_line)
+}
+
+/// Run `pandoc -f <from_format> -t json` over `bytes` and parse the result
+/// as a [`serde_json::Value`].
+///
+/// Pandoc's JSON AST is `{"pandoc-api-version", "meta", "blocks"}`; see
+/// [`parse_blocks`] for how `blocks` is interpreted.
+pub(crate) async fn convert_to_json(bytes: &[u8], from_format: &str) -> Result<Value> {
+    convert_to_json_with_options(bytes, from_format, &PandocConfig::default()).await
+}
+
+/// Like [`convert_to_json`], but applies `options.filters` (in order, as
+/// `--lua-filter`/`--filter`) and `options.reader_extensions`.
+///
+/// Lua runtime errors (a filter calling `error()`, or a malformed filter
+/// script) are reported as [`KreuzbergError::Plugin`] rather than the
+/// generic conversion failure used for other Pandoc invocation problems, so
+/// callers can distinguish "my filter is broken" from "Pandoc couldn't read
+/// this document".
+pub(crate) async fn convert_to_json_with_options(
+    bytes: &[u8],
+    from_format: &str,
+    options: &PandocConfig,
+) -> Result<Value> {
+    options.validate_filters()?;
+
+    let reader = format!("{from_format}{}", options.reader_extensions.suffix());
+    let mut command = Command::new("pandoc");
+    command.arg("-f").arg(&reader).arg("-t").arg("json");
+    options.apply_filters(&mut command);
+    command.stdin(Stdio::piped()).stdout(Stdio::piped()).stderr(Stdio::piped());
+
+    let mut child = command
+        .spawn()
+        .map_err(|_| KreuzbergError::MissingDependency("pandoc".to_string()))?;
+
+    {
+        let stdin = child
+            .stdin
+            .as_mut()
+            .ok_or_else(|| KreuzbergError::Other("Failed to open pandoc stdin".to_string()))?;
+        stdin
+            .write_all(bytes)
+            .await
+            .map_err(|e| KreuzbergError::Parsing { message: format!("Failed to write to pandoc stdin: {}", e), source: None })?;
+    }
+
+    let output = child
+        .wait_with_output()
+        .await
+        .map_err(|e| KreuzbergError::Parsing { message: format!("Failed to read pandoc output: {}", e), source: None })?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        if is_lua_filter_error(&stderr) {
+            return Err(KreuzbergError::Plugin {
+                message: stderr.trim().to_string(),
+                plugin_name: "pandoc-lua-filter".to_string(),
+            });
+        }
+        return Err(KreuzbergError::Parsing {
+            message: format!("pandoc exited with {}: {}", output.status, stderr.trim()),
+            source: None,
+        });
+    }
+
+    serde_json::from_slice(&output.stdout)
+        .map_err(|e| KreuzbergError::Parsing { message: format!("Failed to parse pandoc JSON output: {}", e), source: None })
+}
+
+/// Heuristic for whether Pandoc's stderr describes a Lua filter failure
+/// (e.g. `"Error running filter foo.lua: ..."` or a Lua traceback) rather
+/// than an unrelated conversion error.
+fn is_lua_filter_error(stderr: &str) -> bool {
+    let lower = stderr.to_lowercase();
+    lower.contains("lua filter") || lower.contains(".lua:") || lower.contains("lua error")
+}
+
+/// Run `pandoc -f <from_format> -t <options.markdown_extensions target>`
+/// over `bytes`, returning the raw Markdown Pandoc writes.
+///
+/// Unlike [`convert_to_json_with_options`], this goes through Pandoc's own
+/// Markdown writer rather than the JSON AST, so it's lossy for tables (see
+/// the module docs) but useful when a caller wants Pandoc's Markdown
+/// directly - e.g. to hand off to a downstream Markdown-aware pipeline
+/// stage instead of this crate's own AST walker.
+pub(crate) async fn convert_to_markdown_with_options(
+    bytes: &[u8],
+    from_format: &str,
+    options: &PandocConfig,
+) -> Result<String> {
+    options.validate_filters()?;
+
+    let reader = format!("{from_format}{}", options.reader_extensions.suffix());
+    let target = options.markdown_extensions.target_string();
+    let mut command = Command::new("pandoc");
+    command.arg("-f").arg(&reader).arg("-t").arg(&target);
+    options.apply_filters(&mut command);
+    command.stdin(Stdio::piped()).stdout(Stdio::piped()).stderr(Stdio::piped());
+
+    let mut child = command
+        .spawn()
+        .map_err(|_| KreuzbergError::MissingDependency("pandoc".to_string()))?;
+
+    {
+        let stdin = child
+            .stdin
+            .as_mut()
+            .ok_or_else(|| KreuzbergError::Other("Failed to open pandoc stdin".to_string()))?;
+        stdin
+            .write_all(bytes)
+            .await
+            .map_err(|e| KreuzbergError::Parsing { message: format!("Failed to write to pandoc stdin: {}", e), source: None })?;
+    }
+
+    let output = child
+        .wait_with_output()
+        .await
+        .map_err(|e| KreuzbergError::Parsing { message: format!("Failed to read pandoc output: {}", e), source: None })?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        if is_lua_filter_error(&stderr) {
+            return Err(KreuzbergError::Plugin {
+                message: stderr.trim().to_string(),
+                plugin_name: "pandoc-lua-filter".to_string(),
+            });
+        }
+        return Err(KreuzbergError::Parsing {
+            message: format!("pandoc exited with {}: {}", output.status, stderr.trim()),
+            source: None,
+        });
+    }
+
+    let markdown = String::from_utf8(output.stdout)
+        .map_err(|e| KreuzbergError::Parsing { message: format!("pandoc produced non-UTF-8 output: {}", e), source: None })?;
+    Ok(options.line_ending.normalize(&markdown))
+}
+
+/// A single cell of a [`Table`], already flattened to text.
+pub type TableCell = String;
+
+/// A table reconstructed from a Pandoc AST `Table` block: caption plus the
+/// header row(s) and body rows, each a list of flattened cell text.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Table {
+    pub caption: Option<String>,
+    pub column_count: usize,
+    pub header_rows: Vec<Vec<TableCell>>,
+    pub body_rows: Vec<Vec<TableCell>>,
+}
+
+impl Table {
+    /// Render this table as a GFM pipe table, e.g.:
+    ///
+    /// ```text
+    /// | A | B |
+    /// | --- | --- |
+    /// | 1 | 2 |
+    /// ```
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        if let Some(caption) = &self.caption
+            && !caption.is_empty()
+        {
+            out.push_str(caption);
+            out.push_str("\n\n");
+        }
+
+        let header = self
+            .header_rows
+            .first()
+            .cloned()
+            .unwrap_or_else(|| vec![String::new(); self.column_count]);
+
+        out.push_str("| ");
+        out.push_str(&header.join(" | "));
+        out.push_str(" |\n");
+        out.push_str("| ");
+        out.push_str(&vec!["---"; self.column_count.max(header.len())].join(" | "));
+        out.push_str(" |\n");
+
+        for row in &self.body_rows {
+            out.push_str("| ");
+            out.push_str(&row.join(" | "));
+            out.push_str(" |\n");
+        }
+
+        out.trim_end().to_string()
+    }
+}
+
+/// Walk a Pandoc JSON AST document's top-level `blocks` array, returning the
+/// reconstructed Markdown `content` plus every [`Table`] encountered.
+///
+/// Recognized block tags: `Header`, `Para`, `Plain`, and `Table`. Any other
+/// block tag's inline content (if it has one under `"c"`) is still flattened
+/// to text and appended, so unrecognized blocks degrade to plain text
+/// instead of being silently dropped.
+pub fn walk_document(doc: &Value) -> (String, Vec<Table>) {
+    let mut content = String::new();
+    let mut tables = Vec::new();
+
+    let Some(blocks) = doc.get("blocks").and_then(Value::as_array) else {
+        return (content, tables);
+    };
+
+    for block in blocks {
+        let Some(tag) = block.get("t").and_then(Value::as_str) else {
+            continue;
+        };
+
+        match tag {
+            "Header" => {
+                if let Some(c) = block.get("c").and_then(Value::as_array)
+                    && let (Some(level), Some(inlines)) = (c.first().and_then(Value::as_u64), c.get(2))
+                {
+                    content.push_str(&"#".repeat(level as usize));
+                    content.push(' ');
+                    content.push_str(&flatten_inlines(inlines));
+                    content.push_str("\n\n");
+                }
+            }
+            "Para" | "Plain" => {
+                if let Some(inlines) = block.get("c") {
+                    content.push_str(&flatten_inlines(inlines));
+                    content.push_str("\n\n");
+                }
+            }
+            "Table" => {
+                let table = parse_table(block);
+                content.push_str(&table.to_markdown());
+                content.push_str("\n\n");
+                tables.push(table);
+            }
+            _ => {
+                if let Some(inlines) = block.get("c") {
+                    let text = flatten_inlines(inlines);
+                    if !text.is_empty() {
+                        content.push_str(&text);
+                        content.push_str("\n\n");
+                    }
+                }
+            }
+        }
+    }
+
+    (content.trim().to_string(), tables)
+}
+
+/// Parse a `{"t":"Table","c":[attr, caption, colspecs, head, bodies, foot]}`
+/// block into a [`Table`].
+///
+/// Pandoc's `Table` shape (since the 2.10 AST): `head` is a `TableHead`
+/// (`[attr, [row, ...]]`), `bodies` is a list of `TableBody`
+/// (`[attr, rowHeadCols, [headerRows...], [bodyRows...]]`), and each row is
+/// `[attr, [cell, ...]]` with each cell `[attr, alignment, rowspan, colspan, blocks]`.
+fn parse_table(block: &Value) -> Table {
+    let mut table = Table::default();
+
+    let Some(c) = block.get("c").and_then(Value::as_array) else {
+        return table;
+    };
+
+    if let Some(caption_node) = c.get(1) {
+        let caption_text = caption_node
+            .get(1)
+            .and_then(Value::as_array)
+            .map(|blocks| {
+                blocks
+                    .iter()
+                    .filter_map(|b| b.get("c"))
+                    .map(flatten_inlines)
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .unwrap_or_default();
+        if !caption_text.is_empty() {
+            table.caption = Some(caption_text);
+        }
+    }
+
+    if let Some(colspecs) = c.get(2).and_then(Value::as_array) {
+        table.column_count = colspecs.len();
+    }
+
+    if let Some(head) = c.get(3).and_then(Value::as_array)
+        && let Some(rows) = head.get(1).and_then(Value::as_array)
+    {
+        for row in rows {
+            table.header_rows.push(parse_table_row(row));
+        }
+    }
+
+    if let Some(bodies) = c.get(4).and_then(Value::as_array) {
+        for body in bodies {
+            let Some(body_arr) = body.as_array() else { continue };
+            if let Some(intermediate_head) = body_arr.get(2).and_then(Value::as_array) {
+                for row in intermediate_head {
+                    table.header_rows.push(parse_table_row(row));
+                }
+            }
+            if let Some(body_rows) = body_arr.get(3).and_then(Value::as_array) {
+                for row in body_rows {
+                    table.body_rows.push(parse_table_row(row));
+                }
+            }
+        }
+    }
+
+    if table.column_count == 0 {
+        table.column_count = table
+            .header_rows
+            .first()
+            .or_else(|| table.body_rows.first())
+            .map(|r| r.len())
+            .unwrap_or(0);
+    }
+
+    table
+}
+
+/// Parse a `[attr, [cell, ...]]` table row into flattened cell text, one
+/// entry per cell (`[attr, alignment, rowspan, colspan, blocks]`).
+fn parse_table_row(row: &Value) -> Vec<TableCell> {
+    let Some(cells) = row.as_array().and_then(|r| r.get(1)).and_then(Value::as_array) else {
+        return Vec::new();
+    };
+
+    cells
+        .iter()
+        .map(|cell| {
+            cell.as_array()
+                .and_then(|c| c.get(4))
+                .and_then(Value::as_array)
+                .map(|blocks| {
+                    blocks
+                        .iter()
+                        .filter_map(|b| b.get("c"))
+                        .map(flatten_inlines)
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                        .trim()
+                        .to_string()
+                })
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Flatten a Pandoc inline-node array (`Str`/`Space`/`Emph`/`Strong`/...) to
+/// plain text. Unrecognized inline tags with nested `"c"` content are
+/// recursed into so formatting marks degrade to their plain text rather than
+/// disappearing.
+pub fn flatten_inlines(inlines: &Value) -> String {
+    let Some(items) = inlines.as_array() else {
+        return String::new();
+    };
+
+    let mut out = String::new();
+    for item in items {
+        let Some(tag) = item.get("t").and_then(Value::as_str) else {
+            continue;
+        };
+
+        match tag {
+            "Str" => {
+                if let Some(s) = item.get("c").and_then(Value::as_str) {
+                    out.push_str(s);
+                }
+            }
+            "Space" | "SoftBreak" => out.push(' '),
+            "LineBreak" => out.push('\n'),
+            "Emph" | "Strong" | "Strikeout" | "Superscript" | "Subscript" | "SmallCaps" | "Underline" => {
+                if let Some(inner) = item.get("c") {
+                    out.push_str(&flatten_inlines(inner));
+                }
+            }
+            "Code" => {
+                if let Some(s) = item.get("c").and_then(Value::as_array).and_then(|c| c.get(1)).and_then(Value::as_str) {
+                    out.push_str(s);
+                }
+            }
+            "Link" | "Image" => {
+                if let Some(c) = item.get("c").and_then(Value::as_array)
+                    && let Some(inlines) = c.get(1)
+                {
+                    out.push_str(&flatten_inlines(inlines));
+                }
+            }
+            _ => {}
+        }
+    }
+    out
+}
+
+/// One image Pandoc wrote to the `--extract-media` directory while
+/// converting a document, correlated back to the `Image` AST node(s) that
+/// reference it.
+///
+/// `width`/`height` come from the image bytes themselves (see
+/// [`parse_image_dimensions`]), not from any size Pandoc or the source
+/// format declared - RTF in particular only stores the *display* size, not
+/// the source pixel dimensions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PandocImage {
+    /// Path Pandoc referenced the image by in the AST (e.g. `media/image1.jpg`).
+    pub path: String,
+    pub mime_type: String,
+    pub bytes: Vec<u8>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+}
+
+/// Sniff an image's MIME type from its leading magic bytes. Covers the
+/// three formats [`parse_image_dimensions`] knows how to measure.
+fn sniff_image_mime_type(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some("image/jpeg")
+    } else if bytes.starts_with(&[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A]) {
+        Some("image/png")
+    } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        Some("image/gif")
+    } else {
+        None
+    }
+}
+
+/// Parse `(width, height)` in pixels directly from image bytes, without
+/// pulling in a full image-decoding dependency: just enough of each
+/// container format's header to read its dimensions.
+fn parse_image_dimensions(bytes: &[u8], mime_type: &str) -> Option<(u32, u32)> {
+    match mime_type {
+        "image/jpeg" => parse_jpeg_dimensions(bytes),
+        "image/png" => parse_png_dimensions(bytes),
+        "image/gif" => parse_gif_dimensions(bytes),
+        _ => None,
+    }
+}
+
+/// Walk a JPEG's marker segments looking for a Start-Of-Frame marker
+/// (`0xC0`-`0xCF`, excluding the DHT/JPG/DAC markers `0xC4`/`0xC8`/`0xCC`),
+/// whose payload is `[precision, height_hi, height_lo, width_hi, width_lo, ...]`.
+fn parse_jpeg_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    let mut pos = 2; // skip the SOI marker (0xFFD8)
+    while pos + 4 <= bytes.len() {
+        if bytes[pos] != 0xFF {
+            pos += 1;
+            continue;
+        }
+        let marker = bytes[pos + 1];
+        let is_sof = (0xC0..=0xCF).contains(&marker) && ![0xC4, 0xC8, 0xCC].contains(&marker);
+        let segment_len = u16::from_be_bytes([bytes[pos + 2], bytes[pos + 3]]) as usize;
+
+        if is_sof && pos + 2 + segment_len <= bytes.len() && segment_len >= 7 {
+            let segment = &bytes[pos + 4..pos + 2 + segment_len];
+            let height = u16::from_be_bytes([segment[1], segment[2]]) as u32;
+            let width = u16::from_be_bytes([segment[3], segment[4]]) as u32;
+            return Some((width, height));
+        }
+
+        if marker == 0xD8 || marker == 0x01 || (0xD0..=0xD7).contains(&marker) {
+            pos += 2;
+        } else {
+            pos += 2 + segment_len;
+        }
+    }
+    None
+}
+
+/// PNG's `IHDR` chunk is always the first chunk, at a fixed offset: 8-byte
+/// signature, 4-byte chunk length, 4-byte `"IHDR"` tag, then
+/// `[width: u32, height: u32]` big-endian.
+fn parse_png_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    if bytes.len() < 24 || &bytes[12..16] != b"IHDR" {
+        return None;
+    }
+    let width = u32::from_be_bytes(bytes[16..20].try_into().ok()?);
+    let height = u32::from_be_bytes(bytes[20..24].try_into().ok()?);
+    Some((width, height))
+}
+
+/// GIF's logical screen descriptor follows the 6-byte `"GIF8{7,9}a"`
+/// signature as `[width: u16, height: u16]`, both little-endian.
+fn parse_gif_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    if bytes.len() < 10 {
+        return None;
+    }
+    let width = u16::from_le_bytes([bytes[6], bytes[7]]) as u32;
+    let height = u16::from_le_bytes([bytes[8], bytes[9]]) as u32;
+    Some((width, height))
+}
+
+/// Collect every `Image` node's target path from a Pandoc AST's `blocks`,
+/// in document order (duplicates included, so callers can correlate
+/// positionally if needed).
+fn collect_image_refs(doc: &Value) -> Vec<String> {
+    fn walk_inlines(inlines: &Value, out: &mut Vec<String>) {
+        let Some(items) = inlines.as_array() else { return };
+        for item in items {
+            match item.get("t").and_then(Value::as_str) {
+                Some("Image") => {
+                    if let Some(target) = item
+                        .get("c")
+                        .and_then(Value::as_array)
+                        .and_then(|c| c.get(2))
+                        .and_then(Value::as_array)
+                        .and_then(|t| t.first())
+                        .and_then(Value::as_str)
+                    {
+                        out.push(target.to_string());
+                    }
+                }
+                _ => {
+                    if let Some(inner) = item.get("c") {
+                        walk_inlines(inner, out);
+                    }
+                }
+            }
+        }
+    }
+
+    let mut out = Vec::new();
+    if let Some(blocks) = doc.get("blocks").and_then(Value::as_array) {
+        for block in blocks {
+            if let Some(c) = block.get("c") {
+                walk_inlines(c, &mut out);
+            }
+        }
+    }
+    out
+}
+
+/// Run Pandoc with `--extract-media <dir>` so embedded images are written
+/// out as files, then read each one back, measure its real dimensions, and
+/// correlate it with the `Image` reference(s) in the AST that point to it.
+///
+/// The extraction directory is a unique, process-and-call-scoped temp
+/// directory removed once the images are read back, so concurrent
+/// extractions never collide.
+pub(crate) async fn convert_with_media(
+    bytes: &[u8],
+    from_format: &str,
+    options: &PandocConfig,
+) -> Result<(Value, Vec<PandocImage>)> {
+    options.validate_filters()?;
+
+    let media_dir = std::env::temp_dir().join(format!(
+        "kreuzberg-pandoc-media-{}-{}",
+        std::process::id(),
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or_default()
+    ));
+    std::fs::create_dir_all(&media_dir).map_err(KreuzbergError::Io)?;
+
+    let result = convert_with_media_in_dir(bytes, from_format, options, &media_dir).await;
+    let _ = std::fs::remove_dir_all(&media_dir);
+    result
+}
+
+async fn convert_with_media_in_dir(
+    bytes: &[u8],
+    from_format: &str,
+    options: &PandocConfig,
+    media_dir: &std::path::Path,
+) -> Result<(Value, Vec<PandocImage>)> {
+    let reader = format!("{from_format}{}", options.reader_extensions.suffix());
+    let mut command = Command::new("pandoc");
+    command
+        .arg("-f")
+        .arg(&reader)
+        .arg("-t")
+        .arg("json")
+        .arg("--extract-media")
+        .arg(media_dir);
+    options.apply_filters(&mut command);
+    command.stdin(Stdio::piped()).stdout(Stdio::piped()).stderr(Stdio::piped());
+
+    let mut child = command
+        .spawn()
+        .map_err(|_| KreuzbergError::MissingDependency("pandoc".to_string()))?;
+
+    {
+        let stdin = child
+            .stdin
+            .as_mut()
+            .ok_or_else(|| KreuzbergError::Other("Failed to open pandoc stdin".to_string()))?;
+        stdin
+            .write_all(bytes)
+            .await
+            .map_err(|e| KreuzbergError::Parsing { message: format!("Failed to write to pandoc stdin: {}", e), source: None })?;
+    }
+
+    let output = child
+        .wait_with_output()
+        .await
+        .map_err(|e| KreuzbergError::Parsing { message: format!("Failed to read pandoc output: {}", e), source: None })?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        if is_lua_filter_error(&stderr) {
+            return Err(KreuzbergError::Plugin {
+                message: stderr.trim().to_string(),
+                plugin_name: "pandoc-lua-filter".to_string(),
+            });
+        }
+        return Err(KreuzbergError::Parsing {
+            message: format!("pandoc exited with {}: {}", output.status, stderr.trim()),
+            source: None,
+        });
+    }
+
+    let ast: Value = serde_json::from_slice(&output.stdout)
+        .map_err(|e| KreuzbergError::Parsing { message: format!("Failed to parse pandoc JSON output: {}", e), source: None })?;
+
+    let refs = collect_image_refs(&ast);
+    let mut images = Vec::with_capacity(refs.len());
+    for path in refs {
+        let file_path = media_dir.join(&path);
+        let Ok(bytes) = std::fs::read(&file_path) else {
+            continue;
+        };
+        let mime_type = sniff_image_mime_type(&bytes).unwrap_or("application/octet-stream").to_string();
+        let (width, height) = parse_image_dimensions(&bytes, &mime_type).unzip();
+        images.push(PandocImage { path, mime_type, bytes, width, height });
+    }
+
+    Ok((ast, images))
+}
+
+/// One footnote pulled out of a `Note` AST node: its stable document-order
+/// index, the `[^N]` marker left in `content` at its reference point, and
+/// its fully flattened body text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Footnote {
+    pub index: usize,
+    pub marker: String,
+    pub text: String,
+}
+
+/// One citation key pulled out of a `Cite` AST node (Pandoc's `--citeproc`
+/// input form, e.g. `[@smith2004]`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Citation {
+    pub key: String,
+}
+
+/// Like [`flatten_inlines`], but additionally pulls `Note` and `Cite` nodes
+/// out into `footnotes`/`citations` rather than dropping them (the plain
+/// [`flatten_inlines`] has no case for either tag, so they vanish silently).
+///
+/// A `Note`'s in-text marker (`[^N]`, 1-indexed in document order) is left
+/// in the returned text in place of the note body, so callers get clean
+/// body text plus a separate, reconstructable reference apparatus instead
+/// of footnote text inlined at the point of reference.
+fn flatten_inlines_collecting(inlines: &Value, footnotes: &mut Vec<Footnote>, citations: &mut Vec<Citation>) -> String {
+    let Some(items) = inlines.as_array() else {
+        return String::new();
+    };
+
+    let mut out = String::new();
+    for item in items {
+        let Some(tag) = item.get("t").and_then(Value::as_str) else {
+            continue;
+        };
+
+        match tag {
+            "Str" => {
+                if let Some(s) = item.get("c").and_then(Value::as_str) {
+                    out.push_str(s);
+                }
+            }
+            "Space" | "SoftBreak" => out.push(' '),
+            "LineBreak" => out.push('\n'),
+            "Emph" | "Strong" | "Strikeout" | "Superscript" | "Subscript" | "SmallCaps" | "Underline" => {
+                if let Some(inner) = item.get("c") {
+                    out.push_str(&flatten_inlines_collecting(inner, footnotes, citations));
+                }
+            }
+            "Code" => {
+                if let Some(s) = item.get("c").and_then(Value::as_array).and_then(|c| c.get(1)).and_then(Value::as_str) {
+                    out.push_str(s);
+                }
+            }
+            "Link" | "Image" => {
+                if let Some(c) = item.get("c").and_then(Value::as_array)
+                    && let Some(inlines) = c.get(1)
+                {
+                    out.push_str(&flatten_inlines_collecting(inlines, footnotes, citations));
+                }
+            }
+            "Note" => {
+                let text = item
+                    .get("c")
+                    .and_then(Value::as_array)
+                    .map(|blocks| flatten_blocks(blocks, footnotes, citations))
+                    .unwrap_or_default();
+                let index = footnotes.len() + 1;
+                let marker = format!("[^{}]", index);
+                footnotes.push(Footnote { index, marker: marker.clone(), text });
+                out.push_str(&marker);
+            }
+            "Cite" => {
+                if let Some(c) = item.get("c").and_then(Value::as_array) {
+                    if let Some(citation_list) = c.first().and_then(Value::as_array) {
+                        for citation in citation_list {
+                            if let Some(key) = citation.get("citationId").and_then(Value::as_str) {
+                                citations.push(Citation { key: key.to_string() });
+                            }
+                        }
+                    }
+                    if let Some(inlines) = c.get(1) {
+                        out.push_str(&flatten_inlines_collecting(inlines, footnotes, citations));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    out
+}
+
+/// Flatten a list of AST blocks (as found in a `Note`'s body, or a
+/// document's top-level `blocks`) to plain text, recursing into nested
+/// footnotes/citations the same way [`flatten_inlines_collecting`] does.
+fn flatten_blocks(blocks: &[Value], footnotes: &mut Vec<Footnote>, citations: &mut Vec<Citation>) -> String {
+    let mut out = String::new();
+    for block in blocks {
+        if let Some(inlines) = block.get("c") {
+            let text = flatten_inlines_collecting(inlines, footnotes, citations);
+            if !text.is_empty() {
+                if !out.is_empty() {
+                    out.push(' ');
+                }
+                out.push_str(text.trim());
+            }
+        }
+    }
+    out
+}
+
+/// Like [`walk_document`], but additionally collects footnotes and
+/// citations via [`flatten_inlines_collecting`], leaving `[^N]` markers in
+/// `content` at each footnote's reference point.
+pub fn walk_document_with_notes(doc: &Value) -> (String, Vec<Table>, Vec<Footnote>, Vec<Citation>) {
+    let mut content = String::new();
+    let mut tables = Vec::new();
+    let mut footnotes = Vec::new();
+    let mut citations = Vec::new();
+
+    let Some(blocks) = doc.get("blocks").and_then(Value::as_array) else {
+        return (content, tables, footnotes, citations);
+    };
+
+    for block in blocks {
+        let Some(tag) = block.get("t").and_then(Value::as_str) else {
+            continue;
+        };
+
+        match tag {
+            "Header" => {
+                if let Some(c) = block.get("c").and_then(Value::as_array)
+                    && let (Some(level), Some(inlines)) = (c.first().and_then(Value::as_u64), c.get(2))
+                {
+                    content.push_str(&"#".repeat(level as usize));
+                    content.push(' ');
+                    content.push_str(&flatten_inlines_collecting(inlines, &mut footnotes, &mut citations));
+                    content.push_str("\n\n");
+                }
+            }
+            "Para" | "Plain" => {
+                if let Some(inlines) = block.get("c") {
+                    content.push_str(&flatten_inlines_collecting(inlines, &mut footnotes, &mut citations));
+                    content.push_str("\n\n");
+                }
+            }
+            "Table" => {
+                let table = parse_table(block);
+                content.push_str(&table.to_markdown());
+                content.push_str("\n\n");
+                tables.push(table);
+            }
+            _ => {
+                if let Some(inlines) = block.get("c") {
+                    let text = flatten_inlines_collecting(inlines, &mut footnotes, &mut citations);
+                    if !text.is_empty() {
+                        content.push_str(&text);
+                        content.push_str("\n\n");
+                    }
+                }
+            }
+        }
+    }
+
+    (content.trim().to_string(), tables, footnotes, citations)
+}
+
+#[cfg(feature = "office")]
+#[async_trait]
+impl DocumentExtractor for PandocExtractor {
+    async fn extract_bytes(
+        &self,
+        content: &[u8],
+        mime_type: &str,
+        config: &ExtractionConfig,
+    ) -> Result<ExtractionResult> {
+        let from_format = mime_to_pandoc_reader(mime_type)
+            .ok_or_else(|| KreuzbergError::UnsupportedFormat(mime_type.to_string()))?;
+
+        let default_options = PandocConfig::default();
+        let options = config.pandoc.as_ref().unwrap_or(&default_options);
+
+        let extract_images = config.images.as_ref().map(|cfg| cfg.extract_images).unwrap_or(false);
+
+        let cache_key = options
+            .cache
+            .as_ref()
+            .map(|_| crate::extraction::cache::CacheKey::new(content, &format!("{from_format}|{extract_images}|{options:?}")));
+        if let (Some(cache), Some(key)) = (options.cache.as_ref(), cache_key.as_ref())
+            && let Some(cached) = cache.get(key)
+        {
+            return Ok(cached);
+        }
+
+        let (ast, images) = if extract_images {
+            let (ast, images) = convert_with_media(content, from_format, options).await?;
+            (ast, Some(images))
+        } else {
+            (convert_to_json_with_options(content, from_format, options).await?, None)
+        };
+
+        let mut metadata = Metadata::default();
+        let (content, tables) = match options.output_format {
+            OutputFormat::StructuredAst => {
+                let content = serde_json::to_string(&ast).map_err(|e| KreuzbergError::Serialization {
+                    message: format!("Failed to serialize Pandoc AST: {}", e),
+                    source: None,
+                })?;
+                (content, Vec::new())
+            }
+            OutputFormat::Markdown => {
+                let content = convert_to_markdown_with_options(content, from_format, options).await?;
+                (content, Vec::new())
+            }
+            OutputFormat::PlainText if options.extract_footnotes => {
+                let (content, tables, footnotes, citations) = walk_document_with_notes(&ast);
+                if !footnotes.is_empty() {
+                    metadata.additional.insert(
+                        "footnotes".to_string(),
+                        serde_json::json!(footnotes
+                            .into_iter()
+                            .map(|f| serde_json::json!({"index": f.index, "marker": f.marker, "text": f.text}))
+                            .collect::<Vec<_>>()),
+                    );
+                }
+                if !citations.is_empty() {
+                    metadata.additional.insert(
+                        "citations".to_string(),
+                        serde_json::json!(citations.into_iter().map(|c| c.key).collect::<Vec<_>>()),
+                    );
+                }
+                (content, tables)
+            }
+            OutputFormat::PlainText => walk_document(&ast),
+        };
+        let content = options.line_ending.normalize(&content);
+
+        let result = ExtractionResult {
+            content,
+            mime_type: mime_type.to_string(),
+            metadata,
+            tables: tables.into_iter().map(|t| serde_json::json!(t.to_markdown())).collect(),
+            detected_languages: None,
+            chunks: None,
+            images: images.map(|images| {
+                images
+                    .into_iter()
+                    .map(|image| {
+                        serde_json::json!({
+                            "path": image.path,
+                            "mime_type": image.mime_type,
+                            "bytes": image.bytes,
+                            "width": image.width,
+                            "height": image.height,
+                        })
+                    })
+                    .collect()
+            }),
+        };
+
+        if let (Some(cache), Some(key)) = (options.cache.as_ref(), cache_key) {
+            cache.put(key, result.clone());
+        }
+
+        Ok(result)
+    }
+
+    fn supported_mime_types(&self) -> &[&str] {
+        &[
+            "application/rtf",
+            "text/rtf",
+            "text/org",
+            "text/x-rst",
+            "text/textile",
+            "text/x-mediawiki",
+            "text/x-dokuwiki",
+            "text/x-muse",
+            "text/x-creole",
+            "application/x-jats+xml",
+            "application/epub+zip",
+        ]
+    }
+
+    fn priority(&self) -> i32 {
+        // Below the native RTF/EPUB extractors (50/60); this is the
+        // fallback for whatever they don't handle.
+        40
+    }
+}
+
+/// Map a MIME type to the `-f` reader Pandoc should use. `None` means this
+/// extractor doesn't claim the format.
+///
+/// Covers RTF (the original use case) plus the lightweight markup formats
+/// Pandoc reads natively: Org-mode, reStructuredText, Textile, MediaWiki,
+/// DokuWiki, Muse, Creole, JATS, and EPUB. Deliberately exhaustive rather
+/// than a substring/fallback match, so an unrecognized MIME type returns
+/// `None` (and the caller can try [`extension_to_pandoc_reader`] or give up)
+/// instead of being silently misdetected as RTF.
+fn mime_to_pandoc_reader(mime_type: &str) -> Option<&'static str> {
+    match mime_type {
+        "application/rtf" | "text/rtf" => Some("rtf"),
+        "text/org" => Some("org"),
+        "text/x-rst" => Some("rst"),
+        "text/textile" => Some("textile"),
+        "text/x-mediawiki" => Some("mediawiki"),
+        "text/x-dokuwiki" => Some("dokuwiki"),
+        "text/x-muse" => Some("muse"),
+        "text/x-creole" => Some("creole"),
+        "application/x-jats+xml" => Some("jats"),
+        "application/epub+zip" => Some("epub"),
+        _ => None,
+    }
+}
+
+/// Map a lowercased file extension (without the leading `.`) to the `-f`
+/// reader Pandoc should use, for callers that only have a file name to go
+/// on (no sniffed MIME type). Kept in sync with
+/// [`mime_to_pandoc_reader`]'s format list.
+pub fn extension_to_pandoc_reader(extension: &str) -> Option<&'static str> {
+    match extension {
+        "rtf" => Some("rtf"),
+        "org" => Some("org"),
+        "rst" => Some("rst"),
+        "textile" => Some("textile"),
+        "wiki" | "mediawiki" => Some("mediawiki"),
+        "dokuwiki" => Some("dokuwiki"),
+        "muse" => Some("muse"),
+        "creole" => Some("creole"),
+        "jats" => Some("jats"),
+        "epub" => Some("epub"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sniff_image_mime_type_recognizes_jpeg_png_gif() {
+        assert_eq!(sniff_image_mime_type(&[0xFF, 0xD8, 0xFF, 0xE0]), Some("image/jpeg"));
+        assert_eq!(
+            sniff_image_mime_type(&[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A]),
+            Some("image/png")
+        );
+        assert_eq!(sniff_image_mime_type(b"GIF89a"), Some("image/gif"));
+        assert_eq!(sniff_image_mime_type(b"not an image"), None);
+    }
+
+    #[test]
+    fn test_parse_png_dimensions_reads_ihdr() {
+        let mut bytes = vec![0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+        bytes.extend_from_slice(&[0, 0, 0, 13]); // chunk length
+        bytes.extend_from_slice(b"IHDR");
+        bytes.extend_from_slice(&100u32.to_be_bytes());
+        bytes.extend_from_slice(&50u32.to_be_bytes());
+        assert_eq!(parse_png_dimensions(&bytes), Some((100, 50)));
+    }
+
+    #[test]
+    fn test_parse_gif_dimensions_reads_logical_screen_descriptor() {
+        let mut bytes = b"GIF89a".to_vec();
+        bytes.extend_from_slice(&320u16.to_le_bytes());
+        bytes.extend_from_slice(&240u16.to_le_bytes());
+        assert_eq!(parse_gif_dimensions(&bytes), Some((320, 240)));
+    }
+
+    #[test]
+    fn test_parse_jpeg_dimensions_reads_sof0_segment() {
+        // SOI, then a minimal SOF0 (0xC0) segment: len=8, precision=8, height=480, width=640.
+        let mut bytes = vec![0xFF, 0xD8];
+        bytes.extend_from_slice(&[0xFF, 0xC0, 0x00, 0x08, 0x08]);
+        bytes.extend_from_slice(&480u16.to_be_bytes());
+        bytes.extend_from_slice(&640u16.to_be_bytes());
+        bytes.push(0x03); // component count
+        assert_eq!(parse_jpeg_dimensions(&bytes), Some((640, 480)));
+    }
+
+    #[test]
+    fn test_collect_image_refs_finds_image_target() {
+        let doc = serde_json::json!({
+            "pandoc-api-version": [1, 23],
+            "meta": {},
+            "blocks": [
+                {"t": "Para", "c": [
+                    {"t": "Image", "c": [["", [], []], [{"t": "Str", "c": "image"}], ["media/image1.jpg", ""]]}
+                ]}
+            ]
+        });
+        assert_eq!(collect_image_refs(&doc), vec!["media/image1.jpg".to_string()]);
+    }
+
+    #[test]
+    fn test_walk_document_with_notes_extracts_footnote_with_marker() {
+        let doc = serde_json::json!({
+            "pandoc-api-version": [1, 23],
+            "meta": {},
+            "blocks": [
+                {"t": "Para", "c": [
+                    {"t": "Str", "c": "See"},
+                    {"t": "Space"},
+                    {"t": "Str", "c": "below."},
+                    {"t": "Note", "c": [
+                        {"t": "Para", "c": [{"t": "Str", "c": "The"}, {"t": "Space"}, {"t": "Str", "c": "note."}]}
+                    ]}
+                ]}
+            ]
+        });
+
+        let (content, tables, footnotes, citations) = walk_document_with_notes(&doc);
+        assert_eq!(content, "See below.[^1]");
+        assert!(tables.is_empty());
+        assert!(citations.is_empty());
+        assert_eq!(footnotes.len(), 1);
+        assert_eq!(footnotes[0].index, 1);
+        assert_eq!(footnotes[0].marker, "[^1]");
+        assert_eq!(footnotes[0].text, "The note.");
+    }
+
+    #[test]
+    fn test_walk_document_with_notes_extracts_citation_key() {
+        let doc = serde_json::json!({
+            "pandoc-api-version": [1, 23],
+            "meta": {},
+            "blocks": [
+                {"t": "Para", "c": [
+                    {"t": "Cite", "c": [
+                        [{"citationId": "smith2004"}],
+                        [{"t": "Str", "c": "[@smith2004]"}]
+                    ]}
+                ]}
+            ]
+        });
+
+        let (_content, _tables, footnotes, citations) = walk_document_with_notes(&doc);
+        assert!(footnotes.is_empty());
+        assert_eq!(citations, vec![Citation { key: "smith2004".to_string() }]);
+    }
+
+    #[test]
+    fn test_mime_to_pandoc_reader_covers_lightweight_markup_formats() {
+        assert_eq!(mime_to_pandoc_reader("application/rtf"), Some("rtf"));
+        assert_eq!(mime_to_pandoc_reader("text/org"), Some("org"));
+        assert_eq!(mime_to_pandoc_reader("text/x-rst"), Some("rst"));
+        assert_eq!(mime_to_pandoc_reader("application/epub+zip"), Some("epub"));
+        assert_eq!(mime_to_pandoc_reader("application/pdf"), None);
+    }
+
+    #[test]
+    fn test_extension_to_pandoc_reader_matches_mime_mapping() {
+        assert_eq!(extension_to_pandoc_reader("org"), Some("org"));
+        assert_eq!(extension_to_pandoc_reader("wiki"), Some("mediawiki"));
+        assert_eq!(extension_to_pandoc_reader("unknownext"), None);
+    }
+
+    #[test]
+    fn test_validate_filters_rejects_missing_file() {
+        let config = PandocConfig {
+            filters: vec![PandocFilter::Lua(PathBuf::from("/nonexistent/filter.lua"))],
+            ..Default::default()
+        };
+        let result = config.validate_filters();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_validate_filters_accepts_empty_list() {
+        let config = PandocConfig::default();
+        assert!(config.validate_filters().is_ok());
+    }
+
+    #[test]
+    fn test_reader_extensions_suffix_is_empty_by_default() {
+        assert_eq!(ReaderExtensions::default().suffix(), "");
+    }
+
+    #[test]
+    fn test_reader_extensions_suffix_orders_enable_and_disable() {
+        let extensions = ReaderExtensions::default().enable(ReaderExtension::Smart).disable(ReaderExtension::RawTex);
+        assert_eq!(extensions.suffix(), "+smart-raw_tex");
+    }
+
+    #[test]
+    fn test_line_ending_native_is_a_no_op() {
+        assert_eq!(LineEnding::Native.normalize("a\r\nb\n"), "a\r\nb\n");
+    }
+
+    #[test]
+    fn test_line_ending_lf_strips_carriage_returns() {
+        assert_eq!(LineEnding::Lf.normalize("a\r\nb\n"), "a\nb\n");
+    }
+
+    #[test]
+    fn test_line_ending_crlf_normalizes_bare_lf() {
+        assert_eq!(LineEnding::Crlf.normalize("a\r\nb\n"), "a\r\nb\r\n");
+    }
+
+    #[test]
+    fn test_output_format_defaults_to_plain_text() {
+        assert_eq!(PandocConfig::default().output_format, OutputFormat::PlainText);
+    }
+
+    #[test]
+    fn test_is_lua_filter_error_detects_lua_traceback() {
+        assert!(is_lua_filter_error("Error running filter strip-footnotes.lua:"));
+        assert!(is_lua_filter_error("lua error: attempt to call a nil value"));
+        assert!(!is_lua_filter_error("pandoc: unknown input format \"bogus\""));
+    }
+
+    #[test]
+    fn test_table_to_markdown_renders_pipe_table() {
+        let table = Table {
+            caption: None,
+            column_count: 2,
+            header_rows: vec![vec!["Code".to_string(), "Error".to_string()]],
+            body_rows: vec![
+                vec!["1".to_string(), "Not found".to_string()],
+                vec!["2".to_string(), "Timeout".to_string()],
+            ],
+        };
+
+        let markdown = table.to_markdown();
+        assert!(markdown.contains("| Code | Error |"));
+        assert!(markdown.contains("| --- | --- |"));
+        assert!(markdown.contains("| 1 | Not found |"));
+        assert!(markdown.contains("| 2 | Timeout |"));
+    }
+
+    #[test]
+    fn test_flatten_inlines_joins_str_and_space() {
+        let inlines = serde_json::json!([
+            {"t": "Str", "c": "hello"},
+            {"t": "Space"},
+            {"t": "Str", "c": "world"}
+        ]);
+        assert_eq!(flatten_inlines(&inlines), "hello world");
+    }
+
+    #[test]
+    fn test_flatten_inlines_recurses_into_emph() {
+        let inlines = serde_json::json!([
+            {"t": "Emph", "c": [{"t": "Str", "c": "bold"}]}
+        ]);
+        assert_eq!(flatten_inlines(&inlines), "bold");
+    }
+
+    #[test]
+    fn test_walk_document_renders_header_as_markdown() {
+        let doc = serde_json::json!({
+            "pandoc-api-version": [1, 23],
+            "meta": {},
+            "blocks": [
+                {"t": "Header", "c": [1, ["", [], []], [{"t": "Str", "c": "Title"}]]}
+            ]
+        });
+        let (content, tables) = walk_document(&doc);
+        assert_eq!(content, "# Title");
+        assert!(tables.is_empty());
+    }
+
+    #[test]
+    fn test_markdown_extensions_default_enables_footnotes_and_pipe_tables() {
+        let target = MarkdownExtensions::default().target_string();
+        assert_eq!(target, "markdown+footnotes+pipe_tables");
+    }
+
+    #[test]
+    fn test_markdown_extensions_empty_has_no_toggles() {
+        assert_eq!(MarkdownExtensions::empty().target_string(), "markdown");
+    }
+
+    #[test]
+    fn test_markdown_extensions_enable_and_disable_are_ordered() {
+        let extensions = MarkdownExtensions::empty()
+            .enable(MarkdownExtension::Smart)
+            .enable(MarkdownExtension::Footnotes)
+            .disable(MarkdownExtension::RawHtml);
+        assert_eq!(extensions.target_string(), "markdown+footnotes-raw_html+smart");
+    }
+
+    #[test]
+    fn test_markdown_extensions_disable_overrides_prior_enable() {
+        let extensions = MarkdownExtensions::default().disable(MarkdownExtension::Footnotes);
+        assert_eq!(extensions.target_string(), "markdown-footnotes+pipe_tables");
+    }
+
+    #[test]
+    fn test_walk_document_reconstructs_table_from_ast() {
+        // Simplified Pandoc 2.10+ Table AST: 2 columns, one header row, one body row.
+        let doc = serde_json::json!({
+            "pandoc-api-version": [1, 23],
+            "meta": {},
+            "blocks": [
+                {"t": "Table", "c": [
+                    ["", [], []],
+                    [[], []],
+                    [["AlignDefault", {"t": "ColWidthDefault"}], ["AlignDefault", {"t": "ColWidthDefault"}]],
+                    [["", [], []], [
+                        [["", [], []], [
+                            [["", [], []], "AlignDefault", 1, 1, [{"t": "Plain", "c": [{"t": "Str", "c": "A"}]}]],
+                            [["", [], []], "AlignDefault", 1, 1, [{"t": "Plain", "c": [{"t": "Str", "c": "B"}]}]]
+                        ]]
+                    ]],
+                    [[["", [], []], 0, [], [
+                        [["", [], []], [
+                            [["", [], []], "AlignDefault", 1, 1, [{"t": "Plain", "c": [{"t": "Str", "c": "1"}]}]],
+                            [["", [], []], "AlignDefault", 1, 1, [{"t": "Plain", "c": [{"t": "Str", "c": "2"}]}]]
+                        ]]
+                    ]]],
+                    ["", [], []]
+                ]}
+            ]
+        });
+
+        let (_content, tables) = walk_document(&doc);
+        assert_eq!(tables.len(), 1);
+        let table = &tables[0];
+        assert_eq!(table.column_count, 2);
+        assert_eq!(table.header_rows, vec![vec!["A".to_string(), "B".to_string()]]);
+        assert_eq!(table.body_rows, vec![vec!["1".to_string(), "2".to_string()]]);
+    }
+}
@@ -3,7 +3,8 @@
 //! This extractor provides native Rust-based EPUB extraction as a replacement
 //! for Pandoc, extracting:
 //! - Metadata from OPF (Open Packaging Format) using Dublin Core standards
-//! - Content from XHTML files in spine order
+//! - Content from XHTML files in spine order, chunked by chapter using the
+//!   nav/NCX table of contents
 //! - Cover image detection
 
 use crate::Result;
@@ -29,11 +30,21 @@ impl EpubExtractor {
         Self
     }
 
-    /// Extract text content from an EPUB document
+    /// Extract Markdown content from an EPUB document, along with one
+    /// [`EpubChunk`] per chapter.
+    ///
+    /// Chapter titles come from the EPUB's navigation document (EPUB3 `nav`
+    /// or EPUB2 NCX `toc`, both surfaced by the `epub` crate as
+    /// [`EpubDoc::toc`]) matched back to spine position by resource path;
+    /// spine items with no matching navigation entry fall back to a
+    /// `"Chapter N"` title, so every spine item still gets a chunk.
     #[cfg(feature = "office")]
-    fn extract_content<R: std::io::Read + std::io::Seek>(epub: &mut EpubDoc<R>) -> String {
-        let mut content = String::new();
+    fn extract_content<R: std::io::Read + std::io::Seek>(epub: &mut EpubDoc<R>) -> (String, Vec<EpubChunk>) {
         let num_chapters = epub.get_num_chapters();
+        let chapter_titles = Self::chapter_titles_from_toc(epub, num_chapters);
+
+        let mut content = String::new();
+        let mut chunks = Vec::with_capacity(num_chapters);
 
         // Iterate through all chapters in the EPUB
         for chapter_num in 0..num_chapters {
@@ -41,50 +52,197 @@ impl EpubExtractor {
             epub.set_current_chapter(chapter_num);
 
             // Get current chapter content as string (returns Option)
-            if let Some((data, _mime)) = epub.get_current_str() {
-                // Extract text from XHTML content
-                let extracted_text = Self::extract_text_from_xhtml(&data);
-                if !extracted_text.is_empty() {
-                    content.push_str(&extracted_text);
-                    content.push('\n');
+            let Some((data, _mime)) = epub.get_current_str() else {
+                continue;
+            };
+
+            // Convert the chapter's XHTML into Markdown
+            let markdown = Self::xhtml_to_markdown(&data);
+            if markdown.is_empty() {
+                continue;
+            }
+
+            let start_offset = content.len();
+            content.push_str(&markdown);
+            let end_offset = content.len();
+            content.push_str("\n\n");
+
+            let title = chapter_titles
+                .get(chapter_num)
+                .and_then(|t| t.clone())
+                .unwrap_or_else(|| format!("Chapter {}", chapter_num + 1));
+
+            chunks.push(EpubChunk { title, content: markdown, start_offset, end_offset });
+        }
+
+        (content.trim().to_string(), chunks)
+    }
+
+    /// Match each spine position to a navigation-document title, by
+    /// resolving every `<li>`/`NavPoint`'s target path (TOC paths may
+    /// carry a `#fragment` pointing inside a chapter) back to the spine
+    /// resource it belongs to. Returns `None` for spine positions with no
+    /// matching navigation entry, which happens for EPUBs with no nav/NCX
+    /// document, or for spine items the TOC doesn't reference directly.
+    #[cfg(feature = "office")]
+    fn chapter_titles_from_toc<R: std::io::Read + std::io::Seek>(
+        epub: &EpubDoc<R>,
+        num_chapters: usize,
+    ) -> Vec<Option<String>> {
+        let mut path_to_chapter: HashMap<String, usize> = HashMap::new();
+        for (index, spine_id) in epub.spine.iter().enumerate() {
+            if let Some((path, _mime)) = epub.resources.get(spine_id) {
+                path_to_chapter.insert(Self::normalize_epub_path(path), index);
+            }
+        }
+
+        let mut titles = vec![None; num_chapters];
+        let mut pending: Vec<&epub::doc::NavPoint> = epub.toc.iter().collect();
+        while let Some(nav) = pending.pop() {
+            let key = Self::normalize_epub_path(&nav.content);
+            if let Some(&index) = path_to_chapter.get(&key) {
+                if titles[index].is_none() {
+                    titles[index] = Some(nav.label.clone());
                 }
             }
+            pending.extend(nav.children.iter());
         }
 
-        content.trim().to_string()
+        titles
     }
 
-    /// Extract plain text from XHTML content
+    /// Normalize a TOC/resource path to a comparable key by dropping any
+    /// `#fragment` (TOC entries often point at a specific anchor inside a
+    /// chapter file rather than the file itself).
     #[cfg(feature = "office")]
-    fn extract_text_from_xhtml(html: &str) -> String {
-        let mut text = String::new();
-        let mut in_tag = false;
+    fn normalize_epub_path(path: &std::path::Path) -> String {
+        let raw = path.to_string_lossy();
+        raw.split('#').next().unwrap_or(&raw).to_string()
+    }
+
+    /// Convert XHTML content into Markdown, preserving headings, emphasis,
+    /// links, and (possibly nested) lists instead of flattening every tag to
+    /// plain text.
+    ///
+    /// This is a small hand-rolled tag tokenizer rather than a full HTML
+    /// parser: it tracks an open-tag stack deep enough to know which block
+    /// it's in (heading level, list kind/depth, preformatted), emits the
+    /// matching Markdown markup at tag boundaries, and falls back to
+    /// dropping unrecognized tags (keeping their text) so malformed XHTML
+    /// degrades gracefully instead of erroring.
+    #[cfg(feature = "office")]
+    fn xhtml_to_markdown(html: &str) -> String {
+        let mut out = String::new();
+        let mut list_stack: Vec<ListFrame> = Vec::new();
         let mut in_script_style = false;
-        let mut script_style_tag = String::new();
+        let mut in_pre = false;
+        let mut pending_href: Option<String> = None;
 
         let mut chars = html.chars().peekable();
-
         while let Some(ch) = chars.next() {
             if ch == '<' {
-                in_tag = true;
-                script_style_tag.clear();
-                continue;
-            }
+                let mut raw_tag = String::new();
+                for next_ch in chars.by_ref() {
+                    if next_ch == '>' {
+                        break;
+                    }
+                    raw_tag.push(next_ch);
+                }
 
-            if ch == '>' {
-                in_tag = false;
+                let closing = raw_tag.starts_with('/');
+                let body = raw_tag.trim_start_matches('/').trim_end_matches('/');
+                let name = body.split_whitespace().next().unwrap_or("").to_lowercase();
 
-                // Check for script and style closing tags
-                if script_style_tag.to_lowercase().contains("script")
-                    || script_style_tag.to_lowercase().contains("style")
-                {
-                    in_script_style = !script_style_tag.starts_with('/');
+                if name == "script" || name == "style" {
+                    in_script_style = !closing;
+                    continue;
+                }
+                if in_script_style {
+                    continue;
                 }
-                continue;
-            }
 
-            if in_tag {
-                script_style_tag.push(ch);
+                match name.as_str() {
+                    "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                        if !closing {
+                            ensure_blank_line(&mut out);
+                            let level = name[1..].parse::<usize>().unwrap_or(1);
+                            out.push_str(&"#".repeat(level));
+                            out.push(' ');
+                        } else {
+                            out.push_str("\n\n");
+                        }
+                    }
+                    "p" | "div" => {
+                        if closing {
+                            out.push_str("\n\n");
+                        }
+                    }
+                    "br" => out.push('\n'),
+                    "strong" | "b" => out.push_str("**"),
+                    "em" | "i" => out.push('*'),
+                    "code" if !in_pre => out.push('`'),
+                    "pre" => {
+                        if !closing {
+                            ensure_blank_line(&mut out);
+                            out.push_str("```\n");
+                            in_pre = true;
+                        } else {
+                            out.push_str("\n```\n\n");
+                            in_pre = false;
+                        }
+                    }
+                    "blockquote" => {
+                        if !closing {
+                            ensure_blank_line(&mut out);
+                            out.push_str("> ");
+                        } else {
+                            out.push_str("\n\n");
+                        }
+                    }
+                    "hr" => {
+                        ensure_blank_line(&mut out);
+                        out.push_str("---\n\n");
+                    }
+                    "ul" | "ol" => {
+                        if !closing {
+                            list_stack.push(ListFrame { ordered: name == "ol", counter: 0 });
+                        } else {
+                            list_stack.pop();
+                            if list_stack.is_empty() {
+                                out.push('\n');
+                            }
+                        }
+                    }
+                    "li" => {
+                        if !closing {
+                            ensure_line_start(&mut out);
+                            let depth = list_stack.len().saturating_sub(1);
+                            out.push_str(&"  ".repeat(depth));
+                            if let Some(frame) = list_stack.last_mut() {
+                                if frame.ordered {
+                                    frame.counter += 1;
+                                    out.push_str(&format!("{}. ", frame.counter));
+                                } else {
+                                    out.push_str("- ");
+                                }
+                            } else {
+                                out.push_str("- ");
+                            }
+                        } else {
+                            ensure_line_start(&mut out);
+                        }
+                    }
+                    "a" => {
+                        if !closing {
+                            out.push('[');
+                            pending_href = extract_attr(body, "href");
+                        } else {
+                            let href = pending_href.take().unwrap_or_default();
+                            out.push_str(&format!("]({})", href));
+                        }
+                    }
+                    _ => {}
+                }
                 continue;
             }
 
@@ -92,7 +250,6 @@ impl EpubExtractor {
                 continue;
             }
 
-            // Handle HTML entities
             if ch == '&' {
                 let mut entity = String::from("&");
                 while let Some(&next_ch) = chars.peek() {
@@ -102,7 +259,6 @@ impl EpubExtractor {
                         break;
                     }
                 }
-
                 let decoded = match entity.as_str() {
                     "&nbsp;" => " ",
                     "&lt;" => "<",
@@ -111,42 +267,23 @@ impl EpubExtractor {
                     "&quot;" => "\"",
                     "&apos;" => "'",
                     _ => {
-                        text.push_str(&entity);
+                        out.push_str(&entity);
                         continue;
                     }
                 };
-                text.push_str(decoded);
-            } else if ch == '\n' || ch == '\r' || ch == '\t' {
-                // Normalize whitespace
-                if !text.ends_with(' ') && !text.is_empty() {
-                    text.push(' ');
-                }
-            } else if ch == ' ' {
-                // Avoid multiple spaces
-                if !text.ends_with(' ') {
-                    text.push(' ');
-                }
-            } else {
-                text.push(ch);
-            }
-        }
-
-        // Clean up multiple spaces
-        let mut cleaned = String::new();
-        let mut prev_space = false;
-        for ch in text.chars() {
-            if ch == ' ' {
-                if !prev_space {
-                    cleaned.push(ch);
+                out.push_str(decoded);
+            } else if in_pre {
+                out.push(ch);
+            } else if ch == '\n' || ch == '\r' || ch == '\t' || ch == ' ' {
+                if !out.ends_with(' ') && !out.ends_with('\n') {
+                    out.push(' ');
                 }
-                prev_space = true;
             } else {
-                cleaned.push(ch);
-                prev_space = false;
+                out.push(ch);
             }
         }
 
-        cleaned.trim().to_string()
+        collapse_blank_runs(&out)
     }
 
     /// Extract metadata from EPUB document
@@ -220,6 +357,219 @@ impl EpubExtractor {
         // get_cover_id() returns Option<String>
         epub.get_cover_id()
     }
+
+    /// Pull embedded image bytes out of the EPUB: the cover (first entry,
+    /// via [`Self::detect_cover`]) plus every other `image/*` manifest
+    /// resource referenced by an `<img src="...">`/`<image xlink:href="...">`
+    /// in some chapter's XHTML, tagged with the chapter it appeared in.
+    ///
+    /// Called only when `ExtractionConfig::images`' `extract_images` flag is
+    /// set, since decoding every image resource to bytes is wasted work for
+    /// metadata-only callers.
+    #[cfg(feature = "office")]
+    fn extract_images<R: std::io::Read + std::io::Seek>(epub: &mut EpubDoc<R>) -> Vec<EpubImage> {
+        let num_chapters = epub.get_num_chapters();
+        let chapter_titles = Self::chapter_titles_from_toc(epub, num_chapters);
+
+        let mut images = Vec::new();
+        let mut seen_ids = std::collections::HashSet::new();
+
+        if let Some(cover_id) = Self::detect_cover(epub) {
+            if let Some((bytes, mime_type)) = epub.get_resource(&cover_id) {
+                seen_ids.insert(cover_id.clone());
+                images.push(EpubImage { id: cover_id, mime_type, bytes, chapter: None });
+            }
+        }
+
+        let path_to_image: HashMap<String, (String, String)> = epub
+            .resources
+            .iter()
+            .filter(|(_, (_, mime))| mime.starts_with("image/"))
+            .map(|(id, (path, mime))| (Self::normalize_epub_path(path), (id.clone(), mime.clone())))
+            .collect();
+
+        for chapter_num in 0..num_chapters {
+            epub.set_current_chapter(chapter_num);
+            let Some((data, _mime)) = epub.get_current_str() else {
+                continue;
+            };
+
+            for src in Self::extract_image_srcs(&data) {
+                let key = src.split('#').next().unwrap_or(&src).to_string();
+                let Some((id, _mime)) = path_to_image.get(&key) else {
+                    continue;
+                };
+                if !seen_ids.insert(id.clone()) {
+                    continue;
+                }
+                let Some((bytes, mime_type)) = epub.get_resource(id) else {
+                    continue;
+                };
+                let chapter = chapter_titles
+                    .get(chapter_num)
+                    .and_then(|t| t.clone())
+                    .or_else(|| Some(format!("Chapter {}", chapter_num + 1)));
+                images.push(EpubImage { id: id.clone(), mime_type, bytes, chapter });
+            }
+        }
+
+        images
+    }
+
+    /// Collect every `src`/`xlink:href` attribute value from `<img>` and
+    /// (SVG) `<image>` tags in `html`.
+    #[cfg(feature = "office")]
+    fn extract_image_srcs(html: &str) -> Vec<String> {
+        let mut srcs = Vec::new();
+        let mut chars = html.chars().peekable();
+
+        while let Some(ch) = chars.next() {
+            if ch != '<' {
+                continue;
+            }
+            let mut raw_tag = String::new();
+            for next_ch in chars.by_ref() {
+                if next_ch == '>' {
+                    break;
+                }
+                raw_tag.push(next_ch);
+            }
+
+            let name = raw_tag.trim_start_matches('/').split_whitespace().next().unwrap_or("").to_lowercase();
+            if name != "img" && name != "image" {
+                continue;
+            }
+
+            if let Some(src) = extract_attr(&raw_tag, "src").or_else(|| extract_attr(&raw_tag, "xlink:href")) {
+                srcs.push(src);
+            }
+        }
+
+        srcs
+    }
+}
+
+/// Tracks the kind and item counter of one open `<ul>`/`<ol>` in
+/// [`EpubExtractor::xhtml_to_markdown`]'s list stack.
+#[cfg(feature = "office")]
+struct ListFrame {
+    ordered: bool,
+    counter: usize,
+}
+
+/// One semantically-meaningful segment of an EPUB's content: a chapter
+/// (when a nav/NCX document is present) or a single spine item (fallback).
+/// `start_offset`/`end_offset` are byte offsets of `content` within the
+/// concatenated [`EpubExtractor::extract_content`] output.
+#[cfg(feature = "office")]
+#[derive(Debug, Clone)]
+pub struct EpubChunk {
+    pub title: String,
+    pub content: String,
+    pub start_offset: usize,
+    pub end_offset: usize,
+}
+
+/// One image resource surfaced from an EPUB by [`EpubExtractor::extract_images`]:
+/// either the cover (`chapter: None`) or an `image/*` manifest resource
+/// referenced by some chapter's `<img>`/`<image>` tag.
+#[cfg(feature = "office")]
+#[derive(Debug, Clone)]
+pub struct EpubImage {
+    /// Manifest id of the image resource.
+    pub id: String,
+    pub mime_type: String,
+    pub bytes: Vec<u8>,
+    /// Title of the chapter this image was referenced from, `None` for the cover.
+    pub chapter: Option<String>,
+}
+
+/// Pull `attr="value"` out of a raw tag body (e.g. `a href="..." title="..."`),
+/// matching single or double-quoted values. Returns `None` if the attribute
+/// isn't present.
+#[cfg(feature = "office")]
+fn extract_attr(tag_body: &str, attr: &str) -> Option<String> {
+    let needle = format!("{attr}=");
+    let start = find_ascii_ignore_case(tag_body, &needle)? + needle.len();
+    let rest = &tag_body[start..];
+    let quote = rest.chars().next()?;
+    if quote != '"' && quote != '\'' {
+        return None;
+    }
+    let value_start = 1;
+    let value_end = rest[value_start..].find(quote)? + value_start;
+    Some(rest[value_start..value_end].to_string())
+}
+
+/// Find the byte offset of the first case-insensitive match of the ASCII
+/// `needle` in `haystack`.
+///
+/// `needle` must be ASCII (true of every attribute name this module looks
+/// up, e.g. `"href="`/`"src="`). Matching is done directly on `haystack`'s
+/// bytes rather than on a separately lowercased copy: a lowercased copy can
+/// diverge in byte length from the original when an earlier character's
+/// case folding changes its UTF-8 width (e.g. Turkish `İ` U+0130, 2 bytes,
+/// lowercases to `i̇`, 3 bytes), which previously made offsets computed
+/// against the copy land on the wrong byte — or not a char boundary at all
+/// — in the original. An ASCII needle byte can never equal a UTF-8
+/// continuation byte (0x80-0xFF), so any match this function finds is
+/// guaranteed to start and end on a char boundary in `haystack`.
+fn find_ascii_ignore_case(haystack: &str, needle: &str) -> Option<usize> {
+    let haystack = haystack.as_bytes();
+    let needle = needle.as_bytes();
+    if needle.is_empty() || haystack.len() < needle.len() {
+        return None;
+    }
+
+    (0..=haystack.len() - needle.len()).find(|&i| haystack[i..i + needle.len()].eq_ignore_ascii_case(needle))
+}
+
+/// Ensure `out` ends with exactly one blank line before starting a new block
+/// element (heading, blockquote, list, code fence).
+#[cfg(feature = "office")]
+fn ensure_blank_line(out: &mut String) {
+    while out.ends_with(' ') {
+        out.pop();
+    }
+    if !out.is_empty() && !out.ends_with("\n\n") {
+        if !out.ends_with('\n') {
+            out.push('\n');
+        }
+        out.push('\n');
+    }
+}
+
+/// Ensure `out` starts a fresh line, without forcing a full blank line
+/// (used between adjacent `<li>` items).
+#[cfg(feature = "office")]
+fn ensure_line_start(out: &mut String) {
+    while out.ends_with(' ') {
+        out.pop();
+    }
+    if !out.is_empty() && !out.ends_with('\n') {
+        out.push('\n');
+    }
+}
+
+/// Collapse runs of 3+ consecutive newlines down to a single blank line, and
+/// trim trailing spaces from each line, so block-element markup doesn't
+/// accumulate extra whitespace from nested open/close handling.
+#[cfg(feature = "office")]
+fn collapse_blank_runs(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut newline_run = 0;
+    for ch in text.chars() {
+        if ch == '\n' {
+            newline_run += 1;
+            if newline_run <= 2 {
+                out.push(ch);
+            }
+        } else {
+            newline_run = 0;
+            out.push(ch);
+        }
+    }
+    out.trim().to_string()
 }
 
 impl Default for EpubExtractor {
@@ -260,7 +610,7 @@ impl DocumentExtractor for EpubExtractor {
     #[cfg_attr(
         feature = "otel",
         tracing::instrument(
-            skip(self, content, _config),
+            skip(self, content, config),
             fields(
                 extractor.name = self.name(),
                 content.size_bytes = content.len(),
@@ -271,7 +621,7 @@ impl DocumentExtractor for EpubExtractor {
         &self,
         content: &[u8],
         mime_type: &str,
-        _config: &ExtractionConfig,
+        config: &ExtractionConfig,
     ) -> Result<ExtractionResult> {
         // Create a cursor from the content bytes
         let cursor = Cursor::new(content.to_vec());
@@ -280,8 +630,8 @@ impl DocumentExtractor for EpubExtractor {
         let mut epub = EpubDoc::from_reader(cursor)
             .map_err(|e| crate::KreuzbergError::Other(format!("Failed to open EPUB: {}", e)))?;
 
-        // Extract content
-        let extracted_content = Self::extract_content(&mut epub);
+        // Extract content, chunked by chapter
+        let (extracted_content, epub_chunks) = Self::extract_content(&mut epub);
 
         // Extract metadata
         let metadata_map = Self::extract_metadata(&mut epub);
@@ -292,6 +642,46 @@ impl DocumentExtractor for EpubExtractor {
             metadata_with_cover.insert("cover".to_string(), serde_json::json!(cover));
         }
 
+        let chunks = if epub_chunks.is_empty() {
+            None
+        } else {
+            Some(
+                epub_chunks
+                    .into_iter()
+                    .map(|chunk| serde_json::json!({
+                        "title": chunk.title,
+                        "content": chunk.content,
+                        "start_offset": chunk.start_offset,
+                        "end_offset": chunk.end_offset,
+                    }))
+                    .collect(),
+            )
+        };
+
+        // Decoding every embedded image costs real time/memory, so only do
+        // the full manifest walk when the caller has opted in.
+        let extract_images = config.images.as_ref().map(|cfg| cfg.extract_images).unwrap_or(false);
+        let images = if extract_images {
+            let epub_images = Self::extract_images(&mut epub);
+            if epub_images.is_empty() {
+                None
+            } else {
+                Some(
+                    epub_images
+                        .into_iter()
+                        .map(|image| serde_json::json!({
+                            "id": image.id,
+                            "mime_type": image.mime_type,
+                            "bytes": image.bytes,
+                            "chapter": image.chapter,
+                        }))
+                        .collect(),
+                )
+            }
+        } else {
+            None
+        };
+
         Ok(ExtractionResult {
             content: extracted_content,
             mime_type: mime_type.to_string(),
@@ -301,8 +691,8 @@ impl DocumentExtractor for EpubExtractor {
             },
             tables: vec![],
             detected_languages: None,
-            chunks: None,
-            images: None,
+            chunks,
+            images,
         })
     }
 
@@ -346,46 +736,105 @@ mod tests {
     }
 
     #[test]
-    fn test_extract_text_from_xhtml_simple() {
+    fn test_xhtml_to_markdown_simple() {
         let html = "<html><body><p>Hello World</p></body></html>";
-        let text = EpubExtractor::extract_text_from_xhtml(html);
+        let text = EpubExtractor::xhtml_to_markdown(html);
         assert!(text.contains("Hello World"));
     }
 
     #[test]
-    fn test_extract_text_from_xhtml_with_entities() {
+    fn test_xhtml_to_markdown_with_entities() {
         let html = "<p>Hello&nbsp;&amp;&nbsp;World</p>";
-        let text = EpubExtractor::extract_text_from_xhtml(html);
+        let text = EpubExtractor::xhtml_to_markdown(html);
         assert!(text.contains("Hello"));
         assert!(text.contains("World"));
     }
 
     #[test]
-    fn test_extract_text_from_xhtml_removes_script() {
+    fn test_xhtml_to_markdown_removes_script() {
         let html = "<body><p>Text</p><script>alert('bad');</script><p>More</p></body>";
-        let text = EpubExtractor::extract_text_from_xhtml(html);
+        let text = EpubExtractor::xhtml_to_markdown(html);
         assert!(!text.contains("bad"));
         assert!(text.contains("Text"));
         assert!(text.contains("More"));
     }
 
     #[test]
-    fn test_extract_text_from_xhtml_removes_style() {
+    fn test_xhtml_to_markdown_removes_style() {
         let html = "<body><p>Text</p><style>.class { color: red; }</style><p>More</p></body>";
-        let text = EpubExtractor::extract_text_from_xhtml(html);
+        let text = EpubExtractor::xhtml_to_markdown(html);
         assert!(!text.to_lowercase().contains("color"));
         assert!(text.contains("Text"));
         assert!(text.contains("More"));
     }
 
     #[test]
-    fn test_extract_text_from_xhtml_normalizes_whitespace() {
+    fn test_xhtml_to_markdown_normalizes_whitespace() {
         let html = "<p>Hello   \n\t   World</p>";
-        let text = EpubExtractor::extract_text_from_xhtml(html);
+        let text = EpubExtractor::xhtml_to_markdown(html);
         // Should have single spaces
         assert!(text.contains("Hello World") || text.contains("Hello  World"));
     }
 
+    #[test]
+    fn test_xhtml_to_markdown_headings() {
+        let html = "<h1>Chapter One</h1><p>Body text.</p>";
+        let text = EpubExtractor::xhtml_to_markdown(html);
+        assert!(text.contains("# Chapter One"));
+        assert!(text.contains("Body text."));
+    }
+
+    #[test]
+    fn test_xhtml_to_markdown_bold_and_italic() {
+        let html = "<p><strong>bold</strong> and <em>italic</em></p>";
+        let text = EpubExtractor::xhtml_to_markdown(html);
+        assert!(text.contains("**bold**"));
+        assert!(text.contains("*italic*"));
+    }
+
+    #[test]
+    fn test_xhtml_to_markdown_link() {
+        let html = r#"<p><a href="https://example.com">click here</a></p>"#;
+        let text = EpubExtractor::xhtml_to_markdown(html);
+        assert!(text.contains("[click here](https://example.com)"));
+    }
+
+    #[test]
+    fn test_xhtml_to_markdown_link_with_non_ascii_attr_before_href() {
+        // `İ` (U+0130) lowercases to the 3-byte sequence `i̇`, one byte longer
+        // than its own 2-byte UTF-8 encoding. A naive `tag_body.to_lowercase()`
+        // offset search would find `href=` at the wrong byte position once an
+        // `İ` appears earlier in the tag, either missing the link entirely or
+        // slicing `tag_body` off a char boundary.
+        let html = r#"<p><a data-note="İstanbul" href="https://example.com">click here</a></p>"#;
+        let text = EpubExtractor::xhtml_to_markdown(html);
+        assert!(text.contains("[click here](https://example.com)"));
+    }
+
+    #[test]
+    fn test_xhtml_to_markdown_unordered_list() {
+        let html = "<ul><li>First</li><li>Second</li></ul>";
+        let text = EpubExtractor::xhtml_to_markdown(html);
+        assert!(text.contains("- First"));
+        assert!(text.contains("- Second"));
+    }
+
+    #[test]
+    fn test_xhtml_to_markdown_ordered_list() {
+        let html = "<ol><li>First</li><li>Second</li></ol>";
+        let text = EpubExtractor::xhtml_to_markdown(html);
+        assert!(text.contains("1. First"));
+        assert!(text.contains("2. Second"));
+    }
+
+    #[test]
+    fn test_xhtml_to_markdown_nested_list() {
+        let html = "<ul><li>Outer<ul><li>Inner</li></ul></li></ul>";
+        let text = EpubExtractor::xhtml_to_markdown(html);
+        assert!(text.contains("- Outer"));
+        assert!(text.contains("  - Inner"));
+    }
+
     #[test]
     fn test_epub_extractor_supported_mime_types() {
         let extractor = EpubExtractor::new();
@@ -7,50 +7,140 @@
 //! - Metadata extraction: title, author, date from \title{}, \author{}, \date{}
 //! - Section hierarchy: \section{}, \subsection{}, \subsubsection{}, etc.
 //! - Inline formatting: \emph{}, \textbf{}, \textit{}, \texttt{}, \sout{}
-//! - Lists: itemize, enumerate, description environments
-//! - Tables: tabular environment parsing
+//! - Lists: itemize, enumerate, description environments, with arbitrary
+//!   nesting and per-level item numbering
+//! - Tables: tabular environment parsing, including column alignment,
+//!   `\multicolumn`, and `\multirow`
 //! - Code blocks: verbatim, obeylines, Verbatim environments
 //! - Quotes: quote, quotation environments
 //! - Links: \href{url}{text}, \url{}
 //! - Inline code: \verb!code!
 //! - Math: inline ($...$) and display ($$...$$) math preservation
-//! - Citations: \cite{} extraction
+//! - Citations: \cite{} extraction, resolved against supplied BibTeX sources
+//!   when present (see [`bibtex`])
 //! - Footnotes: \footnote{} content extraction
+//! - User macros: `\newcommand`/`\def` definitions are expanded at use
 //! - Special characters and Unicode support
 //! - Images: \includegraphics{} references
 //!
+//! Unrecognized commands and environments can be customized without
+//! forking via [`LatexHandler`], registered with
+//! [`LatexExtractor::with_handler`].
+//!
+//! [`LatexExtractor::parse_tree`] exposes the document as a [`LatexNode`]
+//! tree and [`LatexExtractor::events`] as a flat [`LatexEvent`] stream, for
+//! callers that want more than the rendered Markdown without re-parsing the
+//! source themselves.
+//!
 //! Requires the `office` feature.
 
+mod ast;
+mod bibtex;
+mod handler;
+
 use crate::Result;
 use crate::core::config::ExtractionConfig;
 use crate::plugins::{DocumentExtractor, Plugin};
 use crate::types::{ExtractionResult, Metadata, Table};
 use async_trait::async_trait;
+pub use ast::{Inline, LatexEvent, LatexNode};
+use bibtex::{BibEntry, CitationStyle};
+pub use handler::LatexHandler;
 use regex::Regex;
+use std::collections::HashMap;
+
+/// Maximum recursion depth when expanding user macros, guarding against a
+/// self-referential `\newcommand`/`\def` looping forever.
+const MAX_MACRO_EXPANSION_DEPTH: usize = 32;
 
 /// LaTeX document extractor
-pub struct LatexExtractor;
+#[derive(Default)]
+pub struct LatexExtractor {
+    /// Resolved BibTeX entries, keyed by citation key, supplied via
+    /// [`Self::with_bibliography`].
+    bibliography: HashMap<String, BibEntry>,
+    citation_style: CitationStyle,
+    /// Constructs a fresh [`LatexHandler`] for each document parsed, since
+    /// the extractor itself is shared across concurrent extractions while a
+    /// handler carries per-document mutable state.
+    handler_factory: Option<std::sync::Arc<dyn Fn() -> Box<dyn LatexHandler + Send> + Send + Sync>>,
+}
 
 impl LatexExtractor {
-    /// Create a new LaTeX extractor.
+    /// Create a new LaTeX extractor with no bibliography or handler attached.
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Register a factory producing a [`LatexHandler`] for each document
+    /// parsed, letting callers override rendering for project-specific
+    /// macros (`\acro`, `\code`, ...) and custom environments without
+    /// forking the crate.
+    pub fn with_handler<F, H>(mut self, factory: F) -> Self
+    where
+        F: Fn() -> H + Send + Sync + 'static,
+        H: LatexHandler + Send + 'static,
+    {
+        self.handler_factory = Some(std::sync::Arc::new(move || Box::new(factory()) as Box<dyn LatexHandler + Send>));
+        self
+    }
+
+    /// Supply one or more `.bib` sources to resolve `\cite{}` keys against.
+    ///
+    /// The document's own `\bibliography{}`/`\addbibresource{}` commands only
+    /// name which files to use; callers are responsible for locating and
+    /// reading those files and passing their contents here, since the
+    /// extractor itself only sees the `.tex` bytes.
+    pub fn with_bibliography<I, S>(mut self, sources: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        for source in sources {
+            self.bibliography.extend(bibtex::parse_bibliography(source.as_ref()));
+        }
+        self
+    }
+
+    /// Set the rendering style used for inline citations and the generated
+    /// references section. Defaults to [`CitationStyle::Numeric`].
+    pub fn with_citation_style(mut self, style: CitationStyle) -> Self {
+        self.citation_style = style;
+        self
     }
 
     /// Parse LaTeX content and extract text.
-    fn extract_from_latex(content: &str) -> (String, Metadata, Vec<Table>) {
-        let mut extractor = LatexParser::new(content);
+    fn extract_from_latex(&self, content: &str) -> (String, Metadata, Vec<Table>) {
+        let handler = self.handler_factory.as_ref().map(|factory| factory());
+        let mut extractor = LatexParser::new(content, &self.bibliography, self.citation_style, handler);
         let text = extractor.parse();
         let metadata = extractor.metadata;
         let tables = extractor.tables;
 
         (text, metadata, tables)
     }
-}
 
-impl Default for LatexExtractor {
-    fn default() -> Self {
-        Self::new()
+    /// Parse LaTeX content into a structured [`LatexNode`] tree instead of
+    /// flattened Markdown.
+    ///
+    /// Render it with [`ast::render_markdown`], or serialize it directly
+    /// (e.g. via `serde_json`) for programmatic consumption.
+    pub fn parse_tree(&self, content: &str) -> Vec<LatexNode> {
+        let handler = self.handler_factory.as_ref().map(|factory| factory());
+        let mut parser = LatexParser::new(content, &self.bibliography, self.citation_style, handler);
+        parser.extract_metadata();
+        parser.build_tree()
+    }
+
+    /// Parse LaTeX content into a flat sequence of [`LatexEvent`]s in
+    /// reading order, for consumers that want to stream to a sink or fold
+    /// over the document without holding the fully rendered Markdown (or
+    /// the [`LatexNode`] tree behind it) in memory at once.
+    ///
+    /// Built on top of [`Self::parse_tree`]; [`ast::render_markdown`] folds
+    /// the same tree into the Markdown [`Self::extract_from_latex`] returns.
+    pub fn events(&self, content: &str) -> impl Iterator<Item = LatexEvent> {
+        ast::flatten_events(&self.parse_tree(content)).into_iter()
     }
 }
 
@@ -96,7 +186,7 @@ impl DocumentExtractor for LatexExtractor {
         _config: &ExtractionConfig,
     ) -> Result<ExtractionResult> {
         let latex_str = String::from_utf8_lossy(content).to_string();
-        let (text, metadata, tables) = Self::extract_from_latex(&latex_str);
+        let (text, metadata, tables) = self.extract_from_latex(&latex_str);
 
         Ok(ExtractionResult {
             content: text,
@@ -119,18 +209,142 @@ impl DocumentExtractor for LatexExtractor {
 }
 
 /// Internal LaTeX parser
-struct LatexParser {
+struct LatexParser<'a> {
     content: String,
     metadata: Metadata,
     tables: Vec<Table>,
+    bibliography: &'a HashMap<String, BibEntry>,
+    citation_style: CitationStyle,
+    /// Citation keys in order of first use, each resolved or not.
+    citation_order: Vec<String>,
+    handler: Option<Box<dyn LatexHandler + Send>>,
+    /// User macros collected from `\newcommand`/`\def` in the preamble.
+    macros: HashMap<String, MacroDef>,
+}
+
+/// A user-defined macro from `\newcommand{\foo}[n][default]{body}` or
+/// `\def\foo{body}`.
+#[derive(Debug, Clone)]
+struct MacroDef {
+    arg_count: usize,
+    default_first_opt: Option<String>,
+    body: String,
 }
 
-impl LatexParser {
-    fn new(content: &str) -> Self {
+/// Per-column alignment parsed from a tabular/array column spec such as
+/// `{l c r | p{3cm}}`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ColumnAlign {
+    Left,
+    Center,
+    Right,
+}
+
+impl ColumnAlign {
+    /// The Markdown header-separator cell for this alignment, e.g. `:---:`.
+    fn separator(self) -> &'static str {
+        match self {
+            ColumnAlign::Left => "---",
+            ColumnAlign::Center => ":---:",
+            ColumnAlign::Right => "---:",
+        }
+    }
+}
+
+impl<'a> LatexParser<'a> {
+    fn new(
+        content: &str,
+        bibliography: &'a HashMap<String, BibEntry>,
+        citation_style: CitationStyle,
+        handler: Option<Box<dyn LatexHandler + Send>>,
+    ) -> Self {
         Self {
             content: content.to_string(),
             metadata: Metadata::default(),
             tables: Vec::new(),
+            bibliography,
+            citation_style,
+            citation_order: Vec::new(),
+            handler,
+            macros: HashMap::new(),
+        }
+    }
+
+    /// Give the registered handler, if any, first refusal on an
+    /// already-named command. Speculatively reads its optional `[...]`
+    /// and `{...}` arguments from a clone of `chars` and only commits that
+    /// advancement (via `*chars = probe`) when the handler actually
+    /// produces output, so a `None` result falls through to the built-in
+    /// dispatch with `chars` untouched.
+    fn try_handle_command(
+        &mut self,
+        cmd: &str,
+        chars: &mut std::iter::Peekable<std::str::Chars>,
+    ) -> Option<String> {
+        let mut handler = self.handler.take()?;
+        let mut probe = chars.clone();
+        let args = Self::peek_command_args(&mut probe);
+        let rendered = handler.command(cmd, &args);
+        self.handler = Some(handler);
+        if rendered.is_some() {
+            *chars = probe;
+        }
+        rendered
+    }
+
+    /// Same speculative-probe strategy as [`Self::try_handle_command`], but
+    /// for a `\begin{name}...\end{name}` environment body.
+    fn try_handle_environment(
+        &mut self,
+        env_name: &str,
+        chars: &mut std::iter::Peekable<std::str::Chars>,
+    ) -> Option<String> {
+        let mut handler = self.handler.take()?;
+        let mut probe = chars.clone();
+        let body = self.extract_environment(&mut probe, env_name);
+        let rendered = handler.environment(env_name, &body);
+        self.handler = Some(handler);
+        if rendered.is_some() {
+            *chars = probe;
+        }
+        rendered
+    }
+
+    /// Read a command's optional `[...]` (discarded) and `{...}` argument,
+    /// without assuming anything about what the command does with it.
+    fn peek_command_args(chars: &mut std::iter::Peekable<std::str::Chars>) -> Vec<String> {
+        if let Some('[') = chars.peek() {
+            chars.next();
+            while let Some(&c) = chars.peek() {
+                if c == ']' {
+                    chars.next();
+                    break;
+                }
+                chars.next();
+            }
+        }
+
+        if let Some('{') = chars.peek() {
+            chars.next();
+            let mut depth = 1;
+            let mut content = String::new();
+            for c in chars.by_ref() {
+                if c == '{' {
+                    depth += 1;
+                    content.push(c);
+                } else if c == '}' {
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+                    content.push(c);
+                } else {
+                    content.push(c);
+                }
+            }
+            vec![content]
+        } else {
+            Vec::new()
         }
     }
 
@@ -138,8 +352,39 @@ impl LatexParser {
         // Extract metadata from preamble
         self.extract_metadata();
 
+        // Scan for \newcommand/\def macro definitions before expanding the body
+        self.extract_macros();
+
         // Process content and extract text
-        self.extract_content()
+        let content = self.content.clone();
+        let mut result = self.extract_content(&content);
+
+        if !self.citation_order.is_empty() {
+            result.push_str("\n\n## References\n\n");
+            for (index, key) in self.citation_order.clone().iter().enumerate() {
+                if let Some(entry) = self.bibliography.get(key) {
+                    result.push_str(&self.citation_style.format_reference(entry, index + 1));
+                    result.push('\n');
+                }
+            }
+        }
+
+        result.trim().to_string()
+    }
+
+    /// Resolve a `\cite{key}` site to its formatted inline citation, falling
+    /// back to the bare `[key]` placeholder when the key has no matching
+    /// bibliography entry.
+    fn resolve_citation(&mut self, key: &str) -> String {
+        let Some(entry) = self.bibliography.get(key) else {
+            return format!("[{}]", key);
+        };
+
+        if !self.citation_order.contains(&key.to_string()) {
+            self.citation_order.push(key.to_string());
+        }
+        let number = self.citation_order.iter().position(|k| k == key).unwrap() + 1;
+        self.citation_style.format_inline(entry, number)
     }
 
     fn extract_metadata(&mut self) {
@@ -196,9 +441,209 @@ impl LatexParser {
             .and_then(|caps| caps.get(1).or_else(|| caps.get(2)).map(|m| m.as_str().to_string()))
     }
 
-    fn extract_content(&mut self) -> String {
+    /// Scan the whole document for `\newcommand`/`\renewcommand`/`\def`
+    /// macro definitions and store them in [`Self::macros`].
+    fn extract_macros(&mut self) {
+        let content = self.content.clone();
+        let mut chars = content.chars().peekable();
+
+        while let Some(ch) = chars.next() {
+            if ch != '\\' {
+                continue;
+            }
+            let cmd = self.read_command_name(&mut chars);
+            match cmd.as_str() {
+                "newcommand" | "renewcommand" => self.parse_newcommand(&mut chars),
+                "def" => self.parse_def(&mut chars),
+                _ => {}
+            }
+        }
+    }
+
+    /// Parse `\newcommand{\foo}[n][default]{body}` (or the braceless-name
+    /// form `\newcommand\foo{body}`) starting right after the command name.
+    fn parse_newcommand(&mut self, chars: &mut std::iter::Peekable<std::str::Chars>) {
+        let name = match chars.peek() {
+            Some('{') => {
+                chars.next();
+                let (inner, _) = self.read_braced_content(chars);
+                inner.trim_start_matches('\\').to_string()
+            }
+            Some('\\') => {
+                chars.next();
+                self.read_command_name(chars)
+            }
+            _ => return,
+        };
+
+        let mut arg_count = 0usize;
+        if let Some('[') = chars.peek() {
+            chars.next();
+            let mut num = String::new();
+            while let Some(&c) = chars.peek() {
+                if c == ']' {
+                    chars.next();
+                    break;
+                }
+                num.push(chars.next().unwrap());
+            }
+            arg_count = num.trim().parse().unwrap_or(0);
+        }
+
+        let mut default_first_opt = None;
+        if arg_count > 0 && chars.peek() == Some(&'[') {
+            chars.next();
+            let mut default = String::new();
+            while let Some(&c) = chars.peek() {
+                if c == ']' {
+                    chars.next();
+                    break;
+                }
+                default.push(chars.next().unwrap());
+            }
+            default_first_opt = Some(default);
+        }
+
+        if let Some('{') = chars.peek() {
+            chars.next();
+            let (body, _) = self.read_braced_content(chars);
+            self.macros.insert(
+                name,
+                MacroDef {
+                    arg_count,
+                    default_first_opt,
+                    body,
+                },
+            );
+        }
+    }
+
+    /// Parse `\def\foo{body}`. TeX's parameter-text syntax (`\def\foo#1{body}`)
+    /// isn't supported; the macro is registered as a plain zero-argument
+    /// substitution.
+    fn parse_def(&mut self, chars: &mut std::iter::Peekable<std::str::Chars>) {
+        let Some('\\') = chars.peek() else { return };
+        chars.next();
+        let name = self.read_command_name(chars);
+
+        while let Some(&c) = chars.peek() {
+            if c == '{' {
+                break;
+            }
+            chars.next();
+        }
+
+        if let Some('{') = chars.peek() {
+            chars.next();
+            let (body, _) = self.read_braced_content(chars);
+            self.macros.insert(
+                name,
+                MacroDef {
+                    arg_count: 0,
+                    default_first_opt: None,
+                    body,
+                },
+            );
+        }
+    }
+
+    /// Read a macro invocation's arguments according to its [`MacroDef`],
+    /// starting right after the macro name has been consumed.
+    fn read_macro_args(macro_def: &MacroDef, chars: &mut std::iter::Peekable<std::str::Chars>) -> Vec<String> {
+        let mut args = Vec::new();
+        let mut remaining = macro_def.arg_count;
+
+        if remaining > 0 && macro_def.default_first_opt.is_some() {
+            if let Some('[') = chars.peek() {
+                chars.next();
+                let mut opt = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c == ']' {
+                        chars.next();
+                        break;
+                    }
+                    opt.push(chars.next().unwrap());
+                }
+                args.push(opt);
+            } else {
+                args.push(macro_def.default_first_opt.clone().unwrap());
+            }
+            remaining -= 1;
+        }
+
+        for _ in 0..remaining {
+            if let Some('{') = chars.peek() {
+                chars.next();
+                let mut depth = 1;
+                let mut arg = String::new();
+                for c in chars.by_ref() {
+                    if c == '{' {
+                        depth += 1;
+                        arg.push(c);
+                    } else if c == '}' {
+                        depth -= 1;
+                        if depth == 0 {
+                            break;
+                        }
+                        arg.push(c);
+                    } else {
+                        arg.push(c);
+                    }
+                }
+                args.push(arg);
+            } else {
+                args.push(String::new());
+            }
+        }
+
+        args
+    }
+
+    fn substitute_macro_args(body: &str, args: &[String]) -> String {
+        let mut result = body.to_string();
+        for (i, arg) in args.iter().enumerate() {
+            result = result.replace(&format!("#{}", i + 1), arg);
+        }
+        result
+    }
+
+    /// Recursively expand any macro calls within already-substituted macro
+    /// body text, bounded by `depth` so a self-referential macro can't loop
+    /// forever.
+    fn expand_macros_in(&self, text: &str, depth: usize) -> String {
+        if depth > MAX_MACRO_EXPANSION_DEPTH {
+            return text.to_string();
+        }
+
         let mut result = String::new();
-        let mut chars = self.content.chars().peekable();
+        let mut chars = text.chars().peekable();
+
+        while let Some(ch) = chars.next() {
+            if ch != '\\' {
+                result.push(ch);
+                continue;
+            }
+
+            let cmd = self.read_command_name(&mut chars);
+            match self.macros.get(&cmd).cloned() {
+                Some(macro_def) => {
+                    let args = Self::read_macro_args(&macro_def, &mut chars);
+                    let substituted = Self::substitute_macro_args(&macro_def.body, &args);
+                    result.push_str(&self.expand_macros_in(&substituted, depth + 1));
+                }
+                None => {
+                    result.push('\\');
+                    result.push_str(&cmd);
+                }
+            }
+        }
+
+        result
+    }
+
+    fn extract_content(&mut self, content: &str) -> String {
+        let mut result = String::new();
+        let mut chars = content.chars().peekable();
         let mut in_document = false;
         let mut in_preamble = true;
 
@@ -216,6 +661,21 @@ impl LatexParser {
                     // Check for environment starts
                     let cmd = self.read_command_name(&mut chars);
 
+                    if let Some(macro_def) = self.macros.get(&cmd).cloned() {
+                        let args = Self::read_macro_args(&macro_def, &mut chars);
+                        let substituted = Self::substitute_macro_args(&macro_def.body, &args);
+                        let expanded = self.expand_macros_in(&substituted, 0);
+                        result.push_str(&self.process_inline_content(&expanded));
+                        continue;
+                    }
+
+                    if cmd != "begin" && cmd != "end" {
+                        if let Some(rendered) = self.try_handle_command(&cmd, &mut chars) {
+                            result.push_str(&rendered);
+                            continue;
+                        }
+                    }
+
                     match cmd.as_str() {
                         // Compiler requires this pattern
                         "begin" => {
@@ -231,40 +691,60 @@ impl LatexParser {
                                     env_name.push(chars.next().unwrap());
                                 }
 
-                                match env_name.as_str() {
-                                    "document" => {
-                                        in_document = true;
-                                        in_preamble = false;
-                                    }
-                                    "tabular" => {
-                                        // Extract table
-                                        let table_content = self.extract_environment(&mut chars, "tabular");
-                                        if let Some((text, table)) = self.parse_tabular(&table_content) {
-                                            result.push_str(&text);
+                                let builtin_environments = [
+                                    "document",
+                                    "tabular",
+                                    "itemize",
+                                    "enumerate",
+                                    "description",
+                                    "quote",
+                                    "quotation",
+                                    "verbatim",
+                                    "Verbatim",
+                                    "obeylines",
+                                ];
+
+                                if !builtin_environments.contains(&env_name.as_str())
+                                    && let Some(rendered) = self.try_handle_environment(&env_name, &mut chars)
+                                {
+                                    result.push_str(&rendered);
+                                    result.push('\n');
+                                } else {
+                                    match env_name.as_str() {
+                                        "document" => {
+                                            in_document = true;
+                                            in_preamble = false;
+                                        }
+                                        "tabular" => {
+                                            // Extract table
+                                            let table_content = self.extract_environment(&mut chars, "tabular");
+                                            if let Some((text, table)) = self.parse_tabular(&table_content) {
+                                                result.push_str(&text);
+                                                result.push('\n');
+                                                self.tables.push(table);
+                                            }
+                                        }
+                                        "itemize" | "enumerate" | "description" => {
+                                            let list_content = self.extract_environment(&mut chars, &env_name);
+                                            let list_text = self.parse_list(&list_content, &env_name);
+                                            result.push_str(&list_text);
+                                            result.push('\n');
+                                        }
+                                        "quote" | "quotation" => {
+                                            let quote_content = self.extract_environment(&mut chars, &env_name);
+                                            result.push_str(&self.process_inline_content(&quote_content));
+                                            result.push('\n');
+                                        }
+                                        "verbatim" | "Verbatim" | "obeylines" => {
+                                            let code_content = self.extract_environment(&mut chars, &env_name);
+                                            result.push_str(&code_content);
+                                            result.push('\n');
+                                        }
+                                        _ => {
+                                            let env_content = self.extract_environment(&mut chars, &env_name);
+                                            result.push_str(&self.process_inline_content(&env_content));
                                             result.push('\n');
-                                            self.tables.push(table);
                                         }
-                                    }
-                                    "itemize" | "enumerate" | "description" => {
-                                        let list_content = self.extract_environment(&mut chars, &env_name);
-                                        let list_text = self.parse_list(&list_content, &env_name);
-                                        result.push_str(&list_text);
-                                        result.push('\n');
-                                    }
-                                    "quote" | "quotation" => {
-                                        let quote_content = self.extract_environment(&mut chars, &env_name);
-                                        result.push_str(&self.process_inline_content(&quote_content));
-                                        result.push('\n');
-                                    }
-                                    "verbatim" | "Verbatim" | "obeylines" => {
-                                        let code_content = self.extract_environment(&mut chars, &env_name);
-                                        result.push_str(&code_content);
-                                        result.push('\n');
-                                    }
-                                    _ => {
-                                        let env_content = self.extract_environment(&mut chars, &env_name);
-                                        result.push_str(&self.process_inline_content(&env_content));
-                                        result.push('\n');
                                     }
                                 }
                             }
@@ -455,16 +935,28 @@ impl LatexParser {
                                 if let Some('{') = chars.peek() {
                                     chars.next();
                                     let (key, _) = self.read_braced_content(&mut chars);
-                                    if pages.is_empty() {
-                                        result.push_str(&format!("[{}]", key));
+                                    let citation = self.resolve_citation(&key);
+                                    if pages.is_empty() || !self.bibliography.contains_key(&key) {
+                                        result.push_str(&citation);
                                     } else {
-                                        result.push_str(&format!("[{}:{}]", key, pages));
+                                        result.push_str(&format!("{}:{}", citation, pages));
                                     }
                                 }
                             } else if let Some('{') = chars.peek() {
                                 chars.next();
                                 let (key, _) = self.read_braced_content(&mut chars);
-                                result.push_str(&format!("[{}]", key));
+                                let citation = self.resolve_citation(&key);
+                                result.push_str(&citation);
+                            }
+                        }
+                        "bibliography" | "addbibresource" => {
+                            // These only name external .bib files; resolution
+                            // happens against sources supplied up front via
+                            // `LatexExtractor::with_bibliography`, so just
+                            // consume the argument without emitting text.
+                            if let Some('{') = chars.peek() {
+                                chars.next();
+                                let (_content, _) = self.read_braced_content(&mut chars);
                             }
                         }
                         "emph" | "textit" => {
@@ -802,11 +1294,18 @@ impl LatexParser {
                     }
                 }
 
-                if check_str.starts_with(&format!("\\end{{{}", env_name)) || check_str == "\\end" {
+                let matched_named_end = check_str.starts_with(&format!("\\end{{{}", env_name));
+                if matched_named_end || check_str == "\\end" {
                     // Found end of environment
                     // consume up to closing }
-                    let mut depth = 0;
-                    let mut found_brace = false;
+                    //
+                    // When we matched the named form above, `check_str` already
+                    // swallowed the opening `{` of `\end{env_name}` while scanning
+                    // for the name, so the very next `}` closes it; the bare
+                    // `\end` fallback hasn't seen an opening brace yet and still
+                    // needs to find one.
+                    let mut depth = if matched_named_end { 1 } else { 0 };
+                    let mut found_brace = matched_named_end;
                     for c in chars.by_ref() {
                         if c == '{' {
                             depth += 1;
@@ -831,6 +1330,14 @@ impl LatexParser {
     }
 
     fn parse_list(&self, content: &str, list_type: &str) -> String {
+        self.parse_list_at_depth(content, list_type, 0)
+    }
+
+    /// Recursive worker behind [`Self::parse_list`]. `depth` is the nesting
+    /// level (0 for the outermost list) and controls how far each emitted
+    /// line is indented, two spaces per level.
+    fn parse_list_at_depth(&self, content: &str, list_type: &str, depth: usize) -> String {
+        let indent = "  ".repeat(depth);
         let mut result = String::new();
         let mut in_item = false;
         let mut current_item = String::new();
@@ -840,9 +1347,11 @@ impl LatexParser {
 
         while let Some(ch) = chars.next() {
             if ch == '\\' {
-                if chars.peek().is_some() {
-                    if let Some(&'i') = chars.peek() {
-                        // Could be \item
+                if let Some(&next) = chars.peek() {
+                    // Only "\item" and "\begin" (for a nested list) need
+                    // special handling here; anything else is reproduced
+                    // verbatim via the `_` arm below.
+                    if next == 'i' || next == 'b' {
                         let mut cmd = String::from("\\");
                         while let Some(&c) = chars.peek() {
                             if c.is_alphabetic() {
@@ -852,42 +1361,75 @@ impl LatexParser {
                             }
                         }
 
-                        if cmd == "\\item" {
-                            if in_item && !current_item.is_empty() {
-                                let prefix = match list_type {
-                                    "enumerate" => format!("{}. ", item_count),
-                                    "description" => {
-                                        // Extract label from [...]
-                                        String::new()
-                                    }
-                                    _ => "- ".to_string(),
-                                };
-                                result.push_str(&prefix);
-                                result.push_str(current_item.trim());
-                                result.push('\n');
-                                item_count += 1;
-                            }
+                        match cmd.as_str() {
+                            "\\item" => {
+                                if in_item && !current_item.is_empty() {
+                                    let prefix = match list_type {
+                                        "enumerate" => format!("{}. ", item_count),
+                                        "description" => {
+                                            // Extract label from [...]
+                                            String::new()
+                                        }
+                                        _ => "- ".to_string(),
+                                    };
+                                    result.push_str(&indent);
+                                    result.push_str(&prefix);
+                                    result.push_str(current_item.trim());
+                                    result.push('\n');
+                                    item_count += 1;
+                                }
 
-                            in_item = true;
-                            current_item.clear();
+                                in_item = true;
+                                current_item.clear();
 
-                            // Check for optional parameter [label]
-                            if let Some('[') = chars.peek() {
-                                chars.next();
-                                let mut label = String::new();
-                                while let Some(&c) = chars.peek() {
-                                    if c == ']' {
-                                        chars.next();
-                                        break;
+                                // Check for optional parameter [label]
+                                if let Some('[') = chars.peek() {
+                                    chars.next();
+                                    let mut label = String::new();
+                                    while let Some(&c) = chars.peek() {
+                                        if c == ']' {
+                                            chars.next();
+                                            break;
+                                        }
+                                        label.push(chars.next().unwrap());
+                                    }
+                                    if list_type == "description" {
+                                        result.push_str(&indent);
+                                        result.push_str(&format!("{}: ", label));
                                     }
-                                    label.push(chars.next().unwrap());
                                 }
-                                if list_type == "description" {
-                                    result.push_str(&format!("{}: ", label));
+                            }
+                            "\\begin" => {
+                                if let Some('{') = chars.peek() {
+                                    chars.next();
+                                    let mut env_name = String::new();
+                                    while let Some(&c) = chars.peek() {
+                                        if c == '}' {
+                                            chars.next();
+                                            break;
+                                        }
+                                        env_name.push(chars.next().unwrap());
+                                    }
+
+                                    if matches!(env_name.as_str(), "itemize" | "enumerate" | "description") {
+                                        let inner_content = self.extract_environment(&mut chars, &env_name);
+                                        let nested = self.parse_list_at_depth(&inner_content, &env_name, depth + 1);
+                                        current_item.push('\n');
+                                        current_item.push_str(nested.trim_end());
+                                        current_item.push('\n');
+                                    } else {
+                                        // Not a nested list we recurse into: reproduce the
+                                        // `\begin{env_name}` we just consumed verbatim, as
+                                        // before this method understood `\begin` at all.
+                                        current_item.push_str("\\begin{");
+                                        current_item.push_str(&env_name);
+                                        current_item.push('}');
+                                    }
+                                } else {
+                                    current_item.push_str(&cmd);
                                 }
                             }
-                        } else {
-                            current_item.push_str(&cmd);
+                            _ => current_item.push_str(&cmd),
                         }
                     } else {
                         current_item.push(ch);
@@ -905,6 +1447,7 @@ impl LatexParser {
                 "enumerate" => format!("{}. ", item_count),
                 _ => "- ".to_string(),
             };
+            result.push_str(&indent);
             result.push_str(&prefix);
             result.push_str(current_item.trim());
             result.push('\n');
@@ -913,7 +1456,148 @@ impl LatexParser {
         result
     }
 
+    /// Parse a tabular/array column spec (the `l c r | p{3cm}` inside
+    /// `\begin{tabular}{...}`) into one [`ColumnAlign`] per data column.
+    /// Vertical bars (`|`) and column separators (`@{...}`, `!{...}`) don't
+    /// produce a column; `p`/`m`/`b` paragraph columns are treated as `Left`.
+    fn parse_column_spec(spec: &str) -> Vec<ColumnAlign> {
+        fn skip_braced(chars: &mut std::iter::Peekable<std::str::Chars>) {
+            if chars.peek() == Some(&'{') {
+                chars.next();
+                let mut depth = 1;
+                for c in chars.by_ref() {
+                    if c == '{' {
+                        depth += 1;
+                    } else if c == '}' {
+                        depth -= 1;
+                        if depth == 0 {
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut aligns = Vec::new();
+        let mut chars = spec.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                'l' => aligns.push(ColumnAlign::Left),
+                'c' => aligns.push(ColumnAlign::Center),
+                'r' => aligns.push(ColumnAlign::Right),
+                'p' | 'm' | 'b' => {
+                    aligns.push(ColumnAlign::Left);
+                    skip_braced(&mut chars);
+                }
+                '@' | '!' => skip_braced(&mut chars),
+                _ => {}
+            }
+        }
+
+        aligns
+    }
+
+    /// Read up to `n` consecutive brace-delimited groups from the start of
+    /// `s` (skipping leading whitespace before each), as used by
+    /// `\multicolumn{span}{align}{content}` and `\multirow{n}{width}{content}`.
+    fn read_n_braced_args(s: &str, n: usize) -> Vec<String> {
+        let mut args = Vec::new();
+        let mut chars = s.chars().peekable();
+
+        for _ in 0..n {
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() {
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+
+            if chars.peek() != Some(&'{') {
+                break;
+            }
+            chars.next();
+
+            let mut depth = 1;
+            let mut arg = String::new();
+            for c in chars.by_ref() {
+                if c == '{' {
+                    depth += 1;
+                    arg.push(c);
+                } else if c == '}' {
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+                    arg.push(c);
+                } else {
+                    arg.push(c);
+                }
+            }
+            args.push(arg);
+        }
+
+        args
+    }
+
+    /// Finalize one scanned tabular cell, expanding `\multicolumn` into
+    /// `span` cells and unwrapping `\multirow` down to its content. The
+    /// cells a `\multirow` covers in the following rows are left blank by
+    /// the source itself (the usual `& &` convention); any row left short
+    /// as a result is padded out to rectangular at the end of
+    /// [`Self::parse_tabular`].
+    fn push_tabular_cell(&self, current_row: &mut Vec<String>, cell_text: &str) {
+        let trimmed = cell_text.trim();
+
+        if let Some(rest) = trimmed.strip_prefix("\\multicolumn") {
+            let args = Self::read_n_braced_args(rest, 3);
+            if let [span, _align, inner] = args.as_slice() {
+                let span: usize = span.trim().parse().unwrap_or(1).max(1);
+                current_row.push(self.process_inline_content(inner.trim()));
+                for _ in 1..span {
+                    current_row.push(String::new());
+                }
+                return;
+            }
+        }
+
+        if let Some(rest) = trimmed.strip_prefix("\\multirow") {
+            let args = Self::read_n_braced_args(rest, 3);
+            if let [_row_span, _width, inner] = args.as_slice() {
+                current_row.push(self.process_inline_content(inner.trim()));
+                return;
+            }
+        }
+
+        current_row.push(self.process_inline_content(trimmed));
+    }
+
     fn parse_tabular(&self, content: &str) -> Option<(String, Table)> {
+        // The column spec, e.g. `{l c r}`, precedes the row data.
+        let trimmed_start = content.trim_start();
+        let (column_aligns, content) = if trimmed_start.starts_with('{') {
+            let mut depth = 0;
+            let mut end = None;
+            for (i, c) in trimmed_start.char_indices() {
+                if c == '{' {
+                    depth += 1;
+                } else if c == '}' {
+                    depth -= 1;
+                    if depth == 0 {
+                        end = Some(i);
+                        break;
+                    }
+                }
+            }
+            match end {
+                Some(end) => (Self::parse_column_spec(&trimmed_start[1..end]), &trimmed_start[end + 1..]),
+                None => (Vec::new(), content),
+            }
+        } else {
+            (Vec::new(), content)
+        };
+
         // Parse LaTeX tabular environment
         let mut cells = Vec::new();
         let mut current_row = Vec::new();
@@ -924,16 +1608,18 @@ impl LatexParser {
         while let Some(ch) = chars.next() {
             if ch == '&' {
                 // Cell separator
-                let processed = self.process_inline_content(current_cell.trim());
-                current_row.push(processed);
+                self.push_tabular_cell(&mut current_row, &current_cell);
                 current_cell.clear();
             } else if ch == '\\' {
                 if let Some(&next_c) = chars.peek() {
                     if next_c == '\\' {
                         chars.next();
                         // Row separator
-                        let processed = self.process_inline_content(current_cell.trim());
-                        current_row.push(processed);
+                        self.push_tabular_cell(&mut current_row, &current_cell);
+                        current_cell.clear();
+                        if current_row.len() < column_aligns.len() {
+                            current_row.resize(column_aligns.len(), String::new());
+                        }
 
                         if !current_row.is_empty() {
                             cells.push(current_row.clone());
@@ -977,14 +1663,24 @@ impl LatexParser {
 
         // Don't forget last cell and row
         if !current_cell.is_empty() {
-            let processed = self.process_inline_content(current_cell.trim());
-            current_row.push(processed);
+            self.push_tabular_cell(&mut current_row, &current_cell);
         }
 
         if !current_row.is_empty() {
+            if current_row.len() < column_aligns.len() {
+                current_row.resize(column_aligns.len(), String::new());
+            }
             cells.push(current_row);
         }
 
+        // A row can run wider than the parsed column spec (e.g. via
+        // `\multicolumn`), or there may be no spec at all; pad every row out
+        // to the widest one so `Table.markdown` stays rectangular.
+        let num_cols = cells.iter().map(Vec::len).max().unwrap_or(0);
+        for row in &mut cells {
+            row.resize(num_cols, String::new());
+        }
+
         if !cells.is_empty() {
             // Build markdown representation
             let mut markdown = String::new();
@@ -996,7 +1692,12 @@ impl LatexParser {
                 markdown.push('\n');
                 // Add header separator after first row
                 if i == 0 && cells.len() > 1 {
-                    markdown.push_str(&"|".repeat(row.len() + 1));
+                    markdown.push('|');
+                    for col in 0..num_cols {
+                        let align = column_aligns.get(col).copied().unwrap_or(ColumnAlign::Left);
+                        markdown.push_str(align.separator());
+                        markdown.push('|');
+                    }
                     markdown.push('\n');
                 }
             }
@@ -1012,6 +1713,183 @@ impl LatexParser {
             None
         }
     }
+
+    /// Build a structured [`LatexNode`] tree for the document.
+    ///
+    /// This is a separate block-level pass from [`Self::extract_content`]:
+    /// it recognizes sections, math, lists, and tabular environments, and
+    /// folds everything else into [`LatexNode::Paragraph`] runs. Call
+    /// [`ast::render_markdown`] on the result for the same Markdown
+    /// `extract_content` produces for these constructs, or serialize the
+    /// tree directly for programmatic consumption.
+    fn build_tree(&mut self) -> Vec<LatexNode> {
+        let content = self.content.clone();
+        let mut chars = content.chars().peekable();
+        let mut nodes = Vec::new();
+        let mut paragraph = String::new();
+        let mut in_document = false;
+        let mut in_preamble = true;
+
+        while let Some(ch) = chars.next() {
+            if ch == '\\' {
+                if chars.peek().is_some() {
+                    let cmd = self.read_command_name(&mut chars);
+
+                    match cmd.as_str() {
+                        "begin" => {
+                            if let Some('{') = chars.peek() {
+                                chars.next();
+                                let mut env_name = String::new();
+                                while let Some(&c) = chars.peek() {
+                                    if c == '}' {
+                                        chars.next();
+                                        break;
+                                    }
+                                    env_name.push(chars.next().unwrap());
+                                }
+
+                                match env_name.as_str() {
+                                    "document" => {
+                                        in_document = true;
+                                        in_preamble = false;
+                                    }
+                                    "tabular" => {
+                                        Self::flush_paragraph(&mut paragraph, &mut nodes);
+                                        let table_content = self.extract_environment(&mut chars, "tabular");
+                                        if let Some((_, table)) = self.parse_tabular(&table_content) {
+                                            nodes.push(LatexNode::Table(table));
+                                        }
+                                    }
+                                    "itemize" | "enumerate" => {
+                                        Self::flush_paragraph(&mut paragraph, &mut nodes);
+                                        let list_content = self.extract_environment(&mut chars, &env_name);
+                                        let items = self.parse_list_items(&list_content);
+                                        nodes.push(LatexNode::List {
+                                            ordered: env_name == "enumerate",
+                                            items,
+                                        });
+                                    }
+                                    "quote" | "quotation" => {
+                                        Self::flush_paragraph(&mut paragraph, &mut nodes);
+                                        let quote_content = self.extract_environment(&mut chars, &env_name);
+                                        let processed = self.process_inline_content(&quote_content);
+                                        nodes.push(LatexNode::Quote(vec![LatexNode::Paragraph(vec![Inline::Text(
+                                            processed,
+                                        )])]));
+                                    }
+                                    "verbatim" | "Verbatim" | "obeylines" => {
+                                        Self::flush_paragraph(&mut paragraph, &mut nodes);
+                                        let code_content = self.extract_environment(&mut chars, &env_name);
+                                        nodes.push(LatexNode::CodeBlock(code_content));
+                                    }
+                                    _ => {
+                                        let env_content = self.extract_environment(&mut chars, &env_name);
+                                        paragraph.push_str(&self.process_inline_content(&env_content));
+                                    }
+                                }
+                            }
+                        }
+                        "end" => {
+                            if let Some('{') = chars.peek() {
+                                chars.next();
+                                while let Some(&c) = chars.peek() {
+                                    if c == '}' {
+                                        chars.next();
+                                        break;
+                                    }
+                                    chars.next();
+                                }
+                            }
+                        }
+                        "section" | "subsection" | "subsubsection" => {
+                            Self::flush_paragraph(&mut paragraph, &mut nodes);
+                            if let Some('{') = chars.peek() {
+                                chars.next();
+                                let (title, _) = self.read_braced_content(&mut chars);
+                                let processed = self.process_inline_content(&title);
+                                let level = match cmd.as_str() {
+                                    "section" => 1,
+                                    "subsection" => 2,
+                                    _ => 3,
+                                };
+                                nodes.push(LatexNode::Section {
+                                    level,
+                                    title: processed,
+                                    children: Vec::new(),
+                                });
+                            }
+                        }
+                        "title" | "author" | "date" | "maketitle" | "usepackage" | "documentclass"
+                        | "newcommand" | "renewcommand" | "bibliography" | "addbibresource" => {
+                            if let Some('{') = chars.peek() {
+                                chars.next();
+                                let (_content, _) = self.read_braced_content(&mut chars);
+                            }
+                        }
+                        _ => {
+                            if let Some('{') = chars.peek() {
+                                chars.next();
+                                let (inner, _) = self.read_braced_content(&mut chars);
+                                paragraph.push_str(&self.process_inline_content(&inner));
+                            }
+                        }
+                    }
+                }
+            } else if ch == '$' {
+                Self::flush_paragraph(&mut paragraph, &mut nodes);
+                let display = chars.peek() == Some(&'$');
+                if display {
+                    chars.next();
+                }
+                let mut body = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c == '$' {
+                        chars.next();
+                        if display && chars.peek() == Some(&'$') {
+                            chars.next();
+                        }
+                        break;
+                    }
+                    body.push(chars.next().unwrap());
+                }
+                nodes.push(LatexNode::Math { inline: !display, body });
+            } else if ch == '\n' && chars.peek() == Some(&'\n') {
+                Self::flush_paragraph(&mut paragraph, &mut nodes);
+            } else if in_document || !in_preamble {
+                paragraph.push(ch);
+            }
+        }
+
+        Self::flush_paragraph(&mut paragraph, &mut nodes);
+        nodes
+    }
+
+    fn flush_paragraph(paragraph: &mut String, nodes: &mut Vec<LatexNode>) {
+        if !paragraph.trim().is_empty() {
+            nodes.push(LatexNode::Paragraph(vec![Inline::Text(paragraph.trim().to_string())]));
+        }
+        paragraph.clear();
+    }
+
+    /// Split an itemize/enumerate body on `\item` into per-item node lists,
+    /// dropping the `description` environment's optional `[label]` (the
+    /// flattened Markdown renderer handles that separately).
+    fn parse_list_items(&self, content: &str) -> Vec<Vec<LatexNode>> {
+        content
+            .split("\\item")
+            .skip(1)
+            .map(|raw_item| {
+                let mut item_text = raw_item;
+                if let Some(stripped) = item_text.trim_start().strip_prefix('[')
+                    && let Some(end) = stripped.find(']')
+                {
+                    item_text = &stripped[end + 1..];
+                }
+                let processed = self.process_inline_content(item_text.trim());
+                vec![LatexNode::Paragraph(vec![Inline::Text(processed)])]
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -1021,7 +1899,7 @@ mod tests {
     #[test]
     fn test_basic_title_extraction() {
         let latex = r#"\title{Hello World}"#;
-        let (_, metadata, _) = LatexExtractor::extract_from_latex(latex);
+        let (_, metadata, _) = LatexExtractor::new().extract_from_latex(latex);
         assert_eq!(
             metadata.additional.get("title").and_then(|v| v.as_str()),
             Some("Hello World")
@@ -1031,14 +1909,226 @@ mod tests {
     #[test]
     fn test_author_extraction() {
         let latex = r#"\author{John Doe}"#;
-        let (_, metadata, _) = LatexExtractor::extract_from_latex(latex);
+        let (_, metadata, _) = LatexExtractor::new().extract_from_latex(latex);
         assert!(metadata.additional.get("author").is_some());
     }
 
     #[test]
     fn test_section_extraction() {
         let latex = r#"\section{Introduction}"#;
-        let (content, _, _) = LatexExtractor::extract_from_latex(latex);
+        let (content, _, _) = LatexExtractor::new().extract_from_latex(latex);
         assert!(content.contains("Introduction"));
     }
+
+    #[test]
+    fn test_cite_without_bibliography_falls_back_to_bare_key() {
+        let latex = r#"\cite{knuth1984}"#;
+        let (content, _, _) = LatexExtractor::new().extract_from_latex(latex);
+        assert!(content.contains("[knuth1984]"));
+    }
+
+    #[test]
+    fn test_cite_resolves_against_supplied_bibliography_and_appends_references() {
+        let bib = r#"@article{knuth1984, title = {Literate Programming}, author = {Knuth, Donald E.}, year = {1984}}"#;
+        let latex = r#"See \cite{knuth1984} for details."#;
+        let extractor = LatexExtractor::new().with_bibliography([bib]);
+        let (content, _, _) = extractor.extract_from_latex(latex);
+        assert!(content.contains("[1]"));
+        assert!(content.contains("## References"));
+        assert!(content.contains("Knuth"));
+    }
+
+    #[test]
+    fn test_author_year_citation_style() {
+        let bib = r#"@article{smith2020, title = {A Paper}, author = {Smith, Jane}, year = {2020}}"#;
+        let latex = r#"\cite{smith2020}"#;
+        let extractor = LatexExtractor::new()
+            .with_bibliography([bib])
+            .with_citation_style(bibtex::CitationStyle::AuthorYear);
+        let (content, _, _) = extractor.extract_from_latex(latex);
+        assert!(content.contains("(Smith, 2020)"));
+    }
+
+    #[test]
+    fn test_bibliography_command_is_not_rendered() {
+        let latex = r#"Body text.\bibliography{refs}"#;
+        let (content, _, _) = LatexExtractor::new().extract_from_latex(latex);
+        assert!(!content.contains("refs"));
+    }
+
+    #[test]
+    fn test_newcommand_zero_arg_macro_is_expanded() {
+        let latex = r#"\newcommand{\company}{Acme Corp}\company"#;
+        let (content, _, _) = LatexExtractor::new().extract_from_latex(latex);
+        assert!(content.contains("Acme Corp"));
+    }
+
+    #[test]
+    fn test_newcommand_with_args_and_default_optional() {
+        let latex = r#"\newcommand{\greet}[2][Hello]{#1, #2!}\greet{Bob}"#;
+        let (content, _, _) = LatexExtractor::new().extract_from_latex(latex);
+        assert!(content.contains("Hello, Bob!"));
+    }
+
+    #[test]
+    fn test_newcommand_optional_arg_override() {
+        let latex = r#"\newcommand{\greet}[2][Hello]{#1, #2!}\greet[Hi]{Bob}"#;
+        let (content, _, _) = LatexExtractor::new().extract_from_latex(latex);
+        assert!(content.contains("Hi, Bob!"));
+    }
+
+    #[test]
+    fn test_def_macro_is_expanded() {
+        let latex = r#"\def\company{Acme Corp}\company"#;
+        let (content, _, _) = LatexExtractor::new().extract_from_latex(latex);
+        assert!(content.contains("Acme Corp"));
+    }
+
+    #[test]
+    fn test_self_referential_macro_does_not_loop_forever() {
+        let latex = r#"\newcommand{\loopy}{\loopy}\loopy"#;
+        // The expansion-depth guard must make this terminate instead of recursing forever.
+        let (content, _, _) = LatexExtractor::new().extract_from_latex(latex);
+        assert!(content.matches("loopy").count() <= 1);
+    }
+
+    #[test]
+    fn test_tabular_column_spec_drives_markdown_alignment() {
+        let latex = "\\begin{tabular}{l c r}\na & b & c \\\\\nd & e & f \\\\\n\\end{tabular}";
+        let (content, _, tables) = LatexExtractor::new().extract_from_latex(latex);
+        assert!(content.contains("|---|:---:|---:|"));
+        assert_eq!(tables[0].cells[0], vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_tabular_multicolumn_expands_to_spanned_cells() {
+        let latex = "\\begin{tabular}{l l l}\n\\multicolumn{2}{c}{Title} & c \\\\\n\\end{tabular}";
+        let (_, _, tables) = LatexExtractor::new().extract_from_latex(latex);
+        assert_eq!(tables[0].cells[0], vec!["Title", "", "c"]);
+    }
+
+    #[test]
+    fn test_tabular_multirow_blanks_covered_cells_in_following_rows() {
+        let latex = "\\begin{tabular}{l l}\n\\multirow{2}{*}{spanned} & a \\\\\n& b \\\\\n\\end{tabular}";
+        let (_, _, tables) = LatexExtractor::new().extract_from_latex(latex);
+        assert_eq!(tables[0].cells[0], vec!["spanned", "a"]);
+        assert_eq!(tables[0].cells[1], vec!["", "b"]);
+    }
+
+    #[test]
+    fn test_nested_itemize_is_indented_under_its_parent_item() {
+        let latex = "\\begin{itemize}\\item Top\\begin{itemize}\\item Nested\\end{itemize}\\item Second\\end{itemize}";
+        let (content, _, _) = LatexExtractor::new().extract_from_latex(latex);
+        assert!(content.contains("- Top"));
+        assert!(content.contains("  - Nested"));
+        assert!(content.contains("- Second"));
+    }
+
+    #[test]
+    fn test_nested_enumerate_tracks_item_count_per_level() {
+        let latex =
+            "\\begin{enumerate}\\item One\\begin{enumerate}\\item Inner A\\item Inner B\\end{enumerate}\\item Two\\end{enumerate}";
+        let (content, _, _) = LatexExtractor::new().extract_from_latex(latex);
+        assert!(content.contains("1. One"));
+        assert!(content.contains("  1. Inner A"));
+        assert!(content.contains("  2. Inner B"));
+        assert!(content.contains("2. Two"));
+    }
+
+    struct AcronymHandler;
+
+    impl LatexHandler for AcronymHandler {
+        fn command(&mut self, name: &str, args: &[String]) -> Option<String> {
+            if name == "acro" {
+                Some(format!("**{}**", args.first()?))
+            } else {
+                None
+            }
+        }
+    }
+
+    #[test]
+    fn test_custom_handler_overrides_unknown_command() {
+        let latex = r#"\acro{HTTP}"#;
+        let extractor = LatexExtractor::new().with_handler(|| AcronymHandler);
+        let (content, _, _) = extractor.extract_from_latex(latex);
+        assert_eq!(content, "**HTTP**");
+    }
+
+    #[test]
+    fn test_custom_handler_none_falls_through_to_builtin() {
+        let latex = r#"\textbf{bold text}"#;
+        let extractor = LatexExtractor::new().with_handler(|| AcronymHandler);
+        let (content, _, _) = extractor.extract_from_latex(latex);
+        assert_eq!(content, "**bold text**");
+    }
+
+    struct TheoremHandler;
+
+    impl LatexHandler for TheoremHandler {
+        fn environment(&mut self, name: &str, body: &str) -> Option<String> {
+            if name == "theorem" {
+                Some(format!("> **Theorem.** {}", body.trim()))
+            } else {
+                None
+            }
+        }
+    }
+
+    #[test]
+    fn test_custom_handler_overrides_custom_environment() {
+        let latex = "\\begin{theorem}P implies P.\\end{theorem}";
+        let extractor = LatexExtractor::new().with_handler(|| TheoremHandler);
+        let (content, _, _) = extractor.extract_from_latex(latex);
+        assert_eq!(content, "> **Theorem.** P implies P.");
+    }
+
+    #[test]
+    fn test_parse_tree_produces_section_and_paragraph_nodes() {
+        let latex = r#"\section{Intro}Hello world."#;
+        let tree = LatexExtractor::new().parse_tree(latex);
+        assert!(tree.iter().any(|n| matches!(n, LatexNode::Section { title, .. } if title == "Intro")));
+        assert!(tree.iter().any(|n| matches!(n, LatexNode::Paragraph(_))));
+    }
+
+    #[test]
+    fn test_parse_tree_renders_to_same_markdown_shape() {
+        let latex = r#"\begin{itemize}\item one\item two\end{itemize}"#;
+        let tree = LatexExtractor::new().parse_tree(latex);
+        let markdown = ast::render_markdown(&tree);
+        assert!(markdown.contains("- one"));
+        assert!(markdown.contains("- two"));
+    }
+
+    #[test]
+    fn test_events_streams_section_and_paragraph_in_reading_order() {
+        let latex = r#"\section{Intro}Hello world."#;
+        let events: Vec<LatexEvent> = LatexExtractor::new().events(latex).collect();
+        assert_eq!(
+            events,
+            vec![
+                LatexEvent::StartSection { level: 1, title: "Intro".to_string() },
+                LatexEvent::Text("Hello world.".to_string()),
+                LatexEvent::Blankline,
+                LatexEvent::EndSection,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_events_and_parse_tree_agree_on_list_items() {
+        let latex = r#"\begin{itemize}\item one\item two\end{itemize}"#;
+        let extractor = LatexExtractor::new();
+        let events: Vec<LatexEvent> = extractor.events(latex).collect();
+        let item_count = events.iter().filter(|e| matches!(e, LatexEvent::StartItem)).count();
+        assert_eq!(item_count, 2);
+    }
+
+    #[test]
+    fn test_handler_cannot_intercept_builtin_environments() {
+        let latex = "\\begin{itemize}\\item one\\end{itemize}";
+        let extractor = LatexExtractor::new().with_handler(|| TheoremHandler);
+        let (content, _, _) = extractor.extract_from_latex(latex);
+        assert!(content.contains("- one"));
+    }
 }
@@ -0,0 +1,273 @@
+//! Structured document tree for the LaTeX extractor.
+//!
+//! Exposes a parsed document as a typed [`LatexNode`] tree, independent of
+//! any one rendering, so the result can be serialized (e.g. to JSON) or
+//! walked by a custom renderer instead of only ever producing Markdown.
+//! [`render_markdown`] is the same rendering `LatexExtractor` uses by
+//! default, factored out as a single function that folds over the tree.
+
+use crate::types::Table;
+use serde::{Deserialize, Serialize};
+
+/// One inline run within a [`LatexNode::Paragraph`].
+///
+/// Character-level inline parsing (bold/italic/links/citations/...) is
+/// still resolved by the existing line-oriented extractor and handed over
+/// here as a single pre-rendered [`Inline::Text`] run; splitting inline
+/// parsing itself into structured nodes is tracked as a follow-up.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Inline {
+    Text(String),
+}
+
+/// One block-level unit of a parsed LaTeX document.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum LatexNode {
+    Section {
+        level: u8,
+        title: String,
+        children: Vec<LatexNode>,
+    },
+    Paragraph(Vec<Inline>),
+    Math {
+        inline: bool,
+        body: String,
+    },
+    List {
+        ordered: bool,
+        items: Vec<Vec<LatexNode>>,
+    },
+    Table(Table),
+    CodeBlock(String),
+    Quote(Vec<LatexNode>),
+}
+
+/// Render a document tree back to Markdown by walking it once, rather than
+/// interleaving rendering decisions with parsing.
+pub fn render_markdown(nodes: &[LatexNode]) -> String {
+    let mut out = String::new();
+    render_nodes(nodes, &mut out);
+    while out.contains("\n\n\n") {
+        out = out.replace("\n\n\n", "\n\n");
+    }
+    out.trim().to_string()
+}
+
+fn render_nodes(nodes: &[LatexNode], out: &mut String) {
+    for node in nodes {
+        render_node(node, out);
+    }
+}
+
+fn render_node(node: &LatexNode, out: &mut String) {
+    match node {
+        LatexNode::Section { level, title, children } => {
+            let marker = "#".repeat((*level).clamp(1, 5) as usize);
+            out.push_str(&format!("\n{} {}\n\n", marker, title));
+            render_nodes(children, out);
+        }
+        LatexNode::Paragraph(inlines) => {
+            for inline in inlines {
+                let Inline::Text(text) = inline;
+                out.push_str(text);
+            }
+            out.push('\n');
+        }
+        LatexNode::Math { inline, body } => {
+            if *inline {
+                out.push_str(&format!("${}$", body));
+            } else {
+                out.push_str(&format!("$${}$$", body));
+            }
+            out.push('\n');
+        }
+        LatexNode::List { ordered, items } => {
+            for (index, item) in items.iter().enumerate() {
+                let prefix = if *ordered { format!("{}. ", index + 1) } else { "- ".to_string() };
+                out.push_str(&prefix);
+                let mut item_text = String::new();
+                render_nodes(item, &mut item_text);
+                out.push_str(item_text.trim());
+                out.push('\n');
+            }
+        }
+        LatexNode::Table(table) => {
+            out.push_str(&table.markdown);
+            out.push('\n');
+        }
+        LatexNode::CodeBlock(code) => {
+            out.push_str(code);
+            out.push('\n');
+        }
+        LatexNode::Quote(children) => {
+            render_nodes(children, out);
+            out.push('\n');
+        }
+    }
+}
+
+/// One event in a document's linear reading order, for streaming consumers
+/// that want to fold or forward output without holding the whole rendered
+/// document in memory at once, analogous to the djot/pulldown-cmark
+/// event-stream model.
+///
+/// [`flatten_events`] produces these in the same order [`render_markdown`]
+/// would render them; folding a stream of [`LatexEvent`]s back into
+/// Markdown should reproduce [`render_markdown`]'s output.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LatexEvent {
+    StartSection { level: u8, title: String },
+    EndSection,
+    Text(String),
+    Math { inline: bool, body: String },
+    StartList { ordered: bool },
+    StartItem,
+    EndItem,
+    EndList,
+    Table(Table),
+    CodeBlock(String),
+    StartQuote,
+    EndQuote,
+    /// A paragraph or list item boundary, rendered as a blank line.
+    Blankline,
+}
+
+/// Flatten a [`LatexNode`] tree into a linear sequence of [`LatexEvent`]s.
+pub fn flatten_events(nodes: &[LatexNode]) -> Vec<LatexEvent> {
+    let mut events = Vec::new();
+    flatten_nodes(nodes, &mut events);
+    events
+}
+
+fn flatten_nodes(nodes: &[LatexNode], events: &mut Vec<LatexEvent>) {
+    for node in nodes {
+        flatten_node(node, events);
+    }
+}
+
+fn flatten_node(node: &LatexNode, events: &mut Vec<LatexEvent>) {
+    match node {
+        LatexNode::Section { level, title, children } => {
+            events.push(LatexEvent::StartSection { level: *level, title: title.clone() });
+            flatten_nodes(children, events);
+            events.push(LatexEvent::EndSection);
+        }
+        LatexNode::Paragraph(inlines) => {
+            for inline in inlines {
+                let Inline::Text(text) = inline;
+                events.push(LatexEvent::Text(text.clone()));
+            }
+            events.push(LatexEvent::Blankline);
+        }
+        LatexNode::Math { inline, body } => {
+            events.push(LatexEvent::Math { inline: *inline, body: body.clone() });
+        }
+        LatexNode::List { ordered, items } => {
+            events.push(LatexEvent::StartList { ordered: *ordered });
+            for item in items {
+                events.push(LatexEvent::StartItem);
+                flatten_nodes(item, events);
+                events.push(LatexEvent::EndItem);
+            }
+            events.push(LatexEvent::EndList);
+        }
+        LatexNode::Table(table) => events.push(LatexEvent::Table(table.clone())),
+        LatexNode::CodeBlock(code) => events.push(LatexEvent::CodeBlock(code.clone())),
+        LatexNode::Quote(children) => {
+            events.push(LatexEvent::StartQuote);
+            flatten_nodes(children, events);
+            events.push(LatexEvent::EndQuote);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_section_with_paragraph_child() {
+        let tree = vec![LatexNode::Section {
+            level: 1,
+            title: "Intro".to_string(),
+            children: vec![LatexNode::Paragraph(vec![Inline::Text("Hello.".to_string())])],
+        }];
+        let markdown = render_markdown(&tree);
+        assert!(markdown.contains("# Intro"));
+        assert!(markdown.contains("Hello."));
+    }
+
+    #[test]
+    fn test_render_ordered_list() {
+        let tree = vec![LatexNode::List {
+            ordered: true,
+            items: vec![
+                vec![LatexNode::Paragraph(vec![Inline::Text("first".to_string())])],
+                vec![LatexNode::Paragraph(vec![Inline::Text("second".to_string())])],
+            ],
+        }];
+        let markdown = render_markdown(&tree);
+        assert!(markdown.contains("1. first"));
+        assert!(markdown.contains("2. second"));
+    }
+
+    #[test]
+    fn test_render_display_math() {
+        let tree = vec![LatexNode::Math { inline: false, body: "E=mc^2".to_string() }];
+        assert_eq!(render_markdown(&tree), "$$E=mc^2$$");
+    }
+
+    #[test]
+    fn test_node_tree_round_trips_through_json() {
+        let tree = vec![LatexNode::Paragraph(vec![Inline::Text("hi".to_string())])];
+        let json = serde_json::to_string(&tree).expect("serializable");
+        let roundtripped: Vec<LatexNode> = serde_json::from_str(&json).expect("deserializable");
+        assert_eq!(render_markdown(&tree), render_markdown(&roundtripped));
+    }
+
+    #[test]
+    fn test_flatten_events_wraps_section_children_in_start_end_pair() {
+        let tree = vec![LatexNode::Section {
+            level: 2,
+            title: "Intro".to_string(),
+            children: vec![LatexNode::Paragraph(vec![Inline::Text("Hello.".to_string())])],
+        }];
+        let events = flatten_events(&tree);
+        assert_eq!(
+            events,
+            vec![
+                LatexEvent::StartSection { level: 2, title: "Intro".to_string() },
+                LatexEvent::Text("Hello.".to_string()),
+                LatexEvent::Blankline,
+                LatexEvent::EndSection,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_flatten_events_wraps_each_list_item_in_start_end_pair() {
+        let tree = vec![LatexNode::List {
+            ordered: true,
+            items: vec![
+                vec![LatexNode::Paragraph(vec![Inline::Text("first".to_string())])],
+                vec![LatexNode::Paragraph(vec![Inline::Text("second".to_string())])],
+            ],
+        }];
+        let events = flatten_events(&tree);
+        assert_eq!(
+            events,
+            vec![
+                LatexEvent::StartList { ordered: true },
+                LatexEvent::StartItem,
+                LatexEvent::Text("first".to_string()),
+                LatexEvent::Blankline,
+                LatexEvent::EndItem,
+                LatexEvent::StartItem,
+                LatexEvent::Text("second".to_string()),
+                LatexEvent::Blankline,
+                LatexEvent::EndItem,
+                LatexEvent::EndList,
+            ]
+        );
+    }
+}
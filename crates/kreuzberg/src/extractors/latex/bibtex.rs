@@ -0,0 +1,436 @@
+//! Minimal BibTeX/biblatex bibliography parser.
+//!
+//! Parses `@type{key, field = value, ...}` records well enough to resolve
+//! `\cite{}` keys in the LaTeX extractor to formatted inline citations and a
+//! generated references section. This is not a full BibTeX implementation:
+//! it covers brace- and quote-delimited field values, `#` string
+//! concatenation, and `@string` macro definitions, which is the subset real
+//! `.bib` files overwhelmingly use.
+
+use std::collections::HashMap;
+
+/// A single parsed author/editor name, split into BibTeX's four components.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BibName {
+    pub first: String,
+    pub von: String,
+    pub last: String,
+    pub jr: String,
+}
+
+impl BibName {
+    /// Parse one name in either `"von Last, Jr, First"` or `"First von Last"` order.
+    pub fn parse(raw: &str) -> Self {
+        let raw = raw.trim();
+        match Self::parse_comma_form(raw) {
+            Some(name) => name,
+            None => Self::parse_space_form(raw),
+        }
+    }
+
+    fn parse_comma_form(raw: &str) -> Option<Self> {
+        if !raw.contains(',') {
+            return None;
+        }
+        let parts: Vec<&str> = raw.split(',').map(str::trim).collect();
+        let (von_last, jr, first) = match parts.as_slice() {
+            [von_last, first] => (*von_last, "", *first),
+            [von_last, jr, first, ..] => (*von_last, *jr, *first),
+            _ => return None,
+        };
+        let (von, last) = Self::split_von_last(von_last);
+        Some(Self {
+            first: first.to_string(),
+            von,
+            last,
+            jr: jr.to_string(),
+        })
+    }
+
+    fn parse_space_form(raw: &str) -> Self {
+        let words: Vec<&str> = raw.split_whitespace().collect();
+        if words.len() <= 1 {
+            return Self {
+                last: raw.to_string(),
+                ..Self::default()
+            };
+        }
+
+        // BibTeX's own heuristic: "von" is the longest run of lowercase-initial
+        // words between the first and last name.
+        let von_start = words[..words.len() - 1]
+            .iter()
+            .position(|w| w.chars().next().is_some_and(|c| c.is_lowercase()));
+
+        match von_start {
+            Some(idx) => {
+                let first = words[..idx].join(" ");
+                let von_last = words[idx..].join(" ");
+                let (von, last) = Self::split_von_last(&von_last);
+                Self { first, von, last, jr: String::new() }
+            }
+            None => Self {
+                first: words[..words.len() - 1].join(" "),
+                von: String::new(),
+                last: words[words.len() - 1].to_string(),
+                jr: String::new(),
+            },
+        }
+    }
+
+    fn split_von_last(von_last: &str) -> (String, String) {
+        let words: Vec<&str> = von_last.split_whitespace().collect();
+        let von_end = words[..words.len().saturating_sub(1)]
+            .iter()
+            .rposition(|w| w.chars().next().is_some_and(|c| c.is_lowercase()));
+
+        match von_end {
+            Some(idx) => (words[..=idx].join(" "), words[idx + 1..].join(" ")),
+            None => (String::new(), von_last.to_string()),
+        }
+    }
+
+    /// Render as `"Last, First"`, the common bibliography display form.
+    pub fn display_last_first(&self) -> String {
+        let last = if self.von.is_empty() {
+            self.last.clone()
+        } else {
+            format!("{} {}", self.von, self.last)
+        };
+        match (self.first.is_empty(), self.jr.is_empty()) {
+            (true, _) => last,
+            (false, true) => format!("{}, {}", last, self.first),
+            (false, false) => format!("{}, {}, {}", last, self.jr, self.first),
+        }
+    }
+}
+
+/// A single parsed bibliography entry, e.g. `@article{knuth1984, ...}`.
+#[derive(Debug, Clone, Default)]
+pub struct BibEntry {
+    pub entry_type: String,
+    pub key: String,
+    pub fields: HashMap<String, String>,
+}
+
+impl BibEntry {
+    fn field(&self, name: &str) -> Option<&str> {
+        self.fields.get(name).map(String::as_str)
+    }
+
+    /// Split `author` on `" and "` and parse each into a [`BibName`].
+    pub fn authors(&self) -> Vec<BibName> {
+        self.field("author")
+            .map(|s| s.split(" and ").map(BibName::parse).collect())
+            .unwrap_or_default()
+    }
+}
+
+/// How a resolved citation should be rendered inline and in the reference list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CitationStyle {
+    /// `[1]`, `[2]`, ... numbered in order of first citation.
+    #[default]
+    Numeric,
+    /// `(Smith, 2020)`.
+    AuthorYear,
+}
+
+impl CitationStyle {
+    /// Render the inline citation marker at its point of use.
+    pub fn format_inline(self, entry: &BibEntry, number: usize) -> String {
+        match self {
+            CitationStyle::Numeric => format!("[{}]", number),
+            CitationStyle::AuthorYear => {
+                let author = entry
+                    .authors()
+                    .first()
+                    .map(|n| if n.last.is_empty() { n.first.clone() } else { n.last.clone() })
+                    .unwrap_or_else(|| entry.key.clone());
+                let year = entry.field("year").unwrap_or("n.d.");
+                format!("({}, {})", author, year)
+            }
+        }
+    }
+
+    /// Render one entry of the generated "References" section.
+    pub fn format_reference(self, entry: &BibEntry, number: usize) -> String {
+        let authors = entry.authors();
+        let author_list = authors
+            .iter()
+            .map(BibName::display_last_first)
+            .collect::<Vec<_>>()
+            .join(", ");
+        let title = entry.field("title").unwrap_or_default();
+        let venue = entry.field("journal").or_else(|| entry.field("booktitle")).unwrap_or_default();
+        let year = entry.field("year").unwrap_or_default();
+
+        let body = [author_list.as_str(), title, venue, year]
+            .into_iter()
+            .filter(|s| !s.is_empty())
+            .collect::<Vec<_>>()
+            .join(". ");
+
+        match self {
+            CitationStyle::Numeric => format!("[{}] {}", number, body),
+            CitationStyle::AuthorYear => body,
+        }
+    }
+}
+
+/// Parse a `.bib` source into a map of citation key → entry.
+///
+/// Entries with no comma-separated key, and `@comment`/`@preamble` blocks,
+/// are silently skipped. `@string` macros are resolved before being stored
+/// (forward references to a macro defined later in the file are not
+/// supported, matching BibTeX's own single-pass behavior).
+pub fn parse_bibliography(content: &str) -> HashMap<String, BibEntry> {
+    let mut string_macros: HashMap<String, String> = HashMap::new();
+    let mut entries = HashMap::new();
+    let mut chars = content.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        if ch != '@' {
+            continue;
+        }
+
+        let entry_type = read_ident(&mut chars).to_lowercase();
+        skip_whitespace(&mut chars);
+
+        let closing = match chars.peek() {
+            Some('{') => {
+                chars.next();
+                '}'
+            }
+            Some('(') => {
+                chars.next();
+                ')'
+            }
+            _ => continue,
+        };
+
+        let body = read_balanced(&mut chars, closing);
+
+        match entry_type.as_str() {
+            "string" => {
+                if let Some((name, value)) = parse_string_macro(&body, &string_macros) {
+                    string_macros.insert(name, value);
+                }
+            }
+            "comment" | "preamble" => {}
+            _ => {
+                if let Some(entry) = parse_entry_body(&entry_type, &body, &string_macros) {
+                    entries.insert(entry.key.clone(), entry);
+                }
+            }
+        }
+    }
+
+    entries
+}
+
+fn read_ident(chars: &mut std::iter::Peekable<std::str::Chars>) -> String {
+    let mut ident = String::new();
+    while let Some(&c) = chars.peek() {
+        if c.is_alphanumeric() || c == '_' || c == '-' {
+            ident.push(chars.next().unwrap());
+        } else {
+            break;
+        }
+    }
+    ident
+}
+
+fn skip_whitespace(chars: &mut std::iter::Peekable<std::str::Chars>) {
+    while matches!(chars.peek(), Some(c) if c.is_whitespace()) {
+        chars.next();
+    }
+}
+
+/// Read a delimiter-balanced block's inner content, having already consumed
+/// the opening delimiter.
+fn read_balanced(chars: &mut std::iter::Peekable<std::str::Chars>, closing: char) -> String {
+    let opening = if closing == '}' { '{' } else { '(' };
+    let mut depth = 1;
+    let mut content = String::new();
+    for c in chars.by_ref() {
+        if c == opening {
+            depth += 1;
+            content.push(c);
+        } else if c == closing {
+            depth -= 1;
+            if depth == 0 {
+                break;
+            }
+            content.push(c);
+        } else {
+            content.push(c);
+        }
+    }
+    content
+}
+
+/// Split `s` on top-level occurrences of `delim`, ignoring anything inside
+/// brace nesting or a double-quoted string.
+fn split_top_level(s: &str, delim: char) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0i32;
+    let mut in_quotes = false;
+
+    for c in s.chars() {
+        match c {
+            '"' if depth == 0 => {
+                in_quotes = !in_quotes;
+                current.push(c);
+            }
+            '{' => {
+                depth += 1;
+                current.push(c);
+            }
+            '}' => {
+                depth -= 1;
+                current.push(c);
+            }
+            c if c == delim && depth == 0 && !in_quotes => {
+                parts.push(current.trim().to_string());
+                current.clear();
+            }
+            _ => current.push(c),
+        }
+    }
+    if !current.trim().is_empty() {
+        parts.push(current.trim().to_string());
+    }
+    parts
+}
+
+/// Resolve a raw field value: strip one level of `{}`/`""` delimiters from
+/// each `#`-concatenated piece, falling back to `@string` macro lookup for
+/// bareword pieces.
+fn parse_field_value(raw: &str, string_macros: &HashMap<String, String>) -> String {
+    split_top_level(raw, '#')
+        .into_iter()
+        .map(|piece| {
+            let piece = piece.trim();
+            if piece.len() >= 2 && piece.starts_with('{') && piece.ends_with('}') {
+                piece[1..piece.len() - 1].to_string()
+            } else if piece.len() >= 2 && piece.starts_with('"') && piece.ends_with('"') {
+                piece[1..piece.len() - 1].to_string()
+            } else if !piece.is_empty() && piece.chars().all(|c| c.is_ascii_digit()) {
+                piece.to_string()
+            } else {
+                string_macros.get(&piece.to_lowercase()).cloned().unwrap_or_else(|| piece.to_string())
+            }
+        })
+        .collect::<Vec<_>>()
+        .concat()
+}
+
+fn parse_string_macro(body: &str, string_macros: &HashMap<String, String>) -> Option<(String, String)> {
+    let eq_pos = body.find('=')?;
+    let name = body[..eq_pos].trim().to_lowercase();
+    let value = parse_field_value(body[eq_pos + 1..].trim(), string_macros);
+    Some((name, value))
+}
+
+fn parse_entry_body(entry_type: &str, body: &str, string_macros: &HashMap<String, String>) -> Option<BibEntry> {
+    let comma_pos = body.find(',')?;
+    let key = body[..comma_pos].trim().to_string();
+    if key.is_empty() {
+        return None;
+    }
+
+    let mut fields = HashMap::new();
+    for field_str in split_top_level(&body[comma_pos + 1..], ',') {
+        let Some(eq_pos) = field_str.find('=') else {
+            continue;
+        };
+        let name = field_str[..eq_pos].trim().to_lowercase();
+        let value = parse_field_value(field_str[eq_pos + 1..].trim(), string_macros);
+        fields.insert(name, value);
+    }
+
+    Some(BibEntry {
+        entry_type: entry_type.to_string(),
+        key,
+        fields,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_basic_article_entry() {
+        let bib = r#"@article{knuth1984, title = {Literate Programming}, author = {Knuth, Donald E.}, year = {1984}}"#;
+        let entries = parse_bibliography(bib);
+        let entry = entries.get("knuth1984").expect("entry present");
+        assert_eq!(entry.entry_type, "article");
+        assert_eq!(entry.field("title"), Some("Literate Programming"));
+        assert_eq!(entry.field("year"), Some("1984"));
+    }
+
+    #[test]
+    fn test_quoted_and_braced_values_both_supported() {
+        let bib = r#"@book{k, title = "Quoted Title", author = {Braced Author}}"#;
+        let entries = parse_bibliography(bib);
+        let entry = &entries["k"];
+        assert_eq!(entry.field("title"), Some("Quoted Title"));
+        assert_eq!(entry.field("author"), Some("Braced Author"));
+    }
+
+    #[test]
+    fn test_string_macro_concatenation() {
+        let bib = r#"
+            @string{pub = "Acme Press"}
+            @book{b, publisher = pub # " Inc."}
+        "#;
+        let entries = parse_bibliography(bib);
+        assert_eq!(entries["b"].field("publisher"), Some("Acme Press Inc."));
+    }
+
+    #[test]
+    fn test_author_name_von_last_jr_first_order() {
+        let name = BibName::parse("van Beethoven, Jr, Ludwig");
+        assert_eq!(name.von, "van");
+        assert_eq!(name.last, "Beethoven");
+        assert_eq!(name.jr, "Jr");
+        assert_eq!(name.first, "Ludwig");
+    }
+
+    #[test]
+    fn test_author_name_first_von_last_order() {
+        let name = BibName::parse("Ludwig van Beethoven");
+        assert_eq!(name.first, "Ludwig");
+        assert_eq!(name.von, "van");
+        assert_eq!(name.last, "Beethoven");
+    }
+
+    #[test]
+    fn test_numeric_citation_style_formats_inline_and_reference() {
+        let mut fields = HashMap::new();
+        fields.insert("title".to_string(), "A Paper".to_string());
+        fields.insert("year".to_string(), "2020".to_string());
+        fields.insert("author".to_string(), "Smith, Jane".to_string());
+        let entry = BibEntry {
+            entry_type: "article".to_string(),
+            key: "smith2020".to_string(),
+            fields,
+        };
+
+        assert_eq!(CitationStyle::Numeric.format_inline(&entry, 1), "[1]");
+        assert_eq!(
+            CitationStyle::AuthorYear.format_inline(&entry, 1),
+            "(Smith, 2020)"
+        );
+        assert!(CitationStyle::Numeric.format_reference(&entry, 1).starts_with("[1] Smith, Jane"));
+    }
+
+    #[test]
+    fn test_unresolved_key_is_absent_from_map() {
+        let entries = parse_bibliography("@article{known, title = {T}}");
+        assert!(!entries.contains_key("unknown"));
+    }
+}
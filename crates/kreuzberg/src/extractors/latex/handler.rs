@@ -0,0 +1,33 @@
+//! Pluggable extension point for custom LaTeX commands and environments.
+//!
+//! Borrowed from the callback-handler pattern used by org-mode parsers
+//! (a trait with one method per element, overridden selectively): rather
+//! than patching the extractor's built-in command/environment dispatch to
+//! support a new macro, implement [`LatexHandler`] and register it via
+//! [`super::LatexExtractor::with_handler`].
+
+/// Callback trait for extending the LaTeX extractor with project-specific
+/// macros and environments, without forking the crate.
+///
+/// Both methods default to returning `None`, which falls through to the
+/// extractor's built-in handling for that command/environment. Returning
+/// `Some(markdown)` overrides the emitted output entirely.
+///
+/// `\begin{document}`/`\end{...}` environment delimiters are dispatched
+/// internally and never reach [`Self::command`]; to customize environment
+/// rendering, implement [`Self::environment`] instead.
+pub trait LatexHandler {
+    /// Called for a command other than `\begin`/`\end`, with its optional
+    /// braced argument (if any) already extracted.
+    fn command(&mut self, name: &str, args: &[String]) -> Option<String> {
+        let _ = (name, args);
+        None
+    }
+
+    /// Called for a `\begin{name}...\end{name}` environment with its raw,
+    /// unprocessed body.
+    fn environment(&mut self, name: &str, body: &str) -> Option<String> {
+        let _ = (name, body);
+        None
+    }
+}
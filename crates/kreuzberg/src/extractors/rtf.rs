@@ -9,6 +9,9 @@ use crate::core::config::ExtractionConfig;
 use crate::plugins::{DocumentExtractor, Plugin};
 use crate::types::{ExtractionResult, Metadata};
 use async_trait::async_trait;
+use std::collections::HashMap;
+use std::iter::Peekable;
+use std::str::Chars;
 
 /// Native Rust RTF extractor.
 ///
@@ -55,24 +58,405 @@ impl Plugin for RtfExtractor {
     }
 }
 
+/// A single-byte Windows codepage used to decode `\'xx` hex escapes.
+///
+/// RTF readers pick the codepage from the document's `\ansicpg` control
+/// word, then override it per run of text with the `\fcharsetN` of the
+/// currently selected font (see [`parse_font_charsets`]). Codepages this
+/// extractor doesn't have a full table for (double-byte ones like
+/// Shift-JIS, or ones we've never seen in the wild) fall back to treating
+/// the byte as Latin-1, which is what the extractor always did before this
+/// existed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Codepage {
+    /// Windows-1252, Western European (the implicit default for `\ansi`).
+    Cp1252,
+    /// Windows-1250, Central European.
+    Cp1250,
+    /// Windows-1251, Cyrillic.
+    Cp1251,
+    /// Any other codepage number; decoded as Latin-1.
+    Other(u16),
+}
+
+impl Codepage {
+    /// Map an `\ansicpg`/resolved-fcharset codepage number to a [`Codepage`].
+    fn from_number(cpg: u16) -> Self {
+        match cpg {
+            1252 => Self::Cp1252,
+            1250 => Self::Cp1250,
+            1251 => Self::Cp1251,
+            other => Self::Other(other),
+        }
+    }
+
+    /// Resolve an RTF `\fcharsetN` value (as seen in `\fonttbl`) to the
+    /// Windows codepage number it corresponds to, per the RTF 1.9.1 spec's
+    /// `\fcharset` table. Returns `None` for `\fcharset0`/`\fcharset1`
+    /// ("ANSI"/"Default"), which mean "use the document's `\ansicpg`"
+    /// rather than naming a codepage of their own.
+    fn from_fcharset(fcharset: u16) -> Option<Self> {
+        let cpg = match fcharset {
+            128 => 932,  // Shift-JIS
+            129 => 949,  // Hangul
+            130 => 1361, // Johab
+            134 => 936,  // GB2312
+            136 => 950,  // Big5
+            161 => 1253, // Greek
+            162 => 1254, // Turkish
+            163 => 1258, // Vietnamese
+            177 => 1255, // Hebrew
+            178 => 1256, // Arabic
+            186 => 1257, // Baltic
+            204 => 1251, // Russian
+            222 => 874,  // Thai
+            238 => 1250, // Eastern European
+            255 => 437,  // OEM/US
+            _ => return None,
+        };
+        Some(Self::from_number(cpg))
+    }
+
+    /// Decode a single `\'xx` byte into its Unicode character.
+    fn decode_byte(self, byte: u8) -> char {
+        if byte < 0x80 {
+            return byte as char;
+        }
+
+        match self {
+            Self::Cp1252 => cp1252_high_byte(byte),
+            Self::Cp1250 => cp1250_high_byte(byte),
+            Self::Cp1251 => cp1251_high_byte(byte),
+            Self::Other(_) => byte as char,
+        }
+    }
+}
+
+/// Windows-1252 punctuation/letters living in the 0x80-0x9F block that
+/// Latin-1 reserves for C1 control codes; 0xA0-0xFF otherwise matches
+/// Latin-1 exactly, so only this block needs a table.
+fn cp1252_high_byte(byte: u8) -> char {
+    match byte {
+        0x80 => '\u{20AC}',
+        0x82 => '\u{201A}',
+        0x83 => '\u{0192}',
+        0x84 => '\u{201E}',
+        0x85 => '\u{2026}',
+        0x86 => '\u{2020}',
+        0x87 => '\u{2021}',
+        0x88 => '\u{02C6}',
+        0x89 => '\u{2030}',
+        0x8A => '\u{0160}',
+        0x8B => '\u{2039}',
+        0x8C => '\u{0152}',
+        0x8E => '\u{017D}',
+        0x91 => '\u{2018}',
+        0x92 => '\u{2019}',
+        0x93 => '\u{201C}',
+        0x94 => '\u{201D}',
+        0x95 => '\u{2022}',
+        0x96 => '\u{2013}',
+        0x97 => '\u{2014}',
+        0x98 => '\u{02DC}',
+        0x99 => '\u{2122}',
+        0x9A => '\u{0161}',
+        0x9B => '\u{203A}',
+        0x9C => '\u{0153}',
+        0x9E => '\u{017E}',
+        0x9F => '\u{0178}',
+        _ => byte as char,
+    }
+}
+
+/// Windows-1250 (Central European) high byte table.
+fn cp1250_high_byte(byte: u8) -> char {
+    match byte {
+        0x80 => '\u{20AC}',
+        0x82 => '\u{201A}',
+        0x84 => '\u{201E}',
+        0x85 => '\u{2026}',
+        0x86 => '\u{2020}',
+        0x87 => '\u{2021}',
+        0x89 => '\u{2030}',
+        0x8A => '\u{0160}',
+        0x8B => '\u{2039}',
+        0x8C => '\u{015A}',
+        0x8D => '\u{0164}',
+        0x8E => '\u{017D}',
+        0x8F => '\u{0179}',
+        0x91 => '\u{2018}',
+        0x92 => '\u{2019}',
+        0x93 => '\u{201C}',
+        0x94 => '\u{201D}',
+        0x95 => '\u{2022}',
+        0x96 => '\u{2013}',
+        0x97 => '\u{2014}',
+        0x99 => '\u{2122}',
+        0x9A => '\u{0161}',
+        0x9B => '\u{203A}',
+        0x9C => '\u{015B}',
+        0x9D => '\u{0165}',
+        0x9E => '\u{017E}',
+        0x9F => '\u{017A}',
+        0xA1 => '\u{02C7}',
+        0xA2 => '\u{02D8}',
+        0xA3 => '\u{0141}',
+        0xA5 => '\u{0104}',
+        0xAA => '\u{015E}',
+        0xAF => '\u{017B}',
+        0xB2 => '\u{02DB}',
+        0xB3 => '\u{0142}',
+        0xB9 => '\u{0105}',
+        0xBA => '\u{015F}',
+        0xBC => '\u{02DD}',
+        0xBD => '\u{013E}',
+        0xBE => '\u{017E}',
+        0xBF => '\u{017C}',
+        0xC0 => '\u{0154}',
+        0xC3 => '\u{0102}',
+        0xC5 => '\u{0139}',
+        0xC6 => '\u{0106}',
+        0xC8 => '\u{010C}',
+        0xCA => '\u{0118}',
+        0xCC => '\u{011A}',
+        0xCF => '\u{010E}',
+        0xD0 => '\u{0110}',
+        0xD1 => '\u{0143}',
+        0xD2 => '\u{0147}',
+        0xD5 => '\u{0150}',
+        0xD8 => '\u{0158}',
+        0xD9 => '\u{016E}',
+        0xDB => '\u{0170}',
+        0xDE => '\u{0162}',
+        0xE0 => '\u{0155}',
+        0xE3 => '\u{0103}',
+        0xE5 => '\u{013A}',
+        0xE6 => '\u{0107}',
+        0xE8 => '\u{010D}',
+        0xEA => '\u{0119}',
+        0xEC => '\u{011B}',
+        0xEF => '\u{010F}',
+        0xF0 => '\u{0111}',
+        0xF1 => '\u{0144}',
+        0xF2 => '\u{0148}',
+        0xF5 => '\u{0151}',
+        0xF8 => '\u{0159}',
+        0xF9 => '\u{016F}',
+        0xFB => '\u{0171}',
+        0xFE => '\u{0163}',
+        0xFF => '\u{02D9}',
+        _ => byte as char,
+    }
+}
+
+/// Windows-1251 (Cyrillic) high byte table. `0xC0..=0xDF`/`0xE0..=0xFF` are
+/// the regular Cyrillic alphabet, laid out contiguously, so those are
+/// computed rather than listed.
+fn cp1251_high_byte(byte: u8) -> char {
+    match byte {
+        0x80 => '\u{0402}',
+        0x81 => '\u{0403}',
+        0x82 => '\u{201A}',
+        0x83 => '\u{0453}',
+        0x84 => '\u{201E}',
+        0x85 => '\u{2026}',
+        0x86 => '\u{2020}',
+        0x87 => '\u{2021}',
+        0x88 => '\u{20AC}',
+        0x89 => '\u{2030}',
+        0x8A => '\u{0409}',
+        0x8B => '\u{2039}',
+        0x8C => '\u{040A}',
+        0x8D => '\u{040C}',
+        0x8E => '\u{040B}',
+        0x8F => '\u{040F}',
+        0x90 => '\u{0452}',
+        0x91 => '\u{2018}',
+        0x92 => '\u{2019}',
+        0x93 => '\u{201C}',
+        0x94 => '\u{201D}',
+        0x95 => '\u{2022}',
+        0x96 => '\u{2013}',
+        0x97 => '\u{2014}',
+        0x99 => '\u{2122}',
+        0x9A => '\u{045A}',
+        0x9B => '\u{203A}',
+        0x9C => '\u{045C}',
+        0x9D => '\u{045D}',
+        0x9E => '\u{045B}',
+        0x9F => '\u{045F}',
+        0xA1 => '\u{040E}',
+        0xA2 => '\u{045E}',
+        0xA3 => '\u{0408}',
+        0xA5 => '\u{0490}',
+        0xA8 => '\u{0401}',
+        0xAA => '\u{0404}',
+        0xAF => '\u{0407}',
+        0xB2 => '\u{0406}',
+        0xB3 => '\u{0456}',
+        0xB4 => '\u{0491}',
+        0xB8 => '\u{0451}',
+        0xB9 => '\u{2116}',
+        0xBA => '\u{0454}',
+        0xBC => '\u{0458}',
+        0xBD => '\u{0405}',
+        0xBE => '\u{0455}',
+        0xBF => '\u{0457}',
+        0xC0..=0xDF => char::from_u32(0x0410 + (byte - 0xC0) as u32).unwrap_or(byte as char),
+        0xE0..=0xFF => char::from_u32(0x0430 + (byte - 0xE0) as u32).unwrap_or(byte as char),
+        _ => byte as char,
+    }
+}
+
+/// Parse the document's `\ansicpg` control word, e.g. `\ansicpg1252`,
+/// defaulting to Windows-1252 (the standard `\ansi` codepage) when absent.
+fn parse_ansicpg(content: &str) -> Codepage {
+    content
+        .find("\\ansicpg")
+        .and_then(|pos| {
+            let digits: String = content[pos + "\\ansicpg".len()..]
+                .chars()
+                .take_while(char::is_ascii_digit)
+                .collect();
+            digits.parse::<u16>().ok()
+        })
+        .map(Codepage::from_number)
+        .unwrap_or(Codepage::Cp1252)
+}
+
+/// Parse the `{\fonttbl ...}` group into a map of font id (the `N` in
+/// `\fN`) to the [`Codepage`] implied by that font's `\fcharsetN`, so the
+/// main extraction pass can switch codepage whenever the body text selects
+/// a different font.
+///
+/// Fonts with no `\fcharset`, or `\fcharset0`/`\fcharset1` ("ANSI"/
+/// "Default"), are omitted; callers should keep using the document
+/// codepage for them.
+fn parse_font_charsets(content: &str) -> HashMap<u32, Codepage> {
+    let mut charsets = HashMap::new();
+
+    let Some(table_start) = content.find("\\fonttbl") else {
+        return charsets;
+    };
+
+    // Walk back to the `{` that opens the fonttbl group, then forward to
+    // its matching `}`, tracking brace depth so nested groups inside a
+    // font entry (e.g. `{\*\falt ...}`) don't end the scan early.
+    let Some(group_start) = content[..table_start].rfind('{') else {
+        return charsets;
+    };
+    let mut depth = 0i32;
+    let mut group_end = content.len();
+    for (offset, ch) in content[group_start..].char_indices() {
+        match ch {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    group_end = group_start + offset + 1;
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+    let table = &content[group_start..group_end];
+
+    for entry in table.split(';') {
+        let Some(font_id) = parse_control_word_number(entry, "\\f") else {
+            continue;
+        };
+        if let Some(fcharset) = parse_control_word_number(entry, "\\fcharset")
+            && let Some(codepage) = Codepage::from_fcharset(fcharset as u16)
+        {
+            charsets.insert(font_id, codepage);
+        }
+    }
+
+    charsets
+}
+
+/// Find `prefix` followed immediately by digits (e.g. `"\f"` in `\f0\fnil`,
+/// but not `\fcharset0`) and parse them, returning `None` if `prefix`
+/// doesn't occur or isn't followed by at least one digit.
+fn parse_control_word_number(text: &str, prefix: &str) -> Option<u32> {
+    let mut search_from = 0;
+    while let Some(rel_pos) = text[search_from..].find(prefix) {
+        let pos = search_from + rel_pos;
+        let after = pos + prefix.len();
+        // Reject e.g. matching "\f" inside "\fcharset": the prefix must be
+        // followed by a digit, not another letter.
+        if text[after..].chars().next().is_some_and(|c| c.is_ascii_digit()) {
+            let digits: String = text[after..].chars().take_while(char::is_ascii_digit).collect();
+            return digits.parse().ok();
+        }
+        search_from = after;
+    }
+    None
+}
+
+/// Skip one RTF "unit" without emitting anything: either a single plain
+/// character, an escaped brace/backslash, a `\'xx` hex pair, or a whole
+/// control word (with its optional numeric parameter). Used to honor
+/// `\ucN`'s fallback-character skip count after a `\uN` Unicode escape.
+fn skip_one_unit(chars: &mut Peekable<Chars>) {
+    let Some(ch) = chars.next() else {
+        return;
+    };
+
+    if ch != '\\' {
+        return;
+    }
+
+    match chars.peek() {
+        Some('\\') | Some('{') | Some('}') => {
+            chars.next();
+        }
+        Some('\'') => {
+            chars.next();
+            chars.next();
+            chars.next();
+        }
+        _ => {
+            while let Some(&c) = chars.peek() {
+                if !c.is_alphanumeric() {
+                    break;
+                }
+                chars.next();
+            }
+            if let Some(&c) = chars.peek()
+                && (c.is_ascii_digit() || c == '-')
+            {
+                chars.next();
+            }
+        }
+    }
+}
+
 /// Extract text from RTF document using simple parsing approach.
 ///
 /// This function extracts plain text from an RTF document by:
 /// 1. Tokenizing control sequences and text
-/// 2. Converting encoded characters to Unicode
+/// 2. Converting encoded characters to Unicode, honoring `\ansicpg` and
+///    per-font `\fcharsetN` codepages for `\'xx` escapes
 /// 3. Extracting text while skipping formatting groups
 /// 4. Normalizing whitespace
 fn extract_text_from_rtf(content: &str) -> String {
+    let document_codepage = parse_ansicpg(content);
+    let font_charsets = parse_font_charsets(content);
+    let mut current_codepage = document_codepage;
+
     let mut result = String::new();
     let mut chars = content.chars().peekable();
-    let mut skip_next_char = false;
+    let mut uc_skip: usize = 1;
+    // `\ucN` and the active codepage (via `\fN`) are group-scoped per the RTF
+    // spec: a nested group (footnotes, comments, embedded objects) may set
+    // its own value, which must not leak into the sibling content that
+    // follows the group's closing brace. Each `{` pushes the state in force
+    // when the group opened; each `}` restores it.
+    let mut group_stack: Vec<(Codepage, usize)> = Vec::new();
 
     while let Some(ch) = chars.next() {
-        if skip_next_char {
-            skip_next_char = false;
-            continue;
-        }
-
         match ch {
             '\\' => {
                 // Handle RTF control sequences
@@ -91,8 +475,7 @@ fn extract_text_from_rtf(content: &str) -> String {
                             if let (Some(h1), Some(h2)) = (hex1, hex2)
                                 && let Ok(code) = u8::from_str_radix(&format!("{}{}", h1, h2), 16)
                             {
-                                // For Western European, assume Latin-1
-                                result.push(code as char);
+                                result.push(current_codepage.decode_byte(code));
                             }
                         }
                         'u' => {
@@ -117,27 +500,65 @@ fn extract_text_from_rtf(content: &str) -> String {
                                     result.push(c);
                                 }
                             }
+                            for _ in 0..uc_skip {
+                                skip_one_unit(&mut chars);
+                            }
                         }
                         _ => {
                             // Regular control word - skip until next whitespace or control char
+                            let mut word = String::new();
                             while let Some(&c) = chars.peek() {
                                 if !c.is_alphanumeric() {
                                     break;
                                 }
+                                word.push(c);
                                 chars.next();
                             }
-                            // Skip one trailing digit if present (for parameterized control words)
+                            // Parse the trailing numeric parameter, if any (for parameterized
+                            // control words like `\f2` or `\ucN`).
+                            let mut param = String::new();
                             if let Some(&c) = chars.peek()
                                 && (c.is_ascii_digit() || c == '-')
                             {
+                                param.push(c);
                                 chars.next();
+                                while let Some(&c) = chars.peek() {
+                                    if !c.is_ascii_digit() {
+                                        break;
+                                    }
+                                    param.push(c);
+                                    chars.next();
+                                }
+                            }
+
+                            match word.as_str() {
+                                "uc" => {
+                                    if let Ok(n) = param.parse::<usize>() {
+                                        uc_skip = n;
+                                    }
+                                }
+                                "f" => {
+                                    if let Ok(id) = param.parse::<u32>() {
+                                        current_codepage = font_charsets.get(&id).copied().unwrap_or(document_codepage);
+                                    }
+                                }
+                                _ => {}
                             }
                         }
                     }
                 }
             }
-            '{' | '}' => {
-                // Group delimiters - just add space
+            '{' => {
+                group_stack.push((current_codepage, uc_skip));
+                if !result.is_empty() && !result.ends_with(' ') {
+                    result.push(' ');
+                }
+            }
+            '}' => {
+                if let Some((saved_codepage, saved_uc_skip)) = group_stack.pop() {
+                    current_codepage = saved_codepage;
+                    uc_skip = saved_uc_skip;
+                }
                 if !result.is_empty() && !result.ends_with(' ') {
                     result.push(' ');
                 }
@@ -161,6 +582,91 @@ fn extract_text_from_rtf(content: &str) -> String {
     cleaned.trim().to_string()
 }
 
+/// Find the `{\info ...}` group and extract its `\title`, `\author`,
+/// `\subject`, `\keywords`, and `\creatim` sub-groups into `metadata`.
+///
+/// Text fields are decoded with [`extract_text_from_rtf`] so they get the
+/// same codepage/hex-escape handling as the document body. `\creatim` is
+/// numeric (`\yr\mo\dy\hr\min`) rather than text, so it's assembled into an
+/// ISO-8601-ish timestamp and stored as [`Metadata::date`] instead of
+/// `additional`, matching how other extractors surface a document date.
+fn extract_info_metadata(content: &str, metadata: &mut Metadata) {
+    let Some(info_start) = content.find("\\info") else {
+        return;
+    };
+    let Some(group_start) = content[..info_start].rfind('{') else {
+        return;
+    };
+
+    let mut depth = 0i32;
+    let mut group_end = content.len();
+    for (offset, ch) in content[group_start..].char_indices() {
+        match ch {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    group_end = group_start + offset + 1;
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+    let info = &content[group_start..group_end];
+
+    for (control_word, key) in [
+        ("\\title", "title"),
+        ("\\author", "author"),
+        ("\\subject", "subject"),
+        ("\\keywords", "keywords"),
+        ("\\operator", "operator"),
+        ("\\doccomm", "comment"),
+    ] {
+        if let Some(text) = extract_info_field(info, control_word) {
+            metadata.additional.insert(key.to_string(), text.into());
+        }
+    }
+
+    if let Some(timestamp) = parse_info_timestamp(info, "\\creatim") {
+        metadata.date = Some(timestamp);
+    }
+
+    if let Some(timestamp) = parse_info_timestamp(info, "\\revtim") {
+        metadata.additional.insert("modified".to_string(), timestamp.into());
+    }
+}
+
+/// Parse a `{\creatim\yr2024\mo1\dy2\hr3\min4}`-style timestamp group for
+/// `control_word` (`\creatim` or `\revtim`) into an ISO-ish
+/// `YYYY-MM-DDTHH:MM:00` string. Missing sub-controls default the way RTF
+/// dates conventionally do: `\yr` to 1970, `\mo`/`\dy` to 1, `\hr`/`\min` to 0.
+fn parse_info_timestamp(info: &str, control_word: &str) -> Option<String> {
+    let pos = info.find(control_word)?;
+    let group_start = info[..pos].rfind('{')?;
+    let group_end = info[group_start..].find('}').map(|i| group_start + i)?;
+    let field = &info[group_start..group_end];
+
+    let year = parse_control_word_number(field, "\\yr").unwrap_or(1970);
+    let month = parse_control_word_number(field, "\\mo").unwrap_or(1);
+    let day = parse_control_word_number(field, "\\dy").unwrap_or(1);
+    let hour = parse_control_word_number(field, "\\hr").unwrap_or(0);
+    let minute = parse_control_word_number(field, "\\min").unwrap_or(0);
+
+    Some(format!("{:04}-{:02}-{:02}T{:02}:{:02}:00", year, month, day, hour, minute))
+}
+
+/// Extract and decode the text of a single `{\title ...}`-style field
+/// inside the `\info` group's text.
+fn extract_info_field(info: &str, control_word: &str) -> Option<String> {
+    let pos = info.find(control_word)?;
+    let group_start = info[..pos].rfind('{')?;
+    let group_end = info[group_start..].find('}').map(|i| group_start + i)?;
+    let field = &info[group_start..group_end];
+    let text = extract_text_from_rtf(field);
+    if text.is_empty() { None } else { Some(text) }
+}
+
 #[async_trait]
 impl DocumentExtractor for RtfExtractor {
     #[cfg_attr(feature = "otel", tracing::instrument(
@@ -174,18 +680,41 @@ impl DocumentExtractor for RtfExtractor {
         &self,
         content: &[u8],
         mime_type: &str,
-        _config: &ExtractionConfig,
+        config: &ExtractionConfig,
     ) -> Result<ExtractionResult> {
+        // This backend never falls back to Pandoc on its own (that's the
+        // whole point of having a native parser); `prefer_for_rtf` is an
+        // explicit opt-in for callers who want the Pandoc path anyway.
+        if let Some(options) = config.pandoc.as_ref()
+            && options.prefer_for_rtf
+        {
+            let ast = crate::extraction::pandoc::convert_to_json_with_options(content, "rtf", options).await?;
+            let (extracted_text, tables) = crate::extraction::pandoc::walk_document(&ast);
+            let extracted_text = options.line_ending.normalize(&extracted_text);
+            return Ok(ExtractionResult {
+                content: extracted_text,
+                mime_type: mime_type.to_string(),
+                metadata: Metadata::default(),
+                tables: tables.into_iter().map(|t| serde_json::json!(t.to_markdown())).collect(),
+                detected_languages: None,
+                chunks: None,
+                images: None,
+            });
+        }
+
         // Convert bytes to string for RTF processing
         let rtf_content = String::from_utf8_lossy(content).to_string();
 
         // Extract text from RTF
         let extracted_text = extract_text_from_rtf(&rtf_content);
 
+        let mut metadata = Metadata::default();
+        extract_info_metadata(&rtf_content, &mut metadata);
+
         Ok(ExtractionResult {
             content: extracted_text,
             mime_type: mime_type.to_string(),
-            metadata: Metadata { ..Default::default() },
+            metadata,
             tables: vec![],
             detected_languages: None,
             chunks: None,
@@ -223,4 +752,96 @@ mod tests {
         let extracted = extract_text_from_rtf(rtf_content);
         assert!(extracted.contains("Hello") || extracted.contains("World"));
     }
+
+    #[test]
+    fn test_default_codepage_decodes_cp1252_hex_escapes() {
+        // \'e9 is "é" in both Latin-1 and cp1252; \'80 is "€", which only
+        // cp1252 (not Latin-1) gets right.
+        let rtf_content = r#"{\rtf1 caf\'e9 \'80}"#;
+        let extracted = extract_text_from_rtf(rtf_content);
+        assert!(extracted.contains('é'));
+        assert!(extracted.contains('€'));
+    }
+
+    #[test]
+    fn test_ansicpg_selects_the_declared_codepage() {
+        // \'c0 is "Ŕ" in cp1250 but "À" in cp1252.
+        let rtf_content = r#"{\rtf1\ansi\ansicpg1250 \'c0}"#;
+        let extracted = extract_text_from_rtf(rtf_content);
+        assert!(extracted.contains('Ŕ'));
+    }
+
+    #[test]
+    fn test_font_table_charset_overrides_codepage_for_that_font() {
+        // Document codepage is 1252 ("À" for \'c0), but font 1 is Cyrillic
+        // (fcharset204 => cp1251, where \'c0 is "А"), and the body switches
+        // to font 1 with \f1 before the escape.
+        let rtf_content =
+            r#"{\rtf1\ansicpg1252{\fonttbl{\f0\fnil Arial;}{\f1\fnil Cyrillic;}}\f1 \'c0}"#;
+        let extracted = extract_text_from_rtf(rtf_content);
+        assert!(extracted.contains('А'));
+    }
+
+    #[test]
+    fn test_uc_skip_count_drops_the_right_number_of_fallback_characters() {
+        // \uc2 means each \u escape is followed by 2 fallback characters
+        // for non-Unicode readers; both "XY" here are that fallback and
+        // should be dropped, leaving just the Unicode char.
+        let rtf_content = r#"{\rtf1\uc2 \u232XY}"#;
+        let extracted = extract_text_from_rtf(rtf_content);
+        assert_eq!(extracted, "è");
+    }
+
+    #[test]
+    fn test_parse_font_charsets_maps_font_id_to_codepage() {
+        let content = r#"{\fonttbl{\f0\fnil\fcharset0 Arial;}{\f1\fnil\fcharset204 Cyrillic;}}"#;
+        let charsets = parse_font_charsets(content);
+        assert_eq!(charsets.get(&1), Some(&Codepage::Cp1251));
+        assert_eq!(charsets.get(&0), None);
+    }
+
+    #[tokio::test]
+    async fn test_extract_bytes_populates_info_metadata() {
+        let extractor = RtfExtractor;
+        let rtf_content = br#"{\rtf1\ansi{\info{\title My Title}{\author Jane Doe}{\creatim\yr2023\mo5\dy10\hr14\min30}}Body text}"#;
+
+        let result = extractor
+            .extract_bytes(rtf_content, "application/rtf", &ExtractionConfig::default())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            result.metadata.additional.get("title").and_then(|v| v.as_str()),
+            Some("My Title")
+        );
+        assert_eq!(
+            result.metadata.additional.get("author").and_then(|v| v.as_str()),
+            Some("Jane Doe")
+        );
+        assert_eq!(result.metadata.date.as_deref(), Some("2023-05-10T14:30:00"));
+    }
+
+    #[tokio::test]
+    async fn test_extract_bytes_populates_operator_comment_and_modified_date() {
+        let extractor = RtfExtractor;
+        let rtf_content = br#"{\rtf1\ansi{\info{\operator John Smith}{\doccomm Draft review notes}{\revtim\yr2024\mo3\dy15\hr9\min5}}Body text}"#;
+
+        let result = extractor
+            .extract_bytes(rtf_content, "application/rtf", &ExtractionConfig::default())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            result.metadata.additional.get("operator").and_then(|v| v.as_str()),
+            Some("John Smith")
+        );
+        assert_eq!(
+            result.metadata.additional.get("comment").and_then(|v| v.as_str()),
+            Some("Draft review notes")
+        );
+        assert_eq!(
+            result.metadata.additional.get("modified").and_then(|v| v.as_str()),
+            Some("2024-03-15T09:05:00")
+        );
+    }
 }
@@ -1,6 +1,7 @@
 use super::error::PdfError;
 use once_cell::sync::Lazy;
 use pdfium_render::prelude::*;
+use std::cell::RefCell;
 use std::path::PathBuf;
 use std::sync::Mutex;
 
@@ -79,11 +80,18 @@ fn bind_to_pdfium(lib_dir: &Option<PathBuf>) -> Result<Box<dyn PdfiumLibraryBind
 ///
 /// # Design Rationale
 ///
-/// Each call creates a fresh Pdfium instance rather than reusing a cached one.
-/// This avoids potential double-free errors when multiple PDFs are processed concurrently,
-/// as the underlying C library may not safely handle overlapping document lifecycles
-/// from the same Pdfium instance. Fresh instances ensure proper resource cleanup
-/// without conflicts.
+/// Every call creates a brand-new Pdfium instance; this function does not
+/// cache one across calls. That's deliberate: the underlying C library may
+/// not safely handle overlapping document lifecycles from the same Pdfium
+/// instance, and a fresh instance per call sidesteps that without needing to
+/// reason about cross-call state.
+///
+/// Most callers shouldn't call this directly. [`with_thread_pdfium`] caches
+/// one instance per thread on top of this function, so repeated extractions
+/// on the same thread reuse bindings instead of paying the binding cost
+/// every time, while still guaranteeing an instance is only ever touched
+/// from the thread that created it (the same safety property the
+/// fresh-instance-per-call design protects, at lower cost).
 ///
 /// # Lock Poisoning Recovery
 ///
@@ -132,6 +140,60 @@ pub(crate) fn bind_pdfium(map_err: fn(String) -> PdfError, context: &'static str
     Ok(pdfium)
 }
 
+thread_local! {
+    /// One lazily-initialized Pdfium instance per OS thread. A `thread_local`
+    /// guarantees the instance is only ever accessed from the thread that
+    /// created it, so it's confined the same way a fresh-instance-per-call
+    /// would be, but reused across calls on that thread instead of rebuilt
+    /// every time. `PDFIUM_INIT_STATE` still owns the one-time `lib_dir`
+    /// extraction; this sits between that cache and callers.
+    static THREAD_PDFIUM: RefCell<Option<Pdfium>> = const { RefCell::new(None) };
+}
+
+/// Run `f` against this thread's cached Pdfium instance, initializing it via
+/// [`bind_pdfium`] on that thread's first call and reusing it on every
+/// subsequent call from the same thread.
+///
+/// # Arguments
+///
+/// * `map_err` - Function to map error strings to `PdfError` variants, used only if initialization fails
+/// * `context` - Context string for error reporting
+/// * `f` - Callback given a reference to this thread's `Pdfium` instance
+pub(crate) fn with_thread_pdfium<R>(
+    map_err: fn(String) -> PdfError,
+    context: &'static str,
+    f: impl FnOnce(&Pdfium) -> R,
+) -> Result<R, PdfError> {
+    THREAD_PDFIUM.with(|cell| {
+        let mut slot = cell.borrow_mut();
+        if slot.is_none() {
+            *slot = Some(bind_pdfium(map_err, context)?);
+        }
+        let pdfium = slot.as_ref().expect("just initialized above if empty");
+        Ok(f(pdfium))
+    })
+}
+
+/// Classify a Pdfium document-load failure message into the dedicated
+/// password variants of [`PdfError`], or `None` when the failure is
+/// unrelated to encryption and the caller should wrap `message` in its own
+/// context-specific variant instead.
+///
+/// Pdfium doesn't expose a structured "wrong password" error code through
+/// pdfium-render, so this matches on the error message; revisit this if a
+/// future pdfium-render release adds a dedicated variant.
+pub(crate) fn classify_password_error(message: &str, password_supplied: bool) -> Option<PdfError> {
+    if message.to_lowercase().contains("password") {
+        Some(if password_supplied {
+            PdfError::IncorrectPassword(message.to_string())
+        } else {
+            PdfError::PasswordRequired
+        })
+    } else {
+        None
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -164,4 +226,66 @@ mod tests {
             _ => panic!("Error mapping failed"),
         }
     }
+
+    #[test]
+    fn test_classify_password_error_without_password_supplied() {
+        let result = classify_password_error("document requires a password", false);
+        assert!(matches!(result, Some(PdfError::PasswordRequired)));
+    }
+
+    #[test]
+    fn test_classify_password_error_with_incorrect_password_supplied() {
+        let result = classify_password_error("incorrect password", true);
+        assert!(matches!(result, Some(PdfError::IncorrectPassword(_))));
+    }
+
+    #[test]
+    fn test_classify_password_error_ignores_unrelated_failures() {
+        let result = classify_password_error("malformed xref table", false);
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn test_with_thread_pdfium_initializes_and_reuses_instance() {
+        let first_ptr = with_thread_pdfium(PdfError::TextExtractionFailed, "test reuse", |pdfium| {
+            pdfium as *const Pdfium
+        })
+        .expect("first call should succeed");
+
+        let second_ptr = with_thread_pdfium(PdfError::TextExtractionFailed, "test reuse", |pdfium| {
+            pdfium as *const Pdfium
+        })
+        .expect("second call should succeed");
+
+        assert_eq!(first_ptr, second_ptr, "same thread should reuse the same Pdfium instance");
+    }
+
+    #[test]
+    fn test_with_thread_pdfium_stress_many_threads() {
+        let thread_count = 16;
+        let calls_per_thread = 20;
+
+        let handles: Vec<_> = (0..thread_count)
+            .map(|_| {
+                std::thread::spawn(move || {
+                    let mut last_ptr: Option<*const Pdfium> = None;
+                    for _ in 0..calls_per_thread {
+                        let ptr = with_thread_pdfium(PdfError::TextExtractionFailed, "stress test", |pdfium| {
+                            pdfium as *const Pdfium
+                        })
+                        .expect("call should succeed");
+
+                        if let Some(previous) = last_ptr {
+                            assert_eq!(previous, ptr, "thread should keep reusing its own Pdfium instance");
+                        }
+                        last_ptr = Some(ptr);
+                    }
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().expect("worker thread should not panic");
+        }
+    }
 }
@@ -0,0 +1,55 @@
+//! Error type for the PDF subsystem.
+//!
+//! Kept separate from [`crate::KreuzbergError`] because Pdfium failures
+//! carry PDF-specific context that doesn't map cleanly onto the generic
+//! extraction-pipeline variants. Call sites pass the variant constructor
+//! that matches their own context (e.g. [`PdfError::RenderFailed`] from the
+//! rendering path) as the `map_err` parameter of
+//! [`super::bindings::bind_pdfium`], then convert to [`crate::KreuzbergError`]
+//! via `From` at the point where the error crosses into the rest of the
+//! extraction pipeline.
+
+use std::fmt;
+
+/// Errors produced by the PDF binding, text-extraction, and rendering paths.
+#[derive(Debug)]
+pub enum PdfError {
+    /// Extracting text from a page or document failed.
+    TextExtractionFailed(String),
+    /// Rendering a page to an image failed.
+    RenderFailed(String),
+    /// The document is encrypted and no password was supplied.
+    PasswordRequired,
+    /// The document is encrypted and the supplied password didn't open it.
+    IncorrectPassword(String),
+}
+
+impl fmt::Display for PdfError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PdfError::TextExtractionFailed(message) => write!(f, "PDF text extraction failed: {message}"),
+            PdfError::RenderFailed(message) => write!(f, "PDF rendering failed: {message}"),
+            PdfError::PasswordRequired => write!(f, "Password required to open encrypted PDF document"),
+            PdfError::IncorrectPassword(message) => {
+                write!(f, "Incorrect password for encrypted PDF document: {message}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PdfError {}
+
+impl From<PdfError> for crate::KreuzbergError {
+    fn from(err: PdfError) -> Self {
+        match err {
+            PdfError::PasswordRequired | PdfError::IncorrectPassword(_) => crate::KreuzbergError::Validation {
+                message: err.to_string(),
+                source: None,
+            },
+            _ => crate::KreuzbergError::Parsing {
+                message: err.to_string(),
+                source: None,
+            },
+        }
+    }
+}
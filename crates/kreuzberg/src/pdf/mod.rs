@@ -0,0 +1,11 @@
+//! PDF-specific extraction machinery: Pdfium bindings, PDF-specific errors,
+//! and page rendering.
+//!
+//! Text extraction from PDFs lives alongside the other format extractors in
+//! [`crate::extractors`]; this module holds the lower-level Pdfium plumbing
+//! those extractors (and the rendering subsystem in [`render`]) share.
+
+pub(crate) mod bindings;
+pub mod error;
+pub mod render;
+pub mod security;
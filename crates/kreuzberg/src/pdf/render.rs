@@ -0,0 +1,269 @@
+//! Rasterize PDF pages to images for previews and thumbnails.
+//!
+//! Built on pdfium-render's `PdfRenderConfig`, running against the calling
+//! thread's cached Pdfium instance via [`super::bindings::with_thread_pdfium`]
+//! so rendering reuses the same per-thread binding as text extraction rather
+//! than paying its own initialization cost.
+
+use super::bindings::{classify_password_error, with_thread_pdfium};
+use super::error::PdfError;
+use pdfium_render::prelude::*;
+use std::io::Cursor;
+use std::ops::Range;
+
+const POINTS_PER_INCH: f32 = 72.0;
+
+/// How a rendered page should be rotated before encoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RenderRotation {
+    #[default]
+    None,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+}
+
+impl RenderRotation {
+    fn to_pdfium(self) -> PdfPageRenderRotation {
+        match self {
+            RenderRotation::None => PdfPageRenderRotation::None,
+            RenderRotation::Rotate90 => PdfPageRenderRotation::Degrees90,
+            RenderRotation::Rotate180 => PdfPageRenderRotation::Degrees180,
+            RenderRotation::Rotate270 => PdfPageRenderRotation::Degrees270,
+        }
+    }
+}
+
+/// Output image format for a rendered page.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RenderEncoding {
+    Png,
+    Jpeg { quality: u8 },
+}
+
+impl RenderEncoding {
+    fn mime_type(self) -> &'static str {
+        match self {
+            RenderEncoding::Png => "image/png",
+            RenderEncoding::Jpeg { .. } => "image/jpeg",
+        }
+    }
+}
+
+/// Options controlling how pages are rasterized.
+///
+/// `dpi` and `target_width` both size the output but are not meant to be
+/// combined: when `dpi` is set it takes priority and `target_width` is
+/// ignored, since the DPI is resolved against each page's own point size.
+#[derive(Debug, Clone)]
+pub struct RenderOptions {
+    /// Target width in pixels.
+    pub target_width: Option<i32>,
+    /// Maximum height in pixels; pdfium scales the image down further if
+    /// applying `target_width` would exceed this.
+    pub maximum_height: Option<i32>,
+    /// Render at a fixed DPI instead of a pixel target, derived from each
+    /// page's point size (1 point = 1/72 inch).
+    pub dpi: Option<f32>,
+    /// Zero-based, end-exclusive range of page indices to render. `None`
+    /// renders every page in the document.
+    pub page_range: Option<Range<u16>>,
+    pub rotation: RenderRotation,
+    pub encoding: RenderEncoding,
+    /// Password for an encrypted document. `None` if the document isn't
+    /// encrypted; if it is and no password (or the wrong one) is supplied,
+    /// [`render_pdf_pages`] returns [`PdfError::PasswordRequired`] or
+    /// [`PdfError::IncorrectPassword`] respectively.
+    pub password: Option<String>,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            target_width: Some(1024),
+            maximum_height: None,
+            dpi: None,
+            page_range: None,
+            rotation: RenderRotation::default(),
+            encoding: RenderEncoding::Png,
+            password: None,
+        }
+    }
+}
+
+/// A single rasterized PDF page.
+#[derive(Debug, Clone)]
+pub struct RenderedPage {
+    pub page_index: u16,
+    pub mime_type: String,
+    pub bytes: Vec<u8>,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Render a PDF's pages to images according to `options`.
+///
+/// Runs on this thread's cached Pdfium instance via [`with_thread_pdfium`],
+/// so only the first call on a given thread pays Pdfium's binding cost;
+/// subsequent calls on that thread reuse it.
+pub fn render_pdf_pages(bytes: &[u8], options: &RenderOptions) -> Result<Vec<RenderedPage>, PdfError> {
+    with_thread_pdfium(
+        PdfError::RenderFailed,
+        "render_pdf_pages",
+        |pdfium| -> Result<Vec<RenderedPage>, PdfError> {
+            let document = pdfium
+                .load_pdf_from_byte_slice(bytes, options.password.as_deref())
+                .map_err(|e| {
+                    let message = e.to_string();
+                    classify_password_error(&message, options.password.is_some())
+                        .unwrap_or_else(|| PdfError::RenderFailed(format!("Failed to load PDF document: {message}")))
+                })?;
+
+            let pages = document.pages();
+            let page_count = pages.len();
+
+            let range = match &options.page_range {
+                Some(range) => range.clone(),
+                None => 0..page_count,
+            };
+
+            let mut rendered = Vec::with_capacity(range.len());
+            for index in range {
+                let page = pages
+                    .get(index)
+                    .map_err(|e| PdfError::RenderFailed(format!("Failed to get page {index}: {e}")))?;
+
+                let mut config = PdfRenderConfig::new().rotate(options.rotation.to_pdfium(), false);
+
+                if let Some(dpi) = options.dpi {
+                    let target_width = ((page.width().value / POINTS_PER_INCH) * dpi).round().max(1.0) as i32;
+                    config = config.set_target_width(target_width);
+                } else if let Some(width) = options.target_width {
+                    config = config.set_target_width(width);
+                }
+
+                if let Some(height) = options.maximum_height {
+                    config = config.set_maximum_height(height);
+                }
+
+                let bitmap = page
+                    .render_with_config(&config)
+                    .map_err(|e| PdfError::RenderFailed(format!("Failed to render page {index}: {e}")))?;
+
+                let image = bitmap.as_image();
+                let width = image.width();
+                let height = image.height();
+
+                let mut encoded = Cursor::new(Vec::new());
+                match options.encoding {
+                    RenderEncoding::Png => {
+                        image.write_to(&mut encoded, image::ImageFormat::Png).map_err(|e| {
+                            PdfError::RenderFailed(format!("Failed to encode page {index} as PNG: {e}"))
+                        })?;
+                    }
+                    RenderEncoding::Jpeg { quality } => {
+                        let mut encoder = image::codecs::jpeg::JpegEncoder::new_with_quality(&mut encoded, quality);
+                        encoder.encode_image(&image).map_err(|e| {
+                            PdfError::RenderFailed(format!("Failed to encode page {index} as JPEG: {e}"))
+                        })?;
+                    }
+                }
+
+                rendered.push(RenderedPage {
+                    page_index: index,
+                    mime_type: options.encoding.mime_type().to_string(),
+                    bytes: encoded.into_inner(),
+                    width,
+                    height,
+                });
+            }
+
+            Ok(rendered)
+        },
+    )?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MINIMAL_PDF_BYTES: &[u8] = b"%PDF-1.4\n\
+1 0 obj\n\
+<< /Type /Catalog /Pages 2 0 R >>\n\
+endobj\n\
+2 0 obj\n\
+<< /Type /Pages /Kids [3 0 R] /Count 1 >>\n\
+endobj\n\
+3 0 obj\n\
+<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] >>\n\
+endobj\n\
+xref\n\
+0 4\n\
+0000000000 65535 f\n\
+0000000009 00000 n\n\
+0000000058 00000 n\n\
+0000000115 00000 n\n\
+trailer\n\
+<< /Size 4 /Root 1 0 R >>\n\
+startxref\n\
+190\n\
+%%EOF";
+
+    #[test]
+    fn test_render_rotation_default_is_none() {
+        assert_eq!(RenderRotation::default(), RenderRotation::None);
+    }
+
+    #[test]
+    fn test_render_rotation_maps_to_pdfium_variants() {
+        assert_eq!(RenderRotation::None.to_pdfium(), PdfPageRenderRotation::None);
+        assert_eq!(RenderRotation::Rotate90.to_pdfium(), PdfPageRenderRotation::Degrees90);
+        assert_eq!(RenderRotation::Rotate180.to_pdfium(), PdfPageRenderRotation::Degrees180);
+        assert_eq!(RenderRotation::Rotate270.to_pdfium(), PdfPageRenderRotation::Degrees270);
+    }
+
+    #[test]
+    fn test_render_encoding_mime_types() {
+        assert_eq!(RenderEncoding::Png.mime_type(), "image/png");
+        assert_eq!(RenderEncoding::Jpeg { quality: 80 }.mime_type(), "image/jpeg");
+    }
+
+    #[test]
+    fn test_render_options_default_targets_1024px_width() {
+        let options = RenderOptions::default();
+        assert_eq!(options.target_width, Some(1024));
+        assert_eq!(options.maximum_height, None);
+        assert_eq!(options.dpi, None);
+        assert_eq!(options.page_range, None);
+        assert_eq!(options.encoding, RenderEncoding::Png);
+        assert_eq!(options.password, None);
+    }
+
+    #[test]
+    fn test_render_pdf_pages_rasterizes_single_page_as_png() {
+        let options = RenderOptions {
+            target_width: Some(200),
+            ..Default::default()
+        };
+
+        let pages = render_pdf_pages(MINIMAL_PDF_BYTES, &options).expect("rendering should succeed");
+
+        assert_eq!(pages.len(), 1);
+        assert_eq!(pages[0].page_index, 0);
+        assert_eq!(pages[0].mime_type, "image/png");
+        assert!(!pages[0].bytes.is_empty());
+        assert_eq!(pages[0].width, 200);
+    }
+
+    #[test]
+    fn test_render_pdf_pages_honors_page_range() {
+        let options = RenderOptions {
+            page_range: Some(1..1),
+            ..Default::default()
+        };
+
+        let pages = render_pdf_pages(MINIMAL_PDF_BYTES, &options).expect("rendering should succeed");
+
+        assert!(pages.is_empty());
+    }
+}
@@ -0,0 +1,379 @@
+//! Lightweight active-content detection for PDFs.
+//!
+//! Mirrors ClamAV's practical approach to flagging suspicious PDFs: rather
+//! than fully parsing the object tree (pdfium-render's safe API doesn't
+//! expose arbitrary catalog/dictionary traversal), this scans the raw
+//! object stream for the PDF name tokens ClamAV's parser flags —
+//! `/JavaScript`, `/JS`, `/OpenAction`, `/AA`, `/Launch`, and embedded-file
+//! markers — giving callers a quick content-security signal during
+//! extraction without a separate tool.
+//!
+//! Since PDF 1.5, most writers store object and cross-reference data in
+//! compressed `ObjStm`/`XRef` streams rather than plain-text objects, and
+//! content streams have always been routinely `/FlateDecode`-compressed —
+//! so scanning only the raw bytes would miss `/JavaScript`, `/OpenAction`,
+//! `/Launch`, and embedded-file markers in most real-world PDFs from
+//! recent tools. To cover that, every `stream`/`endstream` body in the
+//! document is opportunistically zlib-inflated (see
+//! [`decompressed_stream_contents`]) and scanned alongside the raw bytes.
+
+use super::bindings::{classify_password_error, with_thread_pdfium};
+use super::error::PdfError;
+use flate2::read::ZlibDecoder;
+use std::io::Read;
+
+/// Cap on how much of a `/JS` action's script body is captured per
+/// occurrence, so a pathological or malformed document can't make
+/// [`analyze_pdf_security`] buffer an unbounded amount of text.
+const MAX_SNIPPET_LEN: usize = 500;
+
+/// Structured report of active-content indicators found in a PDF.
+///
+/// Covers both plain-text objects and `/FlateDecode`-compressed stream
+/// bodies (content streams, and the compressed `ObjStm`/`XRef` streams
+/// PDF 1.5+ writers use for object/cross-reference data) — see the module
+/// docs for how compressed streams are detected.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PdfSecurityReport {
+    /// Whether the document contains a `/JavaScript` name-tree entry or a
+    /// `/JS` action.
+    pub has_javascript: bool,
+    /// Whether the document's catalog has an `/OpenAction` entry, i.e. an
+    /// action that runs automatically when the document is opened.
+    pub has_open_action: bool,
+    /// Number of `/EmbeddedFile` markers found (attached files, which may
+    /// carry their own payload).
+    pub embedded_files: usize,
+    /// Number of `/Launch` actions found (actions that run an external
+    /// application or file).
+    pub launch_actions: usize,
+    /// Best-effort extraction of `/JS` action script bodies, truncated to
+    /// [`MAX_SNIPPET_LEN`] bytes each.
+    pub scripts: Vec<String>,
+}
+
+impl PdfSecurityReport {
+    /// Serialize this report to a JSON value.
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "has_javascript": self.has_javascript,
+            "has_open_action": self.has_open_action,
+            "embedded_files": self.embedded_files,
+            "launch_actions": self.launch_actions,
+            "scripts": self.scripts,
+        })
+    }
+}
+
+/// Analyze a PDF document for active-content indicators.
+///
+/// Loads the document on this thread's cached Pdfium instance via
+/// [`with_thread_pdfium`] first, so an encrypted document with a missing or
+/// incorrect `password` fails the same way [`super::render::render_pdf_pages`]
+/// does, then scans the raw bytes — and every `/FlateDecode`-compressed
+/// stream body that successfully inflates — for the relevant name tokens.
+pub fn analyze_pdf_security(bytes: &[u8], password: Option<&str>) -> Result<PdfSecurityReport, PdfError> {
+    with_thread_pdfium(
+        PdfError::RenderFailed,
+        "analyze_pdf_security",
+        |pdfium| -> Result<PdfSecurityReport, PdfError> {
+            let _document = pdfium.load_pdf_from_byte_slice(bytes, password).map_err(|e| {
+                let message = e.to_string();
+                classify_password_error(&message, password.is_some())
+                    .unwrap_or_else(|| PdfError::RenderFailed(format!("Failed to load PDF document: {message}")))
+            })?;
+
+            Ok(scan_for_active_content(bytes))
+        },
+    )?
+}
+
+/// Pure byte-level scan for active-content indicators. Kept free of Pdfium
+/// so it can be unit tested without a loaded document.
+///
+/// Scans the raw bytes, then every stream body that successfully zlib-
+/// inflates (see [`decompressed_stream_contents`]), so markers hidden
+/// inside compressed content/object streams are caught too.
+fn scan_for_active_content(bytes: &[u8]) -> PdfSecurityReport {
+    let mut report = scan_bytes_for_active_content(bytes);
+
+    for decompressed in decompressed_stream_contents(bytes) {
+        let inner = scan_bytes_for_active_content(&decompressed);
+        report.has_javascript |= inner.has_javascript;
+        report.has_open_action |= inner.has_open_action;
+        report.embedded_files += inner.embedded_files;
+        report.launch_actions += inner.launch_actions;
+        report.scripts.extend(inner.scripts);
+    }
+
+    report
+}
+
+/// Scan a single (already-decompressed, if applicable) byte slice for
+/// active-content indicators, with no awareness of surrounding streams.
+fn scan_bytes_for_active_content(bytes: &[u8]) -> PdfSecurityReport {
+    let has_javascript = !find_token_positions(bytes, b"/JavaScript").is_empty() || has_js_action(bytes);
+    let has_open_action = !find_token_positions(bytes, b"/OpenAction").is_empty();
+    let embedded_files = find_token_positions(bytes, b"/EmbeddedFile").len();
+    let launch_actions = find_token_positions(bytes, b"/Launch").len();
+    let scripts = extract_js_snippets(bytes);
+
+    PdfSecurityReport {
+        has_javascript,
+        has_open_action,
+        embedded_files,
+        launch_actions,
+        scripts,
+    }
+}
+
+/// Cap on how much inflated output a single stream is allowed to produce,
+/// so a maliciously crafted zlib bomb can't make [`scan_for_active_content`]
+/// exhaust memory.
+const MAX_DECOMPRESSED_STREAM_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Zlib-inflate every `stream`/`endstream` body in `bytes`, discarding any
+/// that don't decode as zlib data.
+///
+/// Deliberately doesn't parse the enclosing object dictionary's `/Filter`
+/// entry first (that would need real PDF tokenizing, which this
+/// byte-level scanner doesn't do anywhere else): `/FlateDecode` dominates
+/// real-world PDFs so overwhelmingly that attempting to inflate every
+/// stream body and keeping only the ones that succeed is simpler and just
+/// as effective.
+fn decompressed_stream_contents(bytes: &[u8]) -> Vec<Vec<u8>> {
+    find_stream_bodies(bytes)
+        .into_iter()
+        .filter_map(|body| {
+            let mut buf = Vec::new();
+            ZlibDecoder::new(body)
+                .take(MAX_DECOMPRESSED_STREAM_BYTES)
+                .read_to_end(&mut buf)
+                .ok()?;
+            (!buf.is_empty()).then_some(buf)
+        })
+        .collect()
+}
+
+/// Find every `stream ... endstream` body in `bytes`: the bytes between
+/// the end-of-line immediately after the `stream` keyword (per the PDF
+/// spec, `stream` is always followed by CRLF or a bare LF before the
+/// actual data) and the matching `endstream` keyword.
+fn find_stream_bodies(bytes: &[u8]) -> Vec<&[u8]> {
+    let mut bodies = Vec::new();
+    let mut search_from = 0;
+
+    while let Some(rel_pos) = find_subslice(&bytes[search_from..], b"stream") {
+        let keyword_start = search_from + rel_pos;
+        let mut body_start = keyword_start + b"stream".len();
+
+        if bytes[body_start..].starts_with(b"\r\n") {
+            body_start += 2;
+        } else if bytes[body_start..].starts_with(b"\n") {
+            body_start += 1;
+        } else {
+            // Not a real `stream` keyword occurrence (e.g. the tail of
+            // `endstream`, or malformed input) - keep scanning past it.
+            search_from = keyword_start + b"stream".len();
+            continue;
+        }
+
+        let Some(end_rel) = find_subslice(&bytes[body_start..], b"endstream") else {
+            break;
+        };
+        let body_end = body_start + end_rel;
+        bodies.push(&bytes[body_start..body_end]);
+        search_from = body_end + b"endstream".len();
+    }
+
+    bodies
+}
+
+/// Find the first occurrence of `needle` in `haystack`, or `None` if absent.
+fn find_subslice(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    if needle.is_empty() || haystack.len() < needle.len() {
+        return None;
+    }
+    haystack.windows(needle.len()).position(|window| window == needle)
+}
+
+fn has_js_action(bytes: &[u8]) -> bool {
+    !find_token_positions(bytes, b"/JS").is_empty()
+}
+
+/// Find every byte offset where `token` occurs in `bytes` on a name-token
+/// boundary (not immediately followed by an alphanumeric byte, so `/JS`
+/// doesn't match inside a longer name like `/JSON`).
+fn find_token_positions(bytes: &[u8], token: &[u8]) -> Vec<usize> {
+    let mut positions = Vec::new();
+    if token.is_empty() || bytes.len() < token.len() {
+        return positions;
+    }
+
+    for start in 0..=(bytes.len() - token.len()) {
+        if &bytes[start..start + token.len()] == token {
+            let boundary_ok = bytes
+                .get(start + token.len())
+                .map(|b| !b.is_ascii_alphanumeric())
+                .unwrap_or(true);
+            if boundary_ok {
+                positions.push(start);
+            }
+        }
+    }
+
+    positions
+}
+
+/// Best-effort extraction of the parenthesized string literal following
+/// each `/JS` token, honoring PDF's backslash-escaped and nested
+/// parentheses. Not a full PDF string-literal parser (octal escapes,
+/// hex strings, and indirect-reference script streams aren't handled) —
+/// good enough for a lightweight signal, not a guarantee of completeness.
+fn extract_js_snippets(bytes: &[u8]) -> Vec<String> {
+    let mut scripts = Vec::new();
+
+    for pos in find_token_positions(bytes, b"/JS") {
+        let after = pos + b"/JS".len();
+        let Some(open_offset) = bytes[after..].iter().position(|&b| b == b'(') else {
+            continue;
+        };
+        let start = after + open_offset + 1;
+
+        let mut depth = 1usize;
+        let mut end = start;
+        while end < bytes.len() && depth > 0 {
+            match bytes[end] {
+                b'\\' => end += 1,
+                b'(' => depth += 1,
+                b')' => depth -= 1,
+                _ => {}
+            }
+            end += 1;
+        }
+
+        if end <= start {
+            continue;
+        }
+
+        let close = (end - 1).min(bytes.len());
+        let snippet_end = close.min(start + MAX_SNIPPET_LEN);
+        if snippet_end > start {
+            scripts.push(String::from_utf8_lossy(&bytes[start..snippet_end]).into_owned());
+        }
+    }
+
+    scripts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_token_positions_respects_name_boundary() {
+        let positions = find_token_positions(b"/JSON /JS /JS(x)", b"/JS");
+        assert_eq!(positions, vec![6, 10]);
+    }
+
+    #[test]
+    fn test_scan_for_active_content_detects_javascript_name_tree() {
+        let report = scan_for_active_content(b"<< /Names << /JavaScript 3 0 R >> >>");
+        assert!(report.has_javascript);
+        assert!(!report.has_open_action);
+    }
+
+    #[test]
+    fn test_scan_for_active_content_detects_open_action_and_launch() {
+        let report = scan_for_active_content(b"<< /OpenAction 4 0 R >> << /S /Launch /F (evil.exe) >>");
+        assert!(report.has_open_action);
+        assert_eq!(report.launch_actions, 1);
+    }
+
+    #[test]
+    fn test_scan_for_active_content_counts_embedded_files() {
+        let report = scan_for_active_content(b"<< /Type /EmbeddedFile >> << /Type /EmbeddedFile >>");
+        assert_eq!(report.embedded_files, 2);
+    }
+
+    /// Build a minimal `stream ... endstream` block wrapping `content`,
+    /// zlib-compressed, the way a `/FlateDecode` content or `ObjStm` object
+    /// stream would appear in a real PDF.
+    fn flate_stream_block(content: &[u8]) -> Vec<u8> {
+        use flate2::Compression;
+        use flate2::write::ZlibEncoder;
+        use std::io::Write;
+
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(content).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let mut block = Vec::new();
+        block.extend_from_slice(b"<< /Filter /FlateDecode /Length ");
+        block.extend_from_slice(compressed.len().to_string().as_bytes());
+        block.extend_from_slice(b" >>\nstream\n");
+        block.extend_from_slice(&compressed);
+        block.extend_from_slice(b"\nendstream\nendobj\n");
+        block
+    }
+
+    #[test]
+    fn test_scan_for_active_content_detects_javascript_inside_compressed_stream() {
+        // This is the shape a PDF 1.5+ writer actually produces: the
+        // `/JavaScript` name tree lives inside a `/FlateDecode`-compressed
+        // `ObjStm` object stream rather than as a plain-text object, which a
+        // scan of the raw bytes alone would silently miss.
+        let block = flate_stream_block(b"<< /Type /ObjStm >> << /Names << /JavaScript 3 0 R >> >>");
+        let report = scan_for_active_content(&block);
+        assert!(
+            report.has_javascript,
+            "JavaScript marker inside a compressed stream must still be detected"
+        );
+    }
+
+    #[test]
+    fn test_scan_for_active_content_detects_open_action_and_embedded_file_inside_compressed_stream() {
+        let block = flate_stream_block(
+            b"<< /OpenAction 4 0 R >> << /S /Launch /F (evil.exe) >> << /Type /EmbeddedFile >>",
+        );
+        let report = scan_for_active_content(&block);
+        assert!(report.has_open_action);
+        assert_eq!(report.launch_actions, 1);
+        assert_eq!(report.embedded_files, 1);
+    }
+
+    #[test]
+    fn test_decompressed_stream_contents_ignores_non_flate_streams() {
+        let mut block = Vec::new();
+        block.extend_from_slice(b"<< /Filter /DCTDecode /Length 11 >>\nstream\nnot zlib!!\nendstream\nendobj\n");
+        assert!(decompressed_stream_contents(&block).is_empty());
+    }
+
+    #[test]
+    fn test_extract_js_snippets_captures_parenthesized_script() {
+        let scripts = extract_js_snippets(b"<< /S /JavaScript /JS (app.alert\\('hi'\\);) >>");
+        assert_eq!(scripts, vec!["app.alert\\('hi'\\);".to_string()]);
+    }
+
+    #[test]
+    fn test_extract_js_snippets_handles_nested_parentheses() {
+        let scripts = extract_js_snippets(b"/JS (if (true) { alert(1); })");
+        assert_eq!(scripts, vec!["if (true) { alert(1); }".to_string()]);
+    }
+
+    #[test]
+    fn test_pdf_security_report_to_json_includes_all_fields() {
+        let report = PdfSecurityReport {
+            has_javascript: true,
+            has_open_action: false,
+            embedded_files: 2,
+            launch_actions: 1,
+            scripts: vec!["alert(1)".to_string()],
+        };
+
+        let json = report.to_json();
+        assert_eq!(json["has_javascript"], true);
+        assert_eq!(json["embedded_files"], 2);
+        assert_eq!(json["scripts"][0], "alert(1)");
+    }
+}
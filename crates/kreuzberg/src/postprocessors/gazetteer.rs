@@ -0,0 +1,327 @@
+//! Aho-Corasick gazetteer postprocessor.
+//!
+//! Scans extracted text against a large, user-supplied dictionary of terms
+//! (PII markers, product names, taxonomy keywords, secret prefixes, ...) in a
+//! single pass using an Aho-Corasick automaton, rather than running one
+//! substring search per pattern. This complements the YAKE/RAKE keyword
+//! extractors, which only surface statistically salient terms, with
+//! deterministic matching against a known vocabulary.
+
+use std::collections::VecDeque;
+
+/// Configuration for the gazetteer postprocessor.
+#[derive(Debug, Clone)]
+pub struct GazetteerConfig {
+    /// Whether the gazetteer pass runs at all.
+    pub enabled: bool,
+    /// Dictionary entries: (label, pattern) pairs. The label is attached to
+    /// every match so callers can distinguish e.g. "email" from "api-key".
+    pub terms: Vec<(String, String)>,
+    /// Match patterns without regard to ASCII/Unicode case.
+    pub case_insensitive: bool,
+    /// When set, matched spans are replaced with this string instead of
+    /// merely being reported. Useful for scrubbing PII in place.
+    pub redaction_replacement: Option<String>,
+}
+
+impl Default for GazetteerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            terms: Vec::new(),
+            case_insensitive: true,
+            redaction_replacement: None,
+        }
+    }
+}
+
+/// A single dictionary hit produced by a gazetteer scan.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GazetteerMatch {
+    /// Byte offset of the first byte of the match in the scanned text.
+    pub start: usize,
+    /// Byte offset one past the last byte of the match.
+    pub end: usize,
+    /// Label of the pattern that matched (as supplied in `GazetteerConfig::terms`).
+    pub label: String,
+    /// The exact substring that matched, as it appears in the source text.
+    pub matched_text: String,
+}
+
+/// Result of running the gazetteer over a document.
+#[derive(Debug, Clone, Default)]
+pub struct GazetteerResult {
+    /// Every dictionary hit found, in document order.
+    pub matches: Vec<GazetteerMatch>,
+    /// The text with matches replaced, present only when redaction is enabled.
+    pub redacted_text: Option<String>,
+}
+
+struct TrieNode {
+    children: std::collections::HashMap<char, usize>,
+    fail: usize,
+    /// Indices into `patterns` for every pattern ending at this node, including
+    /// those inherited through failure links during automaton construction.
+    output: Vec<usize>,
+}
+
+impl TrieNode {
+    fn new() -> Self {
+        Self {
+            children: std::collections::HashMap::new(),
+            fail: 0,
+            output: Vec::new(),
+        }
+    }
+}
+
+/// An Aho-Corasick automaton built from a fixed set of (label, pattern) pairs.
+///
+/// Construction is the classic two-phase algorithm: insert every pattern into
+/// a trie, then compute failure links with a BFS over the trie so that each
+/// node's failure pointer is the longest proper suffix of its path that is
+/// also a trie node, unioning output sets along the way. Scanning then walks
+/// the text once, following goto edges when possible and failure links
+/// otherwise, for overall O(n + matches) time.
+pub struct GazetteerAutomaton {
+    nodes: Vec<TrieNode>,
+    patterns: Vec<(String, String)>,
+    case_insensitive: bool,
+}
+
+impl GazetteerAutomaton {
+    /// Build an automaton from dictionary entries.
+    pub fn build(terms: &[(String, String)], case_insensitive: bool) -> Self {
+        let mut nodes = vec![TrieNode::new()];
+        let patterns: Vec<(String, String)> = terms.to_vec();
+
+        for (idx, (_, pattern)) in patterns.iter().enumerate() {
+            let normalized = Self::normalize(pattern, case_insensitive);
+            let mut current = 0;
+            for ch in normalized.chars() {
+                current = if let Some(&idx) = nodes[current].children.get(&ch) {
+                    idx
+                } else {
+                    let idx = nodes.len();
+                    nodes.push(TrieNode::new());
+                    nodes[current].children.insert(ch, idx);
+                    idx
+                };
+            }
+            nodes[current].output.push(idx);
+        }
+
+        let mut automaton = Self {
+            nodes,
+            patterns,
+            case_insensitive,
+        };
+        automaton.build_failure_links();
+        automaton
+    }
+
+    fn normalize(s: &str, case_insensitive: bool) -> String {
+        if case_insensitive { s.to_lowercase() } else { s.to_string() }
+    }
+
+    fn build_failure_links(&mut self) {
+        let mut queue = VecDeque::new();
+
+        let root_children: Vec<(char, usize)> = self.nodes[0]
+            .children
+            .iter()
+            .map(|(&c, &idx)| (c, idx))
+            .collect();
+        for (_, child) in root_children {
+            self.nodes[child].fail = 0;
+            queue.push_back(child);
+        }
+
+        while let Some(current) = queue.pop_front() {
+            let children: Vec<(char, usize)> = self.nodes[current]
+                .children
+                .iter()
+                .map(|(&c, &idx)| (c, idx))
+                .collect();
+
+            for (ch, child) in children {
+                let mut fail = self.nodes[current].fail;
+                let child_fail = loop {
+                    if let Some(&next) = self.nodes[fail].children.get(&ch) {
+                        if next != child {
+                            break next;
+                        }
+                    }
+                    if fail == 0 {
+                        break 0;
+                    }
+                    fail = self.nodes[fail].fail;
+                };
+
+                self.nodes[child].fail = child_fail;
+                let inherited = self.nodes[child_fail].output.clone();
+                self.nodes[child].output.extend(inherited);
+                queue.push_back(child);
+            }
+        }
+    }
+
+    fn goto(&self, state: usize, ch: char) -> usize {
+        let mut current = state;
+        loop {
+            if let Some(&next) = self.nodes[current].children.get(&ch) {
+                return next;
+            }
+            if current == 0 {
+                return 0;
+            }
+            current = self.nodes[current].fail;
+        }
+    }
+
+    /// Scan `text` once, emitting every dictionary hit with byte offsets.
+    pub fn scan(&self, text: &str) -> Vec<GazetteerMatch> {
+        let normalized = Self::normalize(text, self.case_insensitive);
+        if normalized.chars().count() != text.chars().count() {
+            // Case folding changed the character count (rare, e.g. German ß);
+            // fall back to scanning the normalized text directly so offsets
+            // stay self-consistent even though they no longer map 1:1 to the
+            // original bytes.
+            return self.scan_aligned(&normalized, &normalized);
+        }
+        self.scan_aligned(&normalized, text)
+    }
+
+    fn scan_aligned(&self, scan_text: &str, source_text: &str) -> Vec<GazetteerMatch> {
+        let mut state = 0usize;
+        let mut matches = Vec::new();
+
+        let char_offsets: Vec<usize> = source_text
+            .char_indices()
+            .map(|(idx, _)| idx)
+            .chain(std::iter::once(source_text.len()))
+            .collect();
+
+        for (pos, ch) in scan_text.chars().enumerate() {
+            state = self.goto(state, ch);
+            for &pattern_idx in &self.nodes[state].output {
+                let (label, pattern) = &self.patterns[pattern_idx];
+                let pattern_len = pattern.chars().count();
+                if pos + 1 < pattern_len {
+                    continue;
+                }
+                let start_char = pos + 1 - pattern_len;
+                let start = char_offsets[start_char];
+                let end = char_offsets[pos + 1];
+                matches.push(GazetteerMatch {
+                    start,
+                    end,
+                    label: label.clone(),
+                    matched_text: source_text[start..end].to_string(),
+                });
+            }
+        }
+
+        matches.sort_by_key(|m| m.start);
+        matches
+    }
+}
+
+/// Run the gazetteer postprocessor over `text` according to `config`.
+///
+/// When `config.redaction_replacement` is set, every match in the returned
+/// text is replaced with that string; otherwise the text is left untouched
+/// and callers are expected to annotate matches into result metadata.
+pub fn run_gazetteer(text: &str, config: &GazetteerConfig) -> GazetteerResult {
+    if !config.enabled || config.terms.is_empty() {
+        return GazetteerResult::default();
+    }
+
+    let automaton = GazetteerAutomaton::build(&config.terms, config.case_insensitive);
+    let matches = automaton.scan(text);
+
+    let redacted_text = config.redaction_replacement.as_ref().map(|replacement| {
+        let mut redacted = String::with_capacity(text.len());
+        let mut last_end = 0;
+        for m in &matches {
+            if m.start < last_end {
+                continue;
+            }
+            redacted.push_str(&text[last_end..m.start]);
+            redacted.push_str(replacement);
+            last_end = m.end;
+        }
+        redacted.push_str(&text[last_end..]);
+        redacted
+    });
+
+    GazetteerResult { matches, redacted_text }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn terms() -> Vec<(String, String)> {
+        vec![
+            ("greeting".to_string(), "hello".to_string()),
+            ("name".to_string(), "world".to_string()),
+            ("overlap".to_string(), "he".to_string()),
+        ]
+    }
+
+    #[test]
+    fn test_scan_finds_all_matches_in_one_pass() {
+        let automaton = GazetteerAutomaton::build(&terms(), true);
+        let matches = automaton.scan("hello world");
+        let labels: Vec<&str> = matches.iter().map(|m| m.label.as_str()).collect();
+        assert!(labels.contains(&"greeting"));
+        assert!(labels.contains(&"name"));
+        assert!(labels.contains(&"overlap"));
+    }
+
+    #[test]
+    fn test_scan_is_case_insensitive_by_default() {
+        let automaton = GazetteerAutomaton::build(&terms(), true);
+        let matches = automaton.scan("HELLO WORLD");
+        assert!(matches.iter().any(|m| m.label == "greeting"));
+    }
+
+    #[test]
+    fn test_scan_respects_case_sensitivity() {
+        let automaton = GazetteerAutomaton::build(&terms(), false);
+        let matches = automaton.scan("HELLO world");
+        assert!(!matches.iter().any(|m| m.label == "greeting"));
+        assert!(matches.iter().any(|m| m.label == "name"));
+    }
+
+    #[test]
+    fn test_run_gazetteer_redacts_matches() {
+        let config = GazetteerConfig {
+            enabled: true,
+            terms: vec![("secret".to_string(), "sk-live".to_string())],
+            case_insensitive: true,
+            redaction_replacement: Some("[REDACTED]".to_string()),
+        };
+        let result = run_gazetteer("token=sk-live12345", &config);
+        assert_eq!(result.matches.len(), 1);
+        assert_eq!(result.redacted_text.unwrap(), "token=[REDACTED]12345");
+    }
+
+    #[test]
+    fn test_run_gazetteer_disabled_is_noop() {
+        let config = GazetteerConfig::default();
+        let result = run_gazetteer("hello world", &config);
+        assert!(result.matches.is_empty());
+        assert!(result.redacted_text.is_none());
+    }
+
+    #[test]
+    fn test_byte_offsets_are_correct_for_multibyte_text() {
+        let automaton = GazetteerAutomaton::build(&[("euro".to_string(), "euro".to_string())], true);
+        let text = "café costs 10 euro";
+        let matches = automaton.scan(text);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(&text[matches[0].start..matches[0].end], "euro");
+    }
+}
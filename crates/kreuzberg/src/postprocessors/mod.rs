@@ -0,0 +1,7 @@
+//! Post-processing subsystems that run over already-extracted text.
+//!
+//! These are distinct from the document extractors in [`crate::extractors`]:
+//! they operate on the flattened `content` of an [`ExtractionResult`] rather
+//! than on raw bytes, and are toggled independently via `PostProcessorConfig`.
+
+pub mod gazetteer;
@@ -0,0 +1,156 @@
+//! Shared test helpers for comparing extracted content against Pandoc
+//! baselines by *meaning* rather than raw byte length.
+//!
+//! [`compare_with_baseline`] in `rtf_extractor_tests.rs` only checks a
+//! length ratio, which tolerates wildly different content as long as it
+//! happens to be the right size. [`similarity`] instead tokenizes both
+//! strings into words and scores them by longest-common-subsequence
+//! overlap, the same idea Pandoc's own golden-file test suite uses.
+
+use std::fmt::Write as _;
+
+/// Split `text` into whitespace-delimited words, in order.
+pub fn tokenize_words(text: &str) -> Vec<&str> {
+    text.split_whitespace().collect()
+}
+
+/// Length of the longest common subsequence of `a` and `b`, via the
+/// standard O(|a| * |b|) dynamic program. Fine for the test-fixture sizes
+/// this helper is used against; not intended for large-document diffing.
+fn lcs_table(a: &[&str], b: &[&str]) -> Vec<Vec<usize>> {
+    let mut table = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            table[i][j] = if a[i - 1] == b[j - 1] {
+                table[i - 1][j - 1] + 1
+            } else {
+                table[i - 1][j].max(table[i][j - 1])
+            };
+        }
+    }
+    table
+}
+
+/// Result of comparing extracted content against a baseline: word counts
+/// plus the similarity score `S = 2*L / (len_extracted + len_baseline)`,
+/// where `L` is the LCS length over whitespace-tokenized words. `S` is `1.0`
+/// for identical word sequences and `0.0` for wholly disjoint ones,
+/// regardless of either side's embedded formatting noise.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SimilarityReport {
+    pub extracted_words: usize,
+    pub baseline_words: usize,
+    pub lcs_words: usize,
+    pub similarity: f64,
+}
+
+/// Compare `extracted` against `baseline` by word-level LCS similarity.
+pub fn similarity(extracted: &str, baseline: &str) -> SimilarityReport {
+    let extracted_words = tokenize_words(extracted);
+    let baseline_words = tokenize_words(baseline);
+    let table = lcs_table(&extracted_words, &baseline_words);
+    let lcs_words = table[extracted_words.len()][baseline_words.len()];
+
+    let total = extracted_words.len() + baseline_words.len();
+    let similarity = if total == 0 { 1.0 } else { (2 * lcs_words) as f64 / total as f64 };
+
+    SimilarityReport {
+        extracted_words: extracted_words.len(),
+        baseline_words: baseline_words.len(),
+        lcs_words,
+        similarity,
+    }
+}
+
+/// One aligned line of [`diff_lines`]'s output: a word kept in both texts,
+/// one only in `extracted`, or one only in `baseline`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum DiffOp<'a> {
+    Equal(&'a str),
+    Insert(&'a str),
+    Delete(&'a str),
+}
+
+/// Walk the LCS table backwards to recover the aligned sequence of
+/// kept/inserted/deleted words between `extracted` and `baseline`.
+fn diff_ops<'a>(extracted: &[&'a str], baseline: &[&'a str]) -> Vec<DiffOp<'a>> {
+    let table = lcs_table(extracted, baseline);
+    let mut ops = Vec::new();
+    let (mut i, mut j) = (extracted.len(), baseline.len());
+
+    while i > 0 || j > 0 {
+        if i > 0 && j > 0 && extracted[i - 1] == baseline[j - 1] {
+            ops.push(DiffOp::Equal(extracted[i - 1]));
+            i -= 1;
+            j -= 1;
+        } else if j > 0 && (i == 0 || table[i][j - 1] >= table[i - 1][j]) {
+            ops.push(DiffOp::Insert(baseline[j - 1]));
+            j -= 1;
+        } else {
+            ops.push(DiffOp::Delete(extracted[i - 1]));
+            i -= 1;
+        }
+    }
+
+    ops.reverse();
+    ops
+}
+
+/// Render a human-readable diff between `extracted` and `baseline`, one
+/// word-diff line per row: `  word` for a match, `+ word` for a word only
+/// in the baseline (missing from `extracted`), `- word` for a word only in
+/// `extracted` (not in the baseline). Intended for printing alongside a
+/// failing similarity assertion.
+pub fn format_diff(extracted: &str, baseline: &str) -> String {
+    let extracted_words = tokenize_words(extracted);
+    let baseline_words = tokenize_words(baseline);
+
+    let mut out = String::new();
+    for op in diff_ops(&extracted_words, &baseline_words) {
+        match op {
+            DiffOp::Equal(word) => {
+                let _ = writeln!(out, "  {}", word);
+            }
+            DiffOp::Insert(word) => {
+                let _ = writeln!(out, "+ {}", word);
+            }
+            DiffOp::Delete(word) => {
+                let _ = writeln!(out, "- {}", word);
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_similarity_is_one_for_identical_text() {
+        let report = similarity("the quick brown fox", "the quick brown fox");
+        assert_eq!(report.similarity, 1.0);
+        assert_eq!(report.lcs_words, 4);
+    }
+
+    #[test]
+    fn test_similarity_is_zero_for_disjoint_text() {
+        let report = similarity("alpha beta", "gamma delta");
+        assert_eq!(report.similarity, 0.0);
+        assert_eq!(report.lcs_words, 0);
+    }
+
+    #[test]
+    fn test_similarity_tolerates_inserted_formatting_noise() {
+        let report = similarity("the quick brown fox jumps", "the quick *brown* fox jumps over the lazy dog");
+        assert!(report.similarity > 0.5, "similarity was {}", report.similarity);
+    }
+
+    #[test]
+    fn test_format_diff_marks_insertions_and_deletions() {
+        let diff = format_diff("the fox jumps", "the quick fox");
+        assert!(diff.contains("+ quick"));
+        assert!(diff.contains("- jumps"));
+        assert!(diff.contains("  the"));
+    }
+}
@@ -768,6 +768,19 @@ async fn test_rtf_pandoc_parity_accent() {
         extracted_len,
         baseline_len
     );
+
+    // Length ratio alone tolerates unrelated content of the right size; also
+    // check word-level overlap against the baseline so the assertion means
+    // something regardless of embedded formatting noise.
+    let baseline_path = get_baseline_path("accent_pandoc_baseline.txt");
+    let baseline = fs::read_to_string(&baseline_path).expect("Failed to read accent baseline");
+    let report = helpers::similarity(&extraction.content, &baseline);
+    assert!(
+        report.similarity >= 0.8,
+        "FAIL: word-level similarity {:.2} below 0.8\n{}",
+        report.similarity,
+        helpers::format_diff(&extraction.content, &baseline)
+    );
 }
 
 /// Test Pandoc parity for heading.rtf
@@ -0,0 +1,46 @@
+#![no_main]
+
+use kreuzberg_fuzz::FuzzInput;
+use libfuzzer_sys::fuzz_target;
+use std::io::Write;
+
+fuzz_target!(|input: FuzzInput| {
+    let mime = input.mime.as_str();
+
+    let Ok(runtime) = tokio::runtime::Runtime::new() else {
+        return;
+    };
+
+    let sync_result = kreuzberg::extract_bytes_sync(&input.bytes, mime, None);
+    let async_result = runtime.block_on(kreuzberg::extract_bytes(&input.bytes, mime, None));
+
+    let Ok(mut temp_file) = tempfile::NamedTempFile::new() else {
+        return;
+    };
+    if temp_file.write_all(&input.bytes).is_err() {
+        return;
+    }
+    let file_result = runtime.block_on(kreuzberg::extract_file(temp_file.path(), Some(mime), None));
+
+    // All three paths are handed the same bytes, so they must agree on
+    // success/failure and, when they succeed, on the extracted content and
+    // metadata. A mismatch here is exactly the sync/async or file/bytes
+    // divergence this target exists to catch.
+    match (sync_result, async_result, file_result) {
+        (Ok(sync), Ok(r#async), Ok(file)) => {
+            assert_eq!(sync.content, r#async.content, "sync/async content diverged for {mime}");
+            assert_eq!(sync.content, file.content, "bytes/file content diverged for {mime}");
+            assert_eq!(
+                format!("{:?}", sync.metadata),
+                format!("{:?}", r#async.metadata),
+                "sync/async metadata diverged for {mime}"
+            );
+        }
+        (Err(_), Err(_), Err(_)) => {}
+        (sync, r#async, file) => {
+            panic!(
+                "extraction paths diverged on success/failure for {mime}: sync={sync:?}, async={async:?}, file={file:?}"
+            );
+        }
+    }
+});
@@ -0,0 +1,14 @@
+#![no_main]
+
+use kreuzberg_fuzz::FuzzInput;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: FuzzInput| {
+    // The extractor must never panic or abort the process, regardless of
+    // what garbage bytes it's handed; a returned `Err` is a perfectly fine
+    // outcome, only a panic/abort is a bug. For the Pdfium-backed MIME
+    // types this relies on `with_thread_pdfium`'s thread-confined instance
+    // pool staying well-behaved across repeated fuzz iterations on the
+    // same thread, not just on a single call.
+    let _ = kreuzberg::extract_bytes_sync(&input.bytes, input.mime.as_str(), None);
+});
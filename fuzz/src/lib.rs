@@ -0,0 +1,59 @@
+//! Shared fixtures for the `kreuzberg` fuzz targets.
+
+use arbitrary::Arbitrary;
+
+/// MIME types the crate's extractors advertise support for, including
+/// `application/pdf` so the Pdfium-backed code paths (and the double-free /
+/// lock-poisoning failure modes `bind_pdfium`'s doc comments worry about)
+/// get fuzzed too.
+#[derive(Debug, Clone, Copy, Arbitrary)]
+pub enum MimeChoice {
+    Pdf,
+    Rtf,
+    RtfAlt,
+    Epub,
+    EpubAltX,
+    EpubAltVnd,
+    Latex,
+    Tex,
+    PlainText,
+    Org,
+    Rst,
+    Textile,
+    MediaWiki,
+    DokuWiki,
+    Muse,
+    Creole,
+    Jats,
+}
+
+impl MimeChoice {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            MimeChoice::Pdf => "application/pdf",
+            MimeChoice::Rtf => "application/rtf",
+            MimeChoice::RtfAlt => "text/rtf",
+            MimeChoice::Epub => "application/epub+zip",
+            MimeChoice::EpubAltX => "application/x-epub+zip",
+            MimeChoice::EpubAltVnd => "application/vnd.epub+zip",
+            MimeChoice::Latex => "application/x-latex",
+            MimeChoice::Tex => "text/x-tex",
+            MimeChoice::PlainText => "text/plain",
+            MimeChoice::Org => "text/org",
+            MimeChoice::Rst => "text/x-rst",
+            MimeChoice::Textile => "text/textile",
+            MimeChoice::MediaWiki => "text/x-mediawiki",
+            MimeChoice::DokuWiki => "text/x-dokuwiki",
+            MimeChoice::Muse => "text/x-muse",
+            MimeChoice::Creole => "text/x-creole",
+            MimeChoice::Jats => "application/x-jats+xml",
+        }
+    }
+}
+
+/// Structured fuzz input: an arbitrary MIME type paired with arbitrary bytes.
+#[derive(Debug, Arbitrary)]
+pub struct FuzzInput {
+    pub mime: MimeChoice,
+    pub bytes: Vec<u8>,
+}
@@ -0,0 +1,249 @@
+//! Machine-readable output formats for benchmark runs
+//!
+//! The baseline tests historically reported results with bare `println!`
+//! calls, readable only in raw stdout. [`BenchmarkReport`] captures the
+//! outcome of a single benchmark (name, sample count, [`ResourceStats`],
+//! pass/fail), and the [`Formatter`] trait renders a run's reports in the
+//! format a CI dashboard expects: [`JsonFormatter`] (one JSON object per
+//! line, rustc-libtest style), [`JunitFormatter`] (a JUnit XML
+//! `<testsuite>`), or [`TerseFormatter`] (a short human summary). Select a
+//! formatter with [`Formatter::from_env`] or by constructing one directly.
+
+use crate::monitoring::ResourceStats;
+use std::fmt::Write as _;
+
+/// Environment variable [`Formatter::from_env`] reads to pick a formatter.
+/// Recognizes `json`, `junit`, and `terse` (case-insensitive); defaults to
+/// `terse` when unset or unrecognized.
+pub const FORMATTER_ENV_VAR: &str = "BENCHMARK_FORMAT";
+
+/// Outcome of a single benchmark run, ready to hand to a [`Formatter`].
+#[derive(Debug, Clone)]
+pub struct BenchmarkReport {
+    /// Name of the benchmark, e.g. `"extract_pdf_10mb"`.
+    pub name: String,
+    /// Number of [`crate::monitoring::ResourceSample`]s the stats were computed from.
+    pub sample_count: usize,
+    pub stats: ResourceStats,
+    pub passed: bool,
+    /// Assertion message explaining the failure, e.g. a regression
+    /// comparison's verdict. `None` when `passed` is `true`.
+    pub failure_message: Option<String>,
+}
+
+impl BenchmarkReport {
+    /// Build a passing report.
+    pub fn passed(name: impl Into<String>, sample_count: usize, stats: ResourceStats) -> Self {
+        Self { name: name.into(), sample_count, stats, passed: true, failure_message: None }
+    }
+
+    /// Build a failing report carrying the assertion message that explains why.
+    pub fn failed(name: impl Into<String>, sample_count: usize, stats: ResourceStats, message: impl Into<String>) -> Self {
+        Self { name: name.into(), sample_count, stats, passed: false, failure_message: Some(message.into()) }
+    }
+}
+
+/// Renders a batch of [`BenchmarkReport`]s. Implementations produce a
+/// complete document (JSON lines, a JUnit XML suite, or a terse summary) in
+/// [`Self::format`]; there is no incremental/streaming variant since a
+/// benchmark run's report count is small and known up front.
+pub trait Formatter {
+    /// Render `reports` as this formatter's output document.
+    fn format(&self, reports: &[BenchmarkReport]) -> String;
+}
+
+impl dyn Formatter {
+    /// Pick a [`Formatter`] from the [`FORMATTER_ENV_VAR`] environment
+    /// variable. Defaults to [`TerseFormatter`] when unset or unrecognized.
+    pub fn from_env() -> Box<dyn Formatter> {
+        match std::env::var(FORMATTER_ENV_VAR).unwrap_or_default().to_lowercase().as_str() {
+            "json" => Box::new(JsonFormatter),
+            "junit" => Box::new(JunitFormatter),
+            _ => Box::new(TerseFormatter),
+        }
+    }
+}
+
+/// One JSON object per line, modeled on rustc libtest's `--format json`:
+/// each report becomes a single-line event so a CI log parser can stream
+/// results without buffering the whole document.
+pub struct JsonFormatter;
+
+impl Formatter for JsonFormatter {
+    fn format(&self, reports: &[BenchmarkReport]) -> String {
+        let mut out = String::new();
+        for report in reports {
+            let line = serde_json::json!({
+                "type": "benchmark",
+                "name": report.name,
+                "event": if report.passed { "ok" } else { "failed" },
+                "sample_count": report.sample_count,
+                "avg_cpu_percent": report.stats.avg_cpu_percent,
+                "peak_memory_bytes": report.stats.peak_memory_bytes,
+                "mean_ci": [report.stats.mean_ci.0, report.stats.mean_ci.1],
+                "mild_outliers": report.stats.mild_outliers,
+                "severe_outliers": report.stats.severe_outliers,
+                "failure_message": report.failure_message,
+            });
+            out.push_str(&line.to_string());
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// A JUnit XML `<testsuite>` of `<testcase>` elements, consumable by CI
+/// dashboards (GitHub Actions, GitLab, Jenkins) that understand the JUnit
+/// format. Failing reports get a nested `<failure>` carrying the assertion message.
+pub struct JunitFormatter;
+
+impl Formatter for JunitFormatter {
+    fn format(&self, reports: &[BenchmarkReport]) -> String {
+        let failures = reports.iter().filter(|r| !r.passed).count();
+        let mut out = String::new();
+        let _ = writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+        let _ = writeln!(
+            out,
+            r#"<testsuite name="benchmark-harness" tests="{}" failures="{}">"#,
+            reports.len(),
+            failures
+        );
+        for report in reports {
+            let _ = writeln!(
+                out,
+                r#"  <testcase name="{}" classname="benchmark-harness">"#,
+                xml_escape(&report.name)
+            );
+            if let Some(message) = &report.failure_message {
+                let _ = writeln!(
+                    out,
+                    r#"    <failure message="{}">{}</failure>"#,
+                    xml_escape(message),
+                    xml_escape(message)
+                );
+            }
+            let _ = writeln!(out, "  </testcase>");
+        }
+        let _ = writeln!(out, "</testsuite>");
+        out
+    }
+}
+
+/// A short human-readable summary: one line per benchmark plus a final
+/// pass/fail count, matching the `println!`-based output the baseline
+/// tests used before this module existed.
+pub struct TerseFormatter;
+
+impl Formatter for TerseFormatter {
+    fn format(&self, reports: &[BenchmarkReport]) -> String {
+        let mut out = String::new();
+        for report in reports {
+            let status = if report.passed { "ok" } else { "FAILED" };
+            let _ = writeln!(
+                out,
+                "test {} ... {} ({} samples, avg cpu {:.2}%)",
+                report.name, status, report.sample_count, report.stats.avg_cpu_percent
+            );
+            if let Some(message) = &report.failure_message {
+                let _ = writeln!(out, "  {}", message);
+            }
+        }
+        let passed = reports.iter().filter(|r| r.passed).count();
+        let _ = writeln!(out, "result: {}/{} passed", passed, reports.len());
+        out
+    }
+}
+
+/// Escape the five characters XML attribute/text values can't contain raw.
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_stats() -> ResourceStats {
+        ResourceStats {
+            avg_cpu_percent: 42.5,
+            peak_memory_bytes: 1024,
+            p50_memory_bytes: 512,
+            p95_memory_bytes: 900,
+            p99_memory_bytes: 1000,
+            mean_ci: (40.0, 45.0),
+            median_ci: (40.0, 45.0),
+            mild_outliers: 1,
+            severe_outliers: 0,
+            winsorized_mean_cpu_percent: 42.5,
+            std_dev_cpu_percent: 2.5,
+            mad_cpu_percent: 1.5,
+        }
+    }
+
+    #[test]
+    fn test_json_formatter_emits_one_line_per_report() {
+        let reports = vec![
+            BenchmarkReport::passed("a", 100, sample_stats()),
+            BenchmarkReport::failed("b", 50, sample_stats(), "regression detected"),
+        ];
+
+        let output = JsonFormatter.format(&reports);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 2);
+
+        let first: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(first["name"], "a");
+        assert_eq!(first["event"], "ok");
+
+        let second: serde_json::Value = serde_json::from_str(lines[1]).unwrap();
+        assert_eq!(second["event"], "failed");
+        assert_eq!(second["failure_message"], "regression detected");
+    }
+
+    #[test]
+    fn test_junit_formatter_includes_failure_element_for_failed_reports() {
+        let reports = vec![
+            BenchmarkReport::passed("a", 100, sample_stats()),
+            BenchmarkReport::failed("b", 50, sample_stats(), "regression detected"),
+        ];
+
+        let output = JunitFormatter.format(&reports);
+        assert!(output.contains(r#"tests="2" failures="1""#));
+        assert!(output.contains(r#"<testcase name="a""#));
+        assert!(output.contains(r#"<failure message="regression detected">regression detected</failure>"#));
+    }
+
+    #[test]
+    fn test_junit_formatter_escapes_special_characters() {
+        let reports = vec![BenchmarkReport::failed("a<b>", 1, sample_stats(), "x & y")];
+        let output = JunitFormatter.format(&reports);
+        assert!(output.contains("a&lt;b&gt;"));
+        assert!(output.contains("x &amp; y"));
+    }
+
+    #[test]
+    fn test_terse_formatter_reports_pass_fail_counts() {
+        let reports = vec![
+            BenchmarkReport::passed("a", 100, sample_stats()),
+            BenchmarkReport::failed("b", 50, sample_stats(), "regression detected"),
+        ];
+
+        let output = TerseFormatter.format(&reports);
+        assert!(output.contains("test a ... ok"));
+        assert!(output.contains("test b ... FAILED"));
+        assert!(output.contains("result: 1/2 passed"));
+    }
+
+    #[test]
+    fn test_from_env_defaults_to_terse_when_unset() {
+        std::env::remove_var(FORMATTER_ENV_VAR);
+        let formatter = <dyn Formatter>::from_env();
+        let output = formatter.format(&[BenchmarkReport::passed("a", 1, sample_stats())]);
+        assert!(output.contains("test a ... ok"));
+    }
+}
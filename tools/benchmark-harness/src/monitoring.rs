@@ -0,0 +1,686 @@
+//! CPU and memory resource monitoring for benchmark runs
+//!
+//! [`ResourceMonitor`] samples the current process's CPU and memory usage at
+//! a fixed interval on a background task, then [`ResourceMonitor::calculate_stats`]
+//! turns the collected samples into a [`ResourceStats`] summary: mean CPU
+//! usage, memory percentiles, and the statistical-rigor additions
+//! ([`ResourceStats::mean_ci`]/[`ResourceStats::median_ci`] bootstrap
+//! confidence intervals and Tukey-fence outlier counts) that let benchmark
+//! assertions use a principled interval instead of an ad-hoc
+//! coefficient-of-variation threshold, plus a winsorized mean
+//! ([`ResourceStats::winsorized_mean_cpu_percent`]) and median absolute
+//! deviation ([`ResourceStats::mad_cpu_percent`]) that are robust to a
+//! single scheduler-hiccup spike. [`ResourceMonitor::save_baseline`]/
+//! [`ResourceMonitor::load_baseline`] persist a run's stats to disk, and
+//! [`ResourceMonitor::compare_to_baseline`] classifies a new run against the
+//! saved one as [`Verdict::Improved`]/[`Verdict::Regressed`]/[`Verdict::NoChange`],
+//! turning one-shot validation into a true regression guard.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use sysinfo::{Pid, System};
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+
+/// Default noise threshold for [`ResourceMonitor::compare_to_baseline`]: a
+/// point estimate must move by more than this fraction of the baseline mean
+/// (in addition to falling outside the baseline's confidence interval)
+/// before it's called `Improved`/`Regressed` rather than `NoChange`.
+const DEFAULT_NOISE_THRESHOLD: f64 = 0.05;
+
+/// Directory baselines are saved to/loaded from by [`ResourceMonitor::save_baseline`]
+/// and [`ResourceMonitor::load_baseline`], relative to the current working directory.
+const BASELINE_DIR: &str = "baselines";
+
+/// Number of bootstrap resamples drawn per [`bootstrap_ci`] call. 100k
+/// resamples keeps the 2.5th/97.5th percentile estimates stable without
+/// making `calculate_stats` noticeably slow.
+const BOOTSTRAP_RESAMPLES: usize = 100_000;
+
+/// Below this many samples, a bootstrap CI is unreliable (and the Tukey
+/// fences are near-meaningless), so [`bootstrap_ci`] degrades to the point
+/// estimate as both bounds instead of resampling.
+const MIN_SAMPLES_FOR_CI: usize = 10;
+
+/// Percentile [`winsorize`] clamps to by default: values below the 5th
+/// percentile are raised to it, values above the 95th are lowered to it.
+/// Matches rustc libtest's `bench.rs` winsorization of benchmark samples.
+const WINSORIZE_PERCENTILE: f64 = 5.0;
+
+/// Below this many samples, winsorizing would clamp away most of the
+/// distribution, so [`winsorize`] is a no-op (returns the values unchanged).
+const MIN_SAMPLES_FOR_WINSORIZE: usize = 20;
+
+/// One CPU/memory observation taken during a monitored run.
+#[derive(Debug, Clone, Copy)]
+pub struct ResourceSample {
+    /// CPU usage normalized to a 0-100% single-core-equivalent range
+    /// (the process's raw `sysinfo` usage divided by the physical core
+    /// count and clamped), so it stays comparable across machines.
+    pub cpu_percent: f64,
+    /// Resident memory in bytes at the time of this sample.
+    pub memory_bytes: u64,
+}
+
+/// A memory-only observation, recorded alongside each [`ResourceSample`]
+/// but kept in its own buffer that [`ResourceMonitor::stop`] doesn't drain,
+/// so callers can inspect the full run's memory profile via
+/// [`ResourceMonitor::get_snapshots`] after sampling has already stopped.
+#[derive(Debug, Clone, Copy)]
+pub struct MemorySnapshot {
+    /// Resident memory in bytes.
+    pub bytes: u64,
+}
+
+/// Statistics derived from a monitored run's [`ResourceSample`]s and
+/// [`MemorySnapshot`]s by [`ResourceMonitor::calculate_stats`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ResourceStats {
+    /// Mean of `cpu_percent` across all samples.
+    pub avg_cpu_percent: f64,
+    /// Largest memory reading across all snapshots.
+    pub peak_memory_bytes: u64,
+    /// Median memory reading.
+    pub p50_memory_bytes: u64,
+    /// 95th percentile memory reading.
+    pub p95_memory_bytes: u64,
+    /// 99th percentile memory reading.
+    pub p99_memory_bytes: u64,
+    /// 95% bootstrap confidence interval `(lower, upper)` for the mean
+    /// `cpu_percent`, from 100k resamples with replacement. Degrades to
+    /// `(avg_cpu_percent, avg_cpu_percent)` below [`MIN_SAMPLES_FOR_CI`] samples.
+    pub mean_ci: (f64, f64),
+    /// 95% bootstrap confidence interval `(lower, upper)` for the median
+    /// `cpu_percent`. Degrades the same way as [`Self::mean_ci`].
+    pub median_ci: (f64, f64),
+    /// Count of samples outside the Tukey "mild" fences
+    /// (`Q1 - 1.5*IQR`/`Q3 + 1.5*IQR`) but within the severe ones.
+    pub mild_outliers: usize,
+    /// Count of samples outside the Tukey "severe" fences
+    /// (`Q1 - 3*IQR`/`Q3 + 3*IQR`).
+    pub severe_outliers: usize,
+    /// Mean of `cpu_percent` after [`WINSORIZE_PERCENTILE`]-winsorizing the
+    /// samples (clamping the bottom/top 5% to the 5th/95th percentile
+    /// values), so a single scheduler-hiccup spike doesn't skew a short
+    /// CPU-bound benchmark's headline number. Equal to `avg_cpu_percent`
+    /// below [`MIN_SAMPLES_FOR_WINSORIZE`] samples, where winsorizing would
+    /// collapse most of the distribution instead of trimming its tails.
+    pub winsorized_mean_cpu_percent: f64,
+    /// Standard deviation of `cpu_percent` across all samples.
+    pub std_dev_cpu_percent: f64,
+    /// Median absolute deviation of `cpu_percent`: the median of
+    /// `|value - median|` across all samples. A robust spread measure that,
+    /// unlike [`Self::std_dev_cpu_percent`], isn't itself dominated by the
+    /// same outliers [`Self::mild_outliers`]/[`Self::severe_outliers`] flag.
+    pub mad_cpu_percent: f64,
+}
+
+/// Samples the current process's CPU and memory usage on a background
+/// task, started with [`Self::start`] and stopped with [`Self::stop`].
+pub struct ResourceMonitor {
+    samples: Arc<Mutex<Vec<ResourceSample>>>,
+    snapshots: Arc<Mutex<Vec<MemorySnapshot>>>,
+    task: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl ResourceMonitor {
+    /// Create a monitor with no samples collected yet.
+    pub fn new() -> Self {
+        Self {
+            samples: Arc::new(Mutex::new(Vec::new())),
+            snapshots: Arc::new(Mutex::new(Vec::new())),
+            task: Mutex::new(None),
+        }
+    }
+
+    /// Begin sampling CPU and memory usage every `interval` on a background
+    /// task. Calling this again before [`Self::stop`] replaces the running
+    /// task.
+    pub async fn start(&self, interval: Duration) {
+        let samples = Arc::clone(&self.samples);
+        let snapshots = Arc::clone(&self.snapshots);
+        let handle = tokio::spawn(sample_loop(interval, samples, snapshots));
+        *self.task.lock().await = Some(handle);
+    }
+
+    /// Stop the background sampling task and return every [`ResourceSample`]
+    /// collected since [`Self::start`]. [`Self::get_snapshots`] remains
+    /// available afterwards.
+    pub async fn stop(&self) -> Vec<ResourceSample> {
+        if let Some(handle) = self.task.lock().await.take() {
+            handle.abort();
+        }
+        std::mem::take(&mut *self.samples.lock().await)
+    }
+
+    /// Every [`MemorySnapshot`] recorded so far, independent of whether
+    /// [`Self::stop`] has already drained the [`ResourceSample`]s.
+    pub async fn get_snapshots(&self) -> Vec<MemorySnapshot> {
+        self.snapshots.lock().await.clone()
+    }
+
+    /// Summarize a run's samples and snapshots into [`ResourceStats`].
+    pub fn calculate_stats(samples: &[ResourceSample], snapshots: &[MemorySnapshot]) -> ResourceStats {
+        let cpu_values: Vec<f64> = samples.iter().map(|s| s.cpu_percent).collect();
+        let avg_cpu_percent = mean(&cpu_values);
+
+        let mut memory_bytes: Vec<u64> = snapshots.iter().map(|s| s.bytes).collect();
+        memory_bytes.sort_unstable();
+        let peak_memory_bytes = memory_bytes.last().copied().unwrap_or(0);
+
+        let mean_ci = bootstrap_ci(&cpu_values, mean, 0xC0FFEE_u64);
+        let median_ci = bootstrap_ci(&cpu_values, median, 0xDECAFBAD_u64);
+        let (mild_outliers, severe_outliers) = classify_tukey_outliers(&cpu_values);
+
+        let winsorized_mean_cpu_percent = mean(&winsorize(&cpu_values, WINSORIZE_PERCENTILE));
+        let std_dev_cpu_percent = std_dev(&cpu_values);
+        let mad_cpu_percent = median_absolute_deviation(&cpu_values);
+
+        ResourceStats {
+            avg_cpu_percent,
+            peak_memory_bytes,
+            p50_memory_bytes: percentile_u64(&memory_bytes, 50.0),
+            p95_memory_bytes: percentile_u64(&memory_bytes, 95.0),
+            p99_memory_bytes: percentile_u64(&memory_bytes, 99.0),
+            mean_ci,
+            median_ci,
+            mild_outliers,
+            severe_outliers,
+            winsorized_mean_cpu_percent,
+            std_dev_cpu_percent,
+            mad_cpu_percent,
+        }
+    }
+
+    /// Path a baseline named `name` is saved to/loaded from, under
+    /// [`BASELINE_DIR`].
+    fn baseline_path(name: &str) -> PathBuf {
+        Path::new(BASELINE_DIR).join(format!("{name}.json"))
+    }
+
+    /// Persist `stats` as the baseline for benchmark `name`, creating
+    /// [`BASELINE_DIR`] if it doesn't exist yet. Overwrites any existing
+    /// baseline of the same name.
+    pub fn save_baseline(name: &str, stats: &ResourceStats) -> Result<(), Box<dyn std::error::Error>> {
+        fs::create_dir_all(BASELINE_DIR)?;
+        let json = serde_json::to_string_pretty(&serde_json::json!({
+            "avg_cpu_percent": stats.avg_cpu_percent,
+            "peak_memory_bytes": stats.peak_memory_bytes,
+            "p50_memory_bytes": stats.p50_memory_bytes,
+            "p95_memory_bytes": stats.p95_memory_bytes,
+            "p99_memory_bytes": stats.p99_memory_bytes,
+            "mean_ci": [stats.mean_ci.0, stats.mean_ci.1],
+            "median_ci": [stats.median_ci.0, stats.median_ci.1],
+            "mild_outliers": stats.mild_outliers,
+            "severe_outliers": stats.severe_outliers,
+            "winsorized_mean_cpu_percent": stats.winsorized_mean_cpu_percent,
+            "std_dev_cpu_percent": stats.std_dev_cpu_percent,
+            "mad_cpu_percent": stats.mad_cpu_percent,
+        }))?;
+        fs::write(Self::baseline_path(name), json)?;
+        Ok(())
+    }
+
+    /// Load the baseline previously saved for benchmark `name`.
+    pub fn load_baseline(name: &str) -> Result<ResourceStats, Box<dyn std::error::Error>> {
+        let raw = fs::read_to_string(Self::baseline_path(name))?;
+        let value: serde_json::Value = serde_json::from_str(&raw)?;
+
+        let ci_pair = |key: &str| -> (f64, f64) {
+            let arr = value[key].as_array();
+            let lower = arr.and_then(|a| a.first()).and_then(|v| v.as_f64()).unwrap_or(0.0);
+            let upper = arr.and_then(|a| a.get(1)).and_then(|v| v.as_f64()).unwrap_or(0.0);
+            (lower, upper)
+        };
+
+        Ok(ResourceStats {
+            avg_cpu_percent: value["avg_cpu_percent"].as_f64().unwrap_or(0.0),
+            peak_memory_bytes: value["peak_memory_bytes"].as_u64().unwrap_or(0),
+            p50_memory_bytes: value["p50_memory_bytes"].as_u64().unwrap_or(0),
+            p95_memory_bytes: value["p95_memory_bytes"].as_u64().unwrap_or(0),
+            p99_memory_bytes: value["p99_memory_bytes"].as_u64().unwrap_or(0),
+            mean_ci: ci_pair("mean_ci"),
+            median_ci: ci_pair("median_ci"),
+            mild_outliers: value["mild_outliers"].as_u64().unwrap_or(0) as usize,
+            severe_outliers: value["severe_outliers"].as_u64().unwrap_or(0) as usize,
+            winsorized_mean_cpu_percent: value["winsorized_mean_cpu_percent"].as_f64().unwrap_or(0.0),
+            std_dev_cpu_percent: value["std_dev_cpu_percent"].as_f64().unwrap_or(0.0),
+            mad_cpu_percent: value["mad_cpu_percent"].as_f64().unwrap_or(0.0),
+        })
+    }
+
+    /// Compare `stats` against the baseline saved for benchmark `name` under
+    /// [`Self::save_baseline`].
+    ///
+    /// Borrows Criterion's comparison model: the relative change in mean CPU
+    /// usage is `Regressed`/`Improved` only when the new point estimate
+    /// falls outside the baseline's [`ResourceStats::mean_ci`] *and* moves by
+    /// more than `noise_threshold` (a fraction of the baseline mean, default
+    /// [`DEFAULT_NOISE_THRESHOLD`]); otherwise it's `NoChange`. A higher CPU
+    /// mean is treated as a regression (more work done for the same task).
+    pub fn compare_to_baseline(name: &str, stats: &ResourceStats, noise_threshold: Option<f64>) -> Result<Comparison, Box<dyn std::error::Error>> {
+        let baseline = Self::load_baseline(name)?;
+        let noise_threshold = noise_threshold.unwrap_or(DEFAULT_NOISE_THRESHOLD);
+
+        let relative_change = if baseline.avg_cpu_percent != 0.0 {
+            (stats.avg_cpu_percent - baseline.avg_cpu_percent) / baseline.avg_cpu_percent
+        } else {
+            0.0
+        };
+
+        let outside_ci =
+            stats.avg_cpu_percent < baseline.mean_ci.0 || stats.avg_cpu_percent > baseline.mean_ci.1;
+
+        let verdict = if outside_ci && relative_change > noise_threshold {
+            Verdict::Regressed
+        } else if outside_ci && relative_change < -noise_threshold {
+            Verdict::Improved
+        } else {
+            Verdict::NoChange
+        };
+
+        Ok(Comparison {
+            benchmark_name: name.to_string(),
+            baseline,
+            current: *stats,
+            relative_change,
+            verdict,
+        })
+    }
+}
+
+/// Pass/fail/improve classification produced by [`ResourceMonitor::compare_to_baseline`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Improved,
+    Regressed,
+    NoChange,
+}
+
+/// Result of comparing a run's [`ResourceStats`] against a saved baseline.
+#[derive(Debug, Clone)]
+pub struct Comparison {
+    pub benchmark_name: String,
+    pub baseline: ResourceStats,
+    pub current: ResourceStats,
+    /// `(current.avg_cpu_percent - baseline.avg_cpu_percent) / baseline.avg_cpu_percent`.
+    pub relative_change: f64,
+    pub verdict: Verdict,
+}
+
+impl Comparison {
+    /// Whether this comparison should fail a CI run.
+    pub fn is_regression(&self) -> bool {
+        self.verdict == Verdict::Regressed
+    }
+}
+
+impl Default for ResourceMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Background sampling loop spawned by [`ResourceMonitor::start`]; runs
+/// until its `JoinHandle` is aborted by [`ResourceMonitor::stop`].
+async fn sample_loop(
+    interval: Duration,
+    samples: Arc<Mutex<Vec<ResourceSample>>>,
+    snapshots: Arc<Mutex<Vec<MemorySnapshot>>>,
+) {
+    let pid = Pid::from_u32(std::process::id());
+    let mut system = System::new();
+    let core_count = System::physical_core_count().unwrap_or(1).max(1) as f64;
+    let mut ticker = tokio::time::interval(interval);
+
+    loop {
+        ticker.tick().await;
+        system.refresh_process(pid);
+        let Some(process) = system.process(pid) else {
+            continue;
+        };
+
+        // `Process::cpu_usage` can exceed 100% on multi-core machines (one
+        // core fully busy per logical CPU); normalize to a single-core
+        // equivalent and clamp so `avg_cpu_percent` stays in [0, 100].
+        let cpu_percent = (process.cpu_usage() as f64 / core_count).clamp(0.0, 100.0);
+        let memory_bytes = process.memory();
+
+        samples.lock().await.push(ResourceSample { cpu_percent, memory_bytes });
+        snapshots.lock().await.push(MemorySnapshot { bytes: memory_bytes });
+    }
+}
+
+fn mean(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        0.0
+    } else {
+        values.iter().sum::<f64>() / values.len() as f64
+    }
+}
+
+fn median(values: &[f64]) -> f64 {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    percentile_sorted(&sorted, 50.0)
+}
+
+fn std_dev(values: &[f64]) -> f64 {
+    if values.len() < 2 {
+        return 0.0;
+    }
+    let avg = mean(values);
+    let variance = values.iter().map(|v| (v - avg).powi(2)).sum::<f64>() / values.len() as f64;
+    variance.sqrt()
+}
+
+/// Median of `|value - median(values)|` across `values`: a spread measure
+/// that, unlike standard deviation, isn't dominated by the outliers it's
+/// meant to be robust against.
+fn median_absolute_deviation(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    let center = median(values);
+    let deviations: Vec<f64> = values.iter().map(|v| (v - center).abs()).collect();
+    median(&deviations)
+}
+
+/// Clamp the bottom/top `percentile`% of `values` to the `percentile`th/
+/// `(100 - percentile)`th percentile value (symmetric winsorization), as
+/// rustc libtest's `bench.rs` does before computing benchmark summary
+/// statistics. A no-op below [`MIN_SAMPLES_FOR_WINSORIZE`] samples, where
+/// clamping would collapse most of a small distribution instead of just
+/// trimming its tails.
+fn winsorize(values: &[f64], percentile: f64) -> Vec<f64> {
+    if values.len() < MIN_SAMPLES_FOR_WINSORIZE {
+        return values.to_vec();
+    }
+
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let lower = percentile_sorted(&sorted, percentile);
+    let upper = percentile_sorted(&sorted, 100.0 - percentile);
+
+    values.iter().map(|&v| v.clamp(lower, upper)).collect()
+}
+
+/// Linearly-interpolated percentile of an already-sorted slice.
+fn percentile_sorted(sorted: &[f64], percent: f64) -> f64 {
+    match sorted.len() {
+        0 => 0.0,
+        1 => sorted[0],
+        len => {
+            let rank = (percent / 100.0) * (len - 1) as f64;
+            let lower = rank.floor() as usize;
+            let upper = rank.ceil() as usize;
+            if lower == upper {
+                sorted[lower]
+            } else {
+                let weight = rank - lower as f64;
+                sorted[lower] * (1.0 - weight) + sorted[upper] * weight
+            }
+        }
+    }
+}
+
+fn percentile_u64(sorted: &[u64], percent: f64) -> u64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let rank = ((percent / 100.0) * (sorted.len() - 1) as f64).round() as usize;
+    sorted[rank.min(sorted.len() - 1)]
+}
+
+/// Bootstrap a 95% confidence interval for `statistic` over `values` by
+/// drawing [`BOOTSTRAP_RESAMPLES`] resamples with replacement (seeded by
+/// `seed`, for reproducibility) and taking the 2.5th/97.5th percentiles of
+/// the resulting distribution. Degrades to the point estimate as both
+/// bounds below [`MIN_SAMPLES_FOR_CI`] values.
+fn bootstrap_ci(values: &[f64], statistic: impl Fn(&[f64]) -> f64, seed: u64) -> (f64, f64) {
+    if values.len() < MIN_SAMPLES_FOR_CI {
+        let point = statistic(values);
+        return (point, point);
+    }
+
+    let mut rng = SplitMix64::new(seed);
+    let mut resample = vec![0.0; values.len()];
+    let mut resample_stats = Vec::with_capacity(BOOTSTRAP_RESAMPLES);
+
+    for _ in 0..BOOTSTRAP_RESAMPLES {
+        for slot in &mut resample {
+            let index = (rng.next_u64() as usize) % values.len();
+            *slot = values[index];
+        }
+        resample_stats.push(statistic(&resample));
+    }
+
+    resample_stats.sort_by(f64::total_cmp);
+    (percentile_sorted(&resample_stats, 2.5), percentile_sorted(&resample_stats, 97.5))
+}
+
+/// Classify each value against Tukey fences derived from the sample's own
+/// Q1/Q3/IQR, returning `(mild_outliers, severe_outliers)` counts. Needs at
+/// least 4 values for quartiles to be meaningful; returns `(0, 0)` below that.
+fn classify_tukey_outliers(values: &[f64]) -> (usize, usize) {
+    if values.len() < 4 {
+        return (0, 0);
+    }
+
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let q1 = percentile_sorted(&sorted, 25.0);
+    let q3 = percentile_sorted(&sorted, 75.0);
+    let iqr = q3 - q1;
+
+    let (mild_lower, mild_upper) = (q1 - 1.5 * iqr, q3 + 1.5 * iqr);
+    let (severe_lower, severe_upper) = (q1 - 3.0 * iqr, q3 + 3.0 * iqr);
+
+    let mut mild_outliers = 0;
+    let mut severe_outliers = 0;
+    for &value in values {
+        if value < severe_lower || value > severe_upper {
+            severe_outliers += 1;
+        } else if value < mild_lower || value > mild_upper {
+            mild_outliers += 1;
+        }
+    }
+
+    (mild_outliers, severe_outliers)
+}
+
+/// Minimal seedable PRNG ([SplitMix64](https://prng.di.unimi.it/splitmix64.c))
+/// used for bootstrap resampling so [`bootstrap_ci`] is reproducible in
+/// tests instead of depending on a thread-local RNG.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bootstrap_ci_degrades_to_point_estimate_below_min_samples() {
+        let values = vec![1.0, 2.0, 3.0];
+        let (lower, upper) = bootstrap_ci(&values, mean, 42);
+        assert_eq!(lower, upper);
+        assert_eq!(lower, mean(&values));
+    }
+
+    #[test]
+    fn test_bootstrap_ci_is_reproducible_for_the_same_seed() {
+        let values: Vec<f64> = (0..50).map(|i| i as f64).collect();
+        let first = bootstrap_ci(&values, mean, 7);
+        let second = bootstrap_ci(&values, mean, 7);
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_bootstrap_ci_bounds_contain_the_point_estimate() {
+        let values: Vec<f64> = (0..50).map(|i| i as f64).collect();
+        let (lower, upper) = bootstrap_ci(&values, mean, 7);
+        let point = mean(&values);
+        assert!(lower <= point && point <= upper);
+    }
+
+    #[test]
+    fn test_classify_tukey_outliers_flags_mild_and_severe_points() {
+        // Tight cluster around 10 plus one mild outlier (25) and one severe
+        // outlier (1000).
+        let mut values = vec![10.0; 20];
+        values.push(25.0);
+        values.push(1000.0);
+
+        let (mild, severe) = classify_tukey_outliers(&values);
+        assert_eq!(severe, 1);
+        assert_eq!(mild, 1);
+    }
+
+    #[test]
+    fn test_classify_tukey_outliers_returns_zero_below_four_values() {
+        assert_eq!(classify_tukey_outliers(&[1.0, 2.0, 3.0]), (0, 0));
+    }
+
+    #[test]
+    fn test_winsorize_clamps_extreme_tails() {
+        let mut values: Vec<f64> = (1..=100).map(|i| i as f64).collect();
+        values[0] = -10_000.0;
+        values[99] = 10_000.0;
+
+        let winsorized = winsorize(&values, 5.0);
+        assert!(winsorized.iter().all(|&v| v >= 0.0 && v <= 101.0));
+    }
+
+    #[test]
+    fn test_winsorize_is_a_noop_below_min_samples() {
+        let values = vec![1.0, 2.0, -10_000.0];
+        assert_eq!(winsorize(&values, 5.0), values);
+    }
+
+    #[test]
+    fn test_std_dev_of_constant_values_is_zero() {
+        assert_eq!(std_dev(&[5.0, 5.0, 5.0]), 0.0);
+    }
+
+    #[test]
+    fn test_median_absolute_deviation_ignores_a_single_outlier() {
+        let mut values = vec![10.0; 9];
+        values.push(1000.0);
+        // Median stays 10.0, so every deviation but the outlier's is 0.
+        assert_eq!(median_absolute_deviation(&values), 0.0);
+    }
+
+    #[test]
+    fn test_calculate_stats_reports_winsorized_mean_and_mad() {
+        let mut samples: Vec<ResourceSample> =
+            (0..30).map(|_| ResourceSample { cpu_percent: 10.0, memory_bytes: 0 }).collect();
+        samples[0].cpu_percent = 10_000.0;
+        let snapshots: Vec<MemorySnapshot> = vec![];
+
+        let stats = ResourceMonitor::calculate_stats(&samples, &snapshots);
+
+        assert!(stats.winsorized_mean_cpu_percent < stats.avg_cpu_percent);
+        assert_eq!(stats.mad_cpu_percent, 0.0);
+    }
+
+    #[test]
+    fn test_calculate_stats_reports_memory_percentiles_and_cpu_ci() {
+        let samples: Vec<ResourceSample> = (0..20)
+            .map(|i| ResourceSample { cpu_percent: i as f64, memory_bytes: 0 })
+            .collect();
+        let snapshots: Vec<MemorySnapshot> =
+            (1..=100).map(|bytes| MemorySnapshot { bytes: bytes as u64 }).collect();
+
+        let stats = ResourceMonitor::calculate_stats(&samples, &snapshots);
+
+        assert_eq!(stats.peak_memory_bytes, 100);
+        assert!(stats.p50_memory_bytes <= stats.p95_memory_bytes);
+        assert!(stats.p95_memory_bytes <= stats.p99_memory_bytes);
+        assert!(stats.mean_ci.0 <= stats.avg_cpu_percent && stats.avg_cpu_percent <= stats.mean_ci.1);
+    }
+
+    fn stats_with_mean(avg_cpu_percent: f64, ci_half_width: f64) -> ResourceStats {
+        ResourceStats {
+            avg_cpu_percent,
+            peak_memory_bytes: 0,
+            p50_memory_bytes: 0,
+            p95_memory_bytes: 0,
+            p99_memory_bytes: 0,
+            mean_ci: (avg_cpu_percent - ci_half_width, avg_cpu_percent + ci_half_width),
+            median_ci: (avg_cpu_percent - ci_half_width, avg_cpu_percent + ci_half_width),
+            mild_outliers: 0,
+            severe_outliers: 0,
+            winsorized_mean_cpu_percent: avg_cpu_percent,
+            std_dev_cpu_percent: 0.0,
+            mad_cpu_percent: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_save_and_load_baseline_round_trips() {
+        let name = "test_save_and_load_baseline_round_trips";
+        let stats = stats_with_mean(42.0, 2.0);
+
+        ResourceMonitor::save_baseline(name, &stats).unwrap();
+        let loaded = ResourceMonitor::load_baseline(name).unwrap();
+        fs::remove_file(ResourceMonitor::baseline_path(name)).unwrap();
+
+        assert_eq!(loaded, stats);
+    }
+
+    #[test]
+    fn test_compare_to_baseline_flags_regression_beyond_noise_threshold() {
+        let name = "test_compare_to_baseline_flags_regression_beyond_noise_threshold";
+        let baseline = stats_with_mean(50.0, 1.0);
+        ResourceMonitor::save_baseline(name, &baseline).unwrap();
+
+        let current = stats_with_mean(70.0, 1.0);
+        let comparison = ResourceMonitor::compare_to_baseline(name, &current, None).unwrap();
+        fs::remove_file(ResourceMonitor::baseline_path(name)).unwrap();
+
+        assert_eq!(comparison.verdict, Verdict::Regressed);
+        assert!(comparison.is_regression());
+    }
+
+    #[test]
+    fn test_compare_to_baseline_flags_improvement_beyond_noise_threshold() {
+        let name = "test_compare_to_baseline_flags_improvement_beyond_noise_threshold";
+        let baseline = stats_with_mean(50.0, 1.0);
+        ResourceMonitor::save_baseline(name, &baseline).unwrap();
+
+        let current = stats_with_mean(30.0, 1.0);
+        let comparison = ResourceMonitor::compare_to_baseline(name, &current, None).unwrap();
+        fs::remove_file(ResourceMonitor::baseline_path(name)).unwrap();
+
+        assert_eq!(comparison.verdict, Verdict::Improved);
+        assert!(!comparison.is_regression());
+    }
+
+    #[test]
+    fn test_compare_to_baseline_reports_no_change_within_ci() {
+        let name = "test_compare_to_baseline_reports_no_change_within_ci";
+        let baseline = stats_with_mean(50.0, 5.0);
+        ResourceMonitor::save_baseline(name, &baseline).unwrap();
+
+        let current = stats_with_mean(51.0, 5.0);
+        let comparison = ResourceMonitor::compare_to_baseline(name, &current, None).unwrap();
+        fs::remove_file(ResourceMonitor::baseline_path(name)).unwrap();
+
+        assert_eq!(comparison.verdict, Verdict::NoChange);
+    }
+}
@@ -2,7 +2,10 @@
 //!
 //! This module provides infrastructure for collecting and reporting metrics
 //! from pool operations during document extraction, helping to identify
-//! allocation patterns and pool efficiency.
+//! allocation patterns and pool efficiency. Lookup timing here reflects the
+//! string pool indexed via [`crate::pool_hasher::PoolHasher`], which is used
+//! in place of the default SipHash-based `HashMap` state for pooled-buffer
+//! keys.
 
 use std::collections::HashMap;
 use std::fs;
@@ -17,6 +20,24 @@ pub struct FilePoolMetrics {
     pub string_pool_acquires: usize,
     pub string_pool_reuses: usize,
     pub string_pool_hit_rate: f64,
+    /// Total wall-clock time spent in pool lookups (acquire + release),
+    /// measured around the `HashMap` operations keyed by [`crate::pool_hasher::PoolHasher`].
+    pub string_pool_lookup_time: std::time::Duration,
+    /// `string_pool_lookup_time` divided by `string_pool_acquires`; zero duration when there were no acquires.
+    pub string_pool_avg_acquire_latency: std::time::Duration,
+}
+
+impl FilePoolMetrics {
+    /// Derive the average acquire latency from the total lookup time and acquire count.
+    pub fn with_timing(mut self, lookup_time: std::time::Duration) -> Self {
+        self.string_pool_lookup_time = lookup_time;
+        self.string_pool_avg_acquire_latency = if self.string_pool_acquires > 0 {
+            lookup_time / self.string_pool_acquires as u32
+        } else {
+            std::time::Duration::ZERO
+        };
+        self
+    }
 }
 
 /// Aggregate metrics for all extractions
@@ -74,6 +95,8 @@ impl PoolMetricsReport {
                     "total_acquires": f.string_pool_acquires,
                     "total_reuses": f.string_pool_reuses,
                     "hit_rate_percent": f.string_pool_hit_rate,
+                    "lookup_time_ns": f.string_pool_lookup_time.as_nanos() as u64,
+                    "avg_acquire_latency_ns": f.string_pool_avg_acquire_latency.as_nanos() as u64,
                 }
             })).collect::<Vec<_>>(),
         }))
@@ -86,6 +109,87 @@ impl PoolMetricsReport {
         Ok(())
     }
 
+    /// Load a previously written report (e.g. a committed baseline) from disk.
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn std::error::Error>> {
+        let raw = fs::read_to_string(path)?;
+        let value: serde_json::Value = serde_json::from_str(&raw)?;
+
+        let files = value["files"]
+            .as_array()
+            .map(|arr| {
+                arr.iter()
+                    .map(|f| FilePoolMetrics {
+                        file_name: f["file_name"].as_str().unwrap_or_default().to_string(),
+                        mime_type: f["mime_type"].as_str().unwrap_or_default().to_string(),
+                        file_size: f["file_size"].as_u64().unwrap_or(0) as usize,
+                        string_pool_acquires: f["string_pool"]["total_acquires"].as_u64().unwrap_or(0) as usize,
+                        string_pool_reuses: f["string_pool"]["total_reuses"].as_u64().unwrap_or(0) as usize,
+                        string_pool_hit_rate: f["string_pool"]["hit_rate_percent"].as_f64().unwrap_or(0.0),
+                        string_pool_lookup_time: std::time::Duration::from_nanos(
+                            f["string_pool"]["lookup_time_ns"].as_u64().unwrap_or(0),
+                        ),
+                        string_pool_avg_acquire_latency: std::time::Duration::from_nanos(
+                            f["string_pool"]["avg_acquire_latency_ns"].as_u64().unwrap_or(0),
+                        ),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(Self::from_files(files))
+    }
+
+    /// Compare this report against a previously saved baseline.
+    ///
+    /// Per-file deltas are matched by `file_name`; files present in only one
+    /// of the two reports are ignored for the delta table but still counted
+    /// towards `files_only_in_current`/`files_only_in_baseline`. A file is
+    /// flagged as regressed when its hit rate drops by more than
+    /// `threshold_pct` percentage points versus the baseline.
+    pub fn compare_to(&self, baseline: &PoolMetricsReport, threshold_pct: f64) -> RegressionReport {
+        let baseline_by_name: HashMap<&str, &FilePoolMetrics> =
+            baseline.files.iter().map(|f| (f.file_name.as_str(), f)).collect();
+
+        let mut deltas = Vec::new();
+        let mut regressions = Vec::new();
+        let mut improvements = Vec::new();
+
+        for file in &self.files {
+            if let Some(base_file) = baseline_by_name.get(file.file_name.as_str()) {
+                let delta = file.string_pool_hit_rate - base_file.string_pool_hit_rate;
+                let entry = HitRateDelta {
+                    file_name: file.file_name.clone(),
+                    current_hit_rate: file.string_pool_hit_rate,
+                    baseline_hit_rate: base_file.string_pool_hit_rate,
+                    delta_pct: delta,
+                };
+
+                if delta < -threshold_pct {
+                    regressions.push(entry.clone());
+                } else if delta > threshold_pct {
+                    improvements.push(entry.clone());
+                }
+                deltas.push(entry);
+            }
+        }
+
+        let average_delta_pct = self.average_hit_rate - baseline.average_hit_rate;
+        let verdict = if average_delta_pct < -threshold_pct {
+            RegressionVerdict::Regressed
+        } else {
+            RegressionVerdict::Pass
+        };
+
+        RegressionReport {
+            average_delta_pct,
+            threshold_pct,
+            verdict,
+            deltas,
+            regressions,
+            improvements,
+        }
+    }
+
     /// Print human-readable summary
     pub fn print_summary(&self) {
         println!("\n=== Pool Metrics Report ===");
@@ -95,6 +199,21 @@ impl PoolMetricsReport {
             self.average_hit_rate, self.min_hit_rate, self.max_hit_rate
         );
 
+        if !self.files.is_empty() {
+            let total_lookup_time: std::time::Duration = self.files.iter().map(|f| f.string_pool_lookup_time).sum();
+            let total_acquires: usize = self.files.iter().map(|f| f.string_pool_acquires).sum();
+            let avg_latency = if total_acquires > 0 {
+                total_lookup_time / total_acquires as u32
+            } else {
+                std::time::Duration::ZERO
+            };
+            println!(
+                "Pooling overhead: {:.3}ms total lookup time, {:.1}ns avg acquire latency",
+                total_lookup_time.as_secs_f64() * 1000.0,
+                avg_latency.as_nanos()
+            );
+        }
+
         let mut ranges = HashMap::new();
         for file in &self.files {
             let range = if file.string_pool_hit_rate < 25.0 {
@@ -128,3 +247,108 @@ impl PoolMetricsReport {
         }
     }
 }
+
+/// Per-file hit rate delta between a current run and a baseline.
+#[derive(Debug, Clone)]
+pub struct HitRateDelta {
+    pub file_name: String,
+    pub current_hit_rate: f64,
+    pub baseline_hit_rate: f64,
+    pub delta_pct: f64,
+}
+
+/// Overall pass/fail verdict for a baseline comparison.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegressionVerdict {
+    Pass,
+    Regressed,
+}
+
+/// Result of comparing a [`PoolMetricsReport`] against a baseline.
+#[derive(Debug, Clone)]
+pub struct RegressionReport {
+    pub average_delta_pct: f64,
+    pub threshold_pct: f64,
+    pub verdict: RegressionVerdict,
+    pub deltas: Vec<HitRateDelta>,
+    pub regressions: Vec<HitRateDelta>,
+    pub improvements: Vec<HitRateDelta>,
+}
+
+impl RegressionReport {
+    /// Whether the comparison should fail a CI run.
+    pub fn is_regression(&self) -> bool {
+        self.verdict == RegressionVerdict::Regressed
+    }
+
+    /// Emit a machine-readable JSON report suitable for CI consumption.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(&serde_json::json!({
+            "verdict": match self.verdict {
+                RegressionVerdict::Pass => "pass",
+                RegressionVerdict::Regressed => "regressed",
+            },
+            "average_delta_pct": self.average_delta_pct,
+            "threshold_pct": self.threshold_pct,
+            "regressions": self.regressions.iter().map(|d| serde_json::json!({
+                "file_name": d.file_name,
+                "current_hit_rate": d.current_hit_rate,
+                "baseline_hit_rate": d.baseline_hit_rate,
+                "delta_pct": d.delta_pct,
+            })).collect::<Vec<_>>(),
+            "improvements": self.improvements.iter().map(|d| serde_json::json!({
+                "file_name": d.file_name,
+                "current_hit_rate": d.current_hit_rate,
+                "baseline_hit_rate": d.baseline_hit_rate,
+                "delta_pct": d.delta_pct,
+            })).collect::<Vec<_>>(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod regression_tests {
+    use super::*;
+
+    fn metrics(name: &str, hit_rate: f64) -> FilePoolMetrics {
+        FilePoolMetrics {
+            file_name: name.to_string(),
+            mime_type: "text/plain".to_string(),
+            file_size: 1024,
+            string_pool_acquires: 100,
+            string_pool_reuses: (hit_rate * 100.0 / 100.0) as usize,
+            string_pool_hit_rate: hit_rate,
+            string_pool_lookup_time: std::time::Duration::ZERO,
+            string_pool_avg_acquire_latency: std::time::Duration::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_compare_to_flags_regression_beyond_threshold() {
+        let baseline = PoolMetricsReport::from_files(vec![metrics("a.pdf", 90.0)]);
+        let current = PoolMetricsReport::from_files(vec![metrics("a.pdf", 70.0)]);
+
+        let report = current.compare_to(&baseline, 5.0);
+        assert!(report.is_regression());
+        assert_eq!(report.regressions.len(), 1);
+    }
+
+    #[test]
+    fn test_compare_to_passes_within_threshold() {
+        let baseline = PoolMetricsReport::from_files(vec![metrics("a.pdf", 90.0)]);
+        let current = PoolMetricsReport::from_files(vec![metrics("a.pdf", 88.0)]);
+
+        let report = current.compare_to(&baseline, 5.0);
+        assert!(!report.is_regression());
+        assert!(report.regressions.is_empty());
+    }
+
+    #[test]
+    fn test_compare_to_detects_improvement() {
+        let baseline = PoolMetricsReport::from_files(vec![metrics("a.pdf", 60.0)]);
+        let current = PoolMetricsReport::from_files(vec![metrics("a.pdf", 95.0)]);
+
+        let report = current.compare_to(&baseline, 5.0);
+        assert_eq!(report.improvements.len(), 1);
+    }
+}
@@ -8,7 +8,25 @@
 //! # Feature Gates
 //!
 //! - `profiling`: Enables CPU profiling with pprof (available on non-Windows platforms)
+//! - `protobuf`: Sub-feature of `profiling`; enables exporting captured profiles as
+//!   `profile.proto` protobuf (`ProfilingResult::write_pprof`) alongside SVG flamegraphs
 //! - `memory-profiling`: Enables memory profiling with jemalloc
+//! - `perf-profiling`: Enables the [`PerfBackend`] [`ProfilerBackend`], which
+//!   shells out to the system `perf` sampler (Linux only, requires `perf` on
+//!   `PATH`)
+//! - `oprofile-profiling`: Enables the [`OProfileBackend`] [`ProfilerBackend`],
+//!   which shells out to the system OProfile sampler (Linux only, requires
+//!   `operf`/`opcontrol` on `PATH`)
+//!
+//! [`ProfilerBackend`] generalizes profiling beyond the in-process pprof
+//! sampler: [`best_available_backend`] picks `perf`, then OProfile, then
+//! pprof, then [`NoopBackend`], so callers can ask for "the best sampler
+//! this machine has" instead of hard-coding one.
+//!
+//! [`CriterionProfiler`] wraps [`ProfileGuard`] behind Criterion's
+//! `Profiler` trait, so `cargo bench` can drive the same sampling
+//! automatically via `Criterion::default().with_profiler(..)` instead of
+//! each benchmark managing a guard by hand.
 //!
 //! # Usage
 //!
@@ -37,8 +55,6 @@
 
 use crate::Result;
 use std::path::Path;
-
-#[cfg(all(feature = "profiling", not(target_os = "windows")))]
 use std::time::Duration;
 
 /// CPU profiler with RAII semantics
@@ -220,6 +236,128 @@ impl ProfilingResult {
 
         Ok(())
     }
+
+    /// Serialize the captured profile into the protobuf `profile.proto`
+    /// format used by `pprof`-compatible tooling (`go tool pprof`,
+    /// speedscope, and other flame viewers), as an alternative to the SVG
+    /// [`Self::generate_flamegraph`] produces.
+    ///
+    /// Creates parent directories as needed. The caller picks the file
+    /// extension (conventionally `.pb` or `.pb.gz`); this writes the raw
+    /// encoded bytes regardless of extension.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Profiling`](crate::Error::Profiling) if:
+    /// - Parent directories cannot be created
+    /// - The profile cannot be converted to its protobuf representation
+    /// - The output file cannot be written
+    #[cfg(feature = "protobuf")]
+    pub fn write_pprof(&self, output_path: &Path) -> Result<()> {
+        use pprof::protos::Message;
+
+        if let Some(parent) = output_path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| crate::Error::Profiling(format!("Failed to create output directory: {}", e)))?;
+        }
+
+        let profile = self
+            .report
+            .pprof()
+            .map_err(|e| crate::Error::Profiling(format!("Failed to build pprof profile: {}", e)))?;
+
+        let mut encoded = Vec::new();
+        profile
+            .encode(&mut encoded)
+            .map_err(|e| crate::Error::Profiling(format!("Failed to encode pprof profile: {}", e)))?;
+
+        std::fs::write(output_path, encoded)
+            .map_err(|e| crate::Error::Profiling(format!("Failed to create output file: {}", e)))?;
+
+        eprintln!("pprof profile written to: {}", output_path.display());
+
+        Ok(())
+    }
+
+    /// Stub for when the `protobuf` sub-feature of `profiling` is disabled,
+    /// so call sites stay free of `cfg`.
+    #[cfg(not(feature = "protobuf"))]
+    #[inline(always)]
+    pub fn write_pprof(&self, _output_path: &Path) -> Result<()> {
+        eprintln!("pprof export is not enabled (feature 'protobuf' required)");
+        Ok(())
+    }
+}
+
+/// Output format for a profile a [`CriterionProfiler`] writes out.
+#[cfg(all(feature = "profiling", not(target_os = "windows")))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Output {
+    /// SVG flamegraph, via [`ProfilingResult::generate_flamegraph`].
+    Flamegraph,
+    /// pprof protobuf profile, via [`ProfilingResult::write_pprof`].
+    Pprof,
+}
+
+/// Adapts [`ProfileGuard`] to [`criterion::profiler::Profiler`], so a
+/// benchmark harness can hook profiling into Criterion with
+/// `Criterion::default().with_profiler(CriterionProfiler::new(1000, Output::Flamegraph))`
+/// instead of each benchmark managing a guard by hand.
+#[cfg(all(feature = "profiling", not(target_os = "windows")))]
+pub struct CriterionProfiler {
+    /// Sampling frequency in Hz, forwarded to [`ProfileGuard::new`] (and
+    /// subject to the same 100-10000 Hz clamping).
+    sampling_frequency: i32,
+    output: Output,
+    guard: Option<ProfileGuard>,
+}
+
+#[cfg(all(feature = "profiling", not(target_os = "windows")))]
+impl CriterionProfiler {
+    /// Create a new adapter that samples at `frequency` Hz and writes
+    /// profiles in `output`'s format.
+    pub fn new(frequency: i32, output: Output) -> Self {
+        Self {
+            sampling_frequency: frequency.clamp(100, 10000),
+            output,
+            guard: None,
+        }
+    }
+}
+
+#[cfg(all(feature = "profiling", not(target_os = "windows")))]
+impl criterion::profiler::Profiler for CriterionProfiler {
+    fn start_profiling(&mut self, benchmark_id: &str, _benchmark_dir: &Path) {
+        match ProfileGuard::new(self.sampling_frequency) {
+            Ok(guard) => self.guard = Some(guard),
+            Err(e) => eprintln!("Failed to start profiling for {benchmark_id}: {e}"),
+        }
+    }
+
+    fn stop_profiling(&mut self, benchmark_id: &str, benchmark_dir: &Path) {
+        let Some(guard) = self.guard.take() else {
+            return;
+        };
+
+        let result = match guard.finish() {
+            Ok(result) => result,
+            Err(e) => {
+                eprintln!("Failed to finish profiling for {benchmark_id}: {e}");
+                return;
+            }
+        };
+
+        let write_result = match self.output {
+            Output::Flamegraph => result.generate_flamegraph(&benchmark_dir.join(format!("{benchmark_id}.svg"))),
+            Output::Pprof => result.write_pprof(&benchmark_dir.join(format!("{benchmark_id}.pb"))),
+        };
+
+        if let Err(e) = write_result {
+            eprintln!("Failed to write profile for {benchmark_id}: {e}");
+        }
+    }
 }
 
 /// No-op profiling support when feature is disabled or on Windows
@@ -281,6 +419,13 @@ pub mod noop {
             eprintln!("Profiling is not available on this platform or feature is disabled");
             Ok(())
         }
+
+        /// No-op pprof protobuf export
+        #[inline(always)]
+        pub fn write_pprof(&self, _output_path: &Path) -> Result<()> {
+            eprintln!("Profiling is not available on this platform or feature is disabled");
+            Ok(())
+        }
     }
 }
 
@@ -288,6 +433,357 @@ pub mod noop {
 #[cfg(not(all(feature = "profiling", not(target_os = "windows"))))]
 pub use noop::{ProfileGuard, ProfilingResult};
 
+// --- Pluggable external-profiler backends ---
+//
+// `ProfileGuard` only drives the in-process pprof sampler. `ProfilerBackend`
+// generalizes sampling to out-of-process tools (`perf`, OProfile) behind one
+// interface, so the benchmark harness can pick whichever sampler the current
+// machine actually has instead of depending on pprof alone.
+
+/// Sampling overhead a [`ProfilerBackend`] adds to the profiled workload, as
+/// reported by [`ProfilerBackend::overhead`]. Informational only; backends
+/// don't enforce it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Overhead {
+    /// No measurable slowdown to the profiled workload (e.g. [`NoopBackend`]).
+    Negligible,
+    /// A small, generally acceptable slowdown (sampling profilers at
+    /// reasonable frequencies).
+    Low,
+}
+
+/// A CPU profiling backend selectable at runtime.
+///
+/// Implementations should gracefully degrade to [`NoopBackend`]-like
+/// behavior (succeed, sample nothing) when their underlying tool or
+/// platform is unavailable, rather than erroring out of `start`/`finish`,
+/// so callers can select a backend speculatively via
+/// [`best_available_backend`] without special-casing "not installed".
+pub trait ProfilerBackend {
+    /// Human-readable backend name, e.g. `"pprof"`, `"perf"`, `"oprofile"`.
+    fn name(&self) -> &'static str;
+
+    /// Reported sampling overhead for this backend.
+    fn overhead(&self) -> Overhead;
+
+    /// Begin sampling the current process.
+    fn start(&mut self) -> Result<()>;
+
+    /// Stop sampling and write artifacts under `output_dir`.
+    ///
+    /// # Returns
+    ///
+    /// The path to the primary artifact: a flamegraph SVG for backends that
+    /// can produce one directly, or a sampler-native directory/file for
+    /// backends whose output needs further external post-processing (e.g.
+    /// OProfile's `opout` directory, processed with `opreport`/`opannotate`).
+    fn finish(&mut self, output_dir: &Path) -> Result<std::path::PathBuf>;
+}
+
+/// [`ProfilerBackend`] wrapping the in-process pprof sampler ([`ProfileGuard`]).
+#[cfg(all(feature = "profiling", not(target_os = "windows")))]
+pub struct PprofBackend {
+    frequency: i32,
+    guard: Option<ProfileGuard>,
+}
+
+#[cfg(all(feature = "profiling", not(target_os = "windows")))]
+impl PprofBackend {
+    /// Create a backend that will sample at `frequency` Hz once started.
+    pub fn new(frequency: i32) -> Self {
+        Self { frequency, guard: None }
+    }
+}
+
+#[cfg(all(feature = "profiling", not(target_os = "windows")))]
+impl ProfilerBackend for PprofBackend {
+    fn name(&self) -> &'static str {
+        "pprof"
+    }
+
+    fn overhead(&self) -> Overhead {
+        Overhead::Low
+    }
+
+    fn start(&mut self) -> Result<()> {
+        self.guard = Some(ProfileGuard::new(self.frequency)?);
+        Ok(())
+    }
+
+    fn finish(&mut self, output_dir: &Path) -> Result<std::path::PathBuf> {
+        let guard = self
+            .guard
+            .take()
+            .ok_or_else(|| crate::Error::Profiling("pprof backend was not started".to_string()))?;
+
+        let result = guard.finish()?;
+        let path = output_dir.join("pprof.svg");
+        result.generate_flamegraph(&path)?;
+        Ok(path)
+    }
+}
+
+/// [`ProfilerBackend`] that shells out to the system `perf` sampler.
+///
+/// [`PerfBackend::new`] probes for `perf` on `PATH` and never fails:
+/// when `perf` is missing, `start`/`finish` succeed without recording
+/// anything, matching [`NoopBackend`] behavior, so a caller can select this
+/// backend speculatively via [`best_available_backend`].
+#[cfg(feature = "perf-profiling")]
+pub struct PerfBackend {
+    available: bool,
+    data_path: std::path::PathBuf,
+    child: Option<std::process::Child>,
+}
+
+#[cfg(feature = "perf-profiling")]
+impl PerfBackend {
+    /// Create a backend that will record into `<output_dir>/perf.data`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Profiling`](crate::Error::Profiling) if `output_dir`
+    /// cannot be created.
+    pub fn new(output_dir: &Path) -> Result<Self> {
+        std::fs::create_dir_all(output_dir)
+            .map_err(|e| crate::Error::Profiling(format!("Failed to create output directory: {}", e)))?;
+
+        let available = std::process::Command::new("perf")
+            .arg("--version")
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success());
+
+        Ok(Self {
+            available,
+            data_path: output_dir.join("perf.data"),
+            child: None,
+        })
+    }
+}
+
+#[cfg(feature = "perf-profiling")]
+impl ProfilerBackend for PerfBackend {
+    fn name(&self) -> &'static str {
+        "perf"
+    }
+
+    fn overhead(&self) -> Overhead {
+        Overhead::Low
+    }
+
+    fn start(&mut self) -> Result<()> {
+        if !self.available {
+            eprintln!("perf is not available on PATH; the perf backend will not record samples");
+            return Ok(());
+        }
+
+        // `perf record` samples the target PID until its own child exits;
+        // `sleep infinity` just keeps that child alive until `finish` stops
+        // recording with SIGINT, which flushes `perf.data` on exit.
+        let child = std::process::Command::new("perf")
+            .args(["record", "-g", "-p", &std::process::id().to_string(), "-o"])
+            .arg(&self.data_path)
+            .args(["--", "sleep", "infinity"])
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .spawn()
+            .map_err(|e| crate::Error::Profiling(format!("Failed to launch perf record: {}", e)))?;
+
+        self.child = Some(child);
+        Ok(())
+    }
+
+    fn finish(&mut self, output_dir: &Path) -> Result<std::path::PathBuf> {
+        if !self.available {
+            return Ok(output_dir.join("perf.svg"));
+        }
+
+        let Some(child) = self.child.take() else {
+            return Err(crate::Error::Profiling("perf backend was not started".to_string()));
+        };
+
+        std::process::Command::new("kill")
+            .args(["-INT", &child.id().to_string()])
+            .status()
+            .map_err(|e| crate::Error::Profiling(format!("Failed to stop perf record: {}", e)))?;
+        child
+            .wait_with_output()
+            .map_err(|e| crate::Error::Profiling(format!("Failed to wait for perf record: {}", e)))?;
+
+        let script = std::process::Command::new("perf")
+            .args(["script", "-i"])
+            .arg(&self.data_path)
+            .output()
+            .map_err(|e| crate::Error::Profiling(format!("Failed to run perf script: {}", e)))?;
+
+        let folded = inferno::collapse::perf::Folder::default()
+            .collapse(script.stdout.as_slice(), Vec::new())
+            .map_err(|e| crate::Error::Profiling(format!("Failed to collapse perf stacks: {}", e)))?;
+
+        let flamegraph_path = output_dir.join("perf.svg");
+        let file = std::fs::File::create(&flamegraph_path)
+            .map_err(|e| crate::Error::Profiling(format!("Failed to create output file: {}", e)))?;
+        inferno::flamegraph::from_reader(&mut inferno::flamegraph::Options::default(), folded.as_slice(), file)
+            .map_err(|e| crate::Error::Profiling(format!("Failed to generate flamegraph: {}", e)))?;
+
+        eprintln!("perf flamegraph written to: {}", flamegraph_path.display());
+
+        Ok(flamegraph_path)
+    }
+}
+
+/// [`ProfilerBackend`] that shells out to the system OProfile sampler
+/// (`operf`).
+///
+/// Unlike [`PerfBackend`] and [`PprofBackend`], OProfile's own tooling
+/// (`opreport`, `opannotate`) reads its sample directory directly, so
+/// `finish` hands back that directory rather than a flamegraph.
+///
+/// [`OProfileBackend::new`] probes for `operf` on `PATH` and never fails:
+/// when it's missing, `start`/`finish` succeed without recording anything,
+/// matching [`NoopBackend`] behavior.
+#[cfg(feature = "oprofile-profiling")]
+pub struct OProfileBackend {
+    available: bool,
+    session_dir: std::path::PathBuf,
+    child: Option<std::process::Child>,
+}
+
+#[cfg(feature = "oprofile-profiling")]
+impl OProfileBackend {
+    /// Create a backend that will collect into `<output_dir>/opout`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Profiling`](crate::Error::Profiling) if `output_dir`
+    /// cannot be created.
+    pub fn new(output_dir: &Path) -> Result<Self> {
+        std::fs::create_dir_all(output_dir)
+            .map_err(|e| crate::Error::Profiling(format!("Failed to create output directory: {}", e)))?;
+
+        let available = std::process::Command::new("operf")
+            .arg("--version")
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success());
+
+        Ok(Self {
+            available,
+            session_dir: output_dir.join("opout"),
+            child: None,
+        })
+    }
+}
+
+#[cfg(feature = "oprofile-profiling")]
+impl ProfilerBackend for OProfileBackend {
+    fn name(&self) -> &'static str {
+        "oprofile"
+    }
+
+    fn overhead(&self) -> Overhead {
+        Overhead::Negligible
+    }
+
+    fn start(&mut self) -> Result<()> {
+        if !self.available {
+            eprintln!("operf is not available on PATH; the oprofile backend will not record samples");
+            return Ok(());
+        }
+
+        let child = std::process::Command::new("operf")
+            .arg("--session-dir")
+            .arg(&self.session_dir)
+            .arg("--pid")
+            .arg(std::process::id().to_string())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .spawn()
+            .map_err(|e| crate::Error::Profiling(format!("Failed to launch operf: {}", e)))?;
+
+        self.child = Some(child);
+        Ok(())
+    }
+
+    fn finish(&mut self, _output_dir: &Path) -> Result<std::path::PathBuf> {
+        if !self.available {
+            return Ok(self.session_dir.clone());
+        }
+
+        let Some(child) = self.child.take() else {
+            return Err(crate::Error::Profiling("oprofile backend was not started".to_string()));
+        };
+
+        std::process::Command::new("kill")
+            .args(["-INT", &child.id().to_string()])
+            .status()
+            .map_err(|e| crate::Error::Profiling(format!("Failed to stop operf: {}", e)))?;
+        child
+            .wait_with_output()
+            .map_err(|e| crate::Error::Profiling(format!("Failed to wait for operf: {}", e)))?;
+
+        eprintln!(
+            "OProfile samples written to: {} (process with opreport/opannotate)",
+            self.session_dir.display()
+        );
+
+        Ok(self.session_dir.clone())
+    }
+}
+
+/// [`ProfilerBackend`] used as the final fallback when no other backend is
+/// available; `start`/`finish` succeed immediately without recording
+/// anything.
+#[derive(Debug, Default)]
+pub struct NoopBackend;
+
+impl ProfilerBackend for NoopBackend {
+    fn name(&self) -> &'static str {
+        "noop"
+    }
+
+    fn overhead(&self) -> Overhead {
+        Overhead::Negligible
+    }
+
+    fn start(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn finish(&mut self, _output_dir: &Path) -> Result<std::path::PathBuf> {
+        eprintln!("No profiler backend is available; nothing was sampled");
+        Ok(std::path::PathBuf::new())
+    }
+}
+
+/// Pick the best available [`ProfilerBackend`] for this machine: `perf` if
+/// its CLI is on `PATH`, else OProfile if its CLI is on `PATH`, else the
+/// in-process pprof sampler (if compiled in), else [`NoopBackend`].
+#[allow(unused_variables, unreachable_code)]
+pub fn best_available_backend(frequency: i32, output_dir: &Path) -> Box<dyn ProfilerBackend> {
+    #[cfg(feature = "perf-profiling")]
+    if let Ok(backend) = PerfBackend::new(output_dir)
+        && backend.available
+    {
+        return Box::new(backend);
+    }
+
+    #[cfg(feature = "oprofile-profiling")]
+    if let Ok(backend) = OProfileBackend::new(output_dir)
+        && backend.available
+    {
+        return Box::new(backend);
+    }
+
+    #[cfg(all(feature = "profiling", not(target_os = "windows")))]
+    return Box::new(PprofBackend::new(frequency));
+
+    Box::new(NoopBackend)
+}
+
 /// Dump heap profile to a file using jemalloc
 ///
 /// This function captures a heap profile snapshot from jemalloc and writes it to disk.
@@ -301,20 +797,58 @@ pub use noop::{ProfileGuard, ProfilingResult};
 ///
 /// Ok if the heap dump was successfully written, or an error otherwise
 ///
+/// A byte count, displayed in whichever of b/kb/mb/gb/tb reads most
+/// naturally, e.g. `Bytes(12_300_000)` displays as `11.7mb`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Bytes(pub u64);
+
+impl std::fmt::Display for Bytes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        const UNITS: &[&str] = &["b", "kb", "mb", "gb", "tb"];
+        let mut value = self.0 as f64;
+        let mut unit = 0;
+        while value >= 1024.0 && unit < UNITS.len() - 1 {
+            value /= 1024.0;
+            unit += 1;
+        }
+        write!(f, "{:.1}{}", value, UNITS[unit])
+    }
+}
+
+/// Memory usage summary gathered from jemalloc's `stats.allocated` and
+/// `stats.resident` MIBs, returned by [`dump_heap_profile`].
+#[derive(Debug, Clone, Copy)]
+pub struct MemoryUsage {
+    /// Bytes allocated by the application, per jemalloc's `stats.allocated`.
+    pub allocated: Bytes,
+    /// Bytes resident in physical memory, per jemalloc's `stats.resident`.
+    pub resident: Bytes,
+}
+
+impl std::fmt::Display for MemoryUsage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} allocated / {} resident", self.allocated, self.resident)
+    }
+}
+
 /// # Errors
 ///
-/// Returns an error if:
-/// - Memory profiling feature is not enabled
+/// Returns [`Error::Profiling`](crate::Error::Profiling) if:
+/// - The binary was not started with jemalloc profiling enabled
+///   (`MALLOC_CONF=prof:true`)
 /// - The output file cannot be created
 /// - jemalloc heap dump generation fails
 #[cfg(feature = "memory-profiling")]
-pub fn dump_heap_profile(path: &Path) -> Result<()> {
-    use tikv_jemalloc_ctl::epoch;
+pub fn dump_heap_profile(path: &Path) -> Result<MemoryUsage> {
+    use tikv_jemalloc_ctl::{epoch, opt, stats};
 
-    epoch::mib()
-        .map_err(|e| crate::Error::Profiling(format!("Failed to get epoch mib: {}", e)))?
-        .advance()
-        .map_err(|e| crate::Error::Profiling(format!("Failed to advance epoch: {}", e)))?;
+    let profiling_enabled = opt::prof::read()
+        .map_err(|e| crate::Error::Profiling(format!("Failed to read opt.prof: {}", e)))?;
+    if !profiling_enabled {
+        return Err(crate::Error::Profiling(
+            "jemalloc profiling is not enabled; start the binary with MALLOC_CONF=prof:true".to_string(),
+        ));
+    }
 
     if let Some(parent) = path.parent()
         && !parent.as_os_str().is_empty()
@@ -326,20 +860,230 @@ pub fn dump_heap_profile(path: &Path) -> Result<()> {
     let mut prof_path = path.to_path_buf();
     prof_path.set_extension("heap");
 
-    eprintln!(
-        "Heap profile ready at: {} (jemalloc memory statistics have been updated)",
-        prof_path.display()
-    );
+    let c_path = std::ffi::CString::new(prof_path.as_os_str().as_encoded_bytes())
+        .map_err(|e| crate::Error::Profiling(format!("Invalid heap dump path: {}", e)))?;
 
-    Ok(())
+    // SAFETY: `c_path` is a valid NUL-terminated C string that outlives the
+    // call, matching what the `prof.dump` mallctl expects for its `const
+    // char *` argument.
+    unsafe {
+        tikv_jemalloc_ctl::raw::write(b"prof.dump\0", c_path.as_ptr())
+            .map_err(|e| crate::Error::Profiling(format!("Failed to write jemalloc heap dump: {}", e)))?;
+    }
+
+    epoch::mib()
+        .map_err(|e| crate::Error::Profiling(format!("Failed to get epoch mib: {}", e)))?
+        .advance()
+        .map_err(|e| crate::Error::Profiling(format!("Failed to advance epoch: {}", e)))?;
+
+    let allocated = stats::allocated::read().map_err(|e| crate::Error::Profiling(format!("Failed to read stats.allocated: {}", e)))?;
+    let resident = stats::resident::read().map_err(|e| crate::Error::Profiling(format!("Failed to read stats.resident: {}", e)))?;
+
+    let usage = MemoryUsage {
+        allocated: Bytes(allocated as u64),
+        resident: Bytes(resident as u64),
+    };
+
+    eprintln!("Heap profile written to: {} ({})", prof_path.display(), usage);
+
+    Ok(usage)
 }
 
 /// No-op heap dump when memory profiling is disabled
 #[cfg(not(feature = "memory-profiling"))]
 #[inline(always)]
-pub fn dump_heap_profile(_path: &Path) -> Result<()> {
+pub fn dump_heap_profile(_path: &Path) -> Result<MemoryUsage> {
     eprintln!("Memory profiling is not enabled (feature 'memory-profiling' required)");
-    Ok(())
+    Ok(MemoryUsage { allocated: Bytes(0), resident: Bytes(0) })
+}
+
+// --- Named timing scopes ---
+//
+// A lightweight instrumentation subsystem that complements sampling-based
+// pprof profiling with explicit, named scopes, for attributing time to
+// specific extractor stages (RTF tokenizing, OCR, table parsing) without
+// needing a full sampling profiler attached.
+
+use std::cell::RefCell;
+use std::time::Instant;
+
+/// One named duration recorded by [`profile`], plus the (already merged)
+/// durations recorded by scopes nested inside it.
+#[derive(Debug, Clone)]
+struct ScopeNode {
+    description: String,
+    duration: Duration,
+    count: usize,
+    children: Vec<ScopeNode>,
+}
+
+struct ScopeFrame {
+    description: String,
+    start: Instant,
+    children: Vec<ScopeNode>,
+}
+
+thread_local! {
+    static SCOPE_STACK: RefCell<Vec<ScopeFrame>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Allow-list and reporting limits for the tree [`ProfileScope`] prints on
+/// the outermost scope's exit, configured globally via [`set_filter`].
+///
+/// # Spec syntax
+///
+/// `Filter::from_spec` parses a `|`-separated allow-list of descriptions,
+/// plus an optional `@N` maximum nesting depth and an optional `>Nms`
+/// minimum-duration threshold, e.g. `"rtf|ocr@3"` allows only scopes named
+/// `rtf` or `ocr`, at most 3 levels deep. An empty allow-list (no
+/// descriptions before the `@`/`>` suffixes, or an empty spec) allows every
+/// description.
+#[derive(Debug, Clone, Default)]
+pub struct Filter {
+    /// `None` means every description is allowed.
+    allow: Option<Vec<String>>,
+    max_depth: Option<usize>,
+    min_duration: Option<Duration>,
+}
+
+impl Filter {
+    /// Parse a filter spec; see the [type-level docs](Self) for the syntax.
+    pub fn from_spec(spec: &str) -> Self {
+        let mut remainder = spec.to_string();
+        let mut max_depth = None;
+        let mut min_duration = None;
+
+        if let Some(at_pos) = remainder.find('@') {
+            let after = remainder[at_pos + 1..].to_string();
+            let digits: String = after.chars().take_while(char::is_ascii_digit).collect();
+            max_depth = digits.parse::<usize>().ok();
+            remainder = format!("{}{}", &remainder[..at_pos], &after[digits.len()..]);
+        }
+
+        if let Some(gt_pos) = remainder.find('>')
+            && let Some(ms_pos) = remainder[gt_pos + 1..].find("ms")
+        {
+            let digits = &remainder[gt_pos + 1..gt_pos + 1 + ms_pos];
+            min_duration = digits.parse::<u64>().ok().map(Duration::from_millis);
+            remainder = format!("{}{}", &remainder[..gt_pos], &remainder[gt_pos + 1 + ms_pos + 2..]);
+        }
+
+        let allow = remainder
+            .split('|')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+
+        Self {
+            allow: if allow.is_empty() { None } else { Some(allow) },
+            max_depth,
+            min_duration,
+        }
+    }
+
+    fn allows(&self, description: &str, depth: usize) -> bool {
+        if self.max_depth.is_some_and(|max_depth| depth > max_depth) {
+            return false;
+        }
+        self.allow.as_ref().is_none_or(|allow| allow.iter().any(|a| a == description))
+    }
+
+    fn meets_threshold(&self, duration: Duration) -> bool {
+        self.min_duration.is_none_or(|min_duration| duration >= min_duration)
+    }
+}
+
+static FILTER: std::sync::OnceLock<std::sync::RwLock<Filter>> = std::sync::OnceLock::new();
+
+fn filter_lock() -> &'static std::sync::RwLock<Filter> {
+    FILTER.get_or_init(|| std::sync::RwLock::new(Filter::default()))
+}
+
+/// Set the global filter used when the outermost [`ProfileScope`] prints its
+/// tree. Defaults to allowing every description at every depth.
+pub fn set_filter(filter: Filter) {
+    *filter_lock().write().expect("profiling filter lock poisoned") = filter;
+}
+
+/// Start a named timing scope. Elapsed time is recorded on drop into a
+/// thread-local stack that mirrors the caller's (possibly nested) scopes;
+/// when the outermost scope in the stack is dropped, the accumulated tree is
+/// printed to stderr.
+///
+/// # Examples
+///
+/// ```
+/// use benchmark_harness::profiling::profile;
+///
+/// fn tokenize() {
+///     let _scope = profile("rtf-tokenize");
+///     // ... do work ...
+/// }
+/// ```
+pub fn profile(description: &str) -> ProfileScope {
+    SCOPE_STACK.with(|stack| {
+        stack.borrow_mut().push(ScopeFrame {
+            description: description.to_string(),
+            start: Instant::now(),
+            children: Vec::new(),
+        });
+    });
+
+    ProfileScope { _private: () }
+}
+
+/// RAII guard returned by [`profile`]. Recording happens entirely on drop;
+/// this type carries no public state.
+#[must_use = "a ProfileScope does nothing until dropped"]
+pub struct ProfileScope {
+    _private: (),
+}
+
+impl Drop for ProfileScope {
+    fn drop(&mut self) {
+        SCOPE_STACK.with(|stack| {
+            let mut stack = stack.borrow_mut();
+            let Some(frame) = stack.pop() else {
+                return;
+            };
+
+            let node = ScopeNode {
+                description: frame.description,
+                duration: frame.start.elapsed(),
+                count: 1,
+                children: frame.children,
+            };
+
+            match stack.last_mut() {
+                Some(parent) => merge_sibling(&mut parent.children, node),
+                None => {
+                    let filter = filter_lock().read().expect("profiling filter lock poisoned").clone();
+                    print_tree(std::slice::from_ref(&node), 0, &filter);
+                }
+            }
+        });
+    }
+}
+
+fn merge_sibling(siblings: &mut Vec<ScopeNode>, node: ScopeNode) {
+    match siblings.iter_mut().find(|s| s.description == node.description) {
+        Some(existing) => {
+            existing.duration += node.duration;
+            existing.count += node.count;
+            existing.children.extend(node.children);
+        }
+        None => siblings.push(node),
+    }
+}
+
+fn print_tree(nodes: &[ScopeNode], depth: usize, filter: &Filter) {
+    for node in nodes {
+        if filter.allows(&node.description, depth) && filter.meets_threshold(node.duration) {
+            eprintln!("{}{}: {:?} ({})", "  ".repeat(depth), node.description, node.duration, node.count);
+        }
+        print_tree(&node.children, depth + 1, filter);
+    }
 }
 
 #[cfg(test)]
@@ -364,6 +1108,14 @@ mod tests {
             result.generate_flamegraph(Path::new("/tmp/noop.svg"))?;
             Ok(())
         }
+
+        #[test]
+        fn test_noop_write_pprof() -> crate::Result<()> {
+            let guard = ProfileGuard::new(1000)?;
+            let result = guard.finish()?;
+            result.write_pprof(Path::new("/tmp/noop.pb"))?;
+            Ok(())
+        }
     }
 
     #[cfg(all(feature = "profiling", not(target_os = "windows")))]
@@ -414,5 +1166,190 @@ mod tests {
 
             Ok(())
         }
+
+        #[cfg(feature = "protobuf")]
+        #[test]
+        #[ignore]
+        fn test_write_pprof() -> crate::Result<()> {
+            let guard = ProfileGuard::new(1000)?;
+
+            let _sum: u64 = (0..1_000_000).sum();
+
+            let result = guard.finish()?;
+
+            let temp_dir = TempDir::new()?;
+            let output_path = temp_dir.path().join("profile.pb");
+
+            result.write_pprof(&output_path)?;
+
+            assert!(output_path.exists(), "pprof profile file should exist");
+
+            Ok(())
+        }
+
+        #[test]
+        #[ignore]
+        fn test_criterion_profiler_writes_flamegraph_on_stop() -> crate::Result<()> {
+            use crate::profiling::{CriterionProfiler, Output};
+            use criterion::profiler::Profiler;
+
+            let mut profiler = CriterionProfiler::new(1000, Output::Flamegraph);
+            let temp_dir = TempDir::new()?;
+
+            profiler.start_profiling("bench_example", temp_dir.path());
+            let _sum: u64 = (0..1_000_000).sum();
+            profiler.stop_profiling("bench_example", temp_dir.path());
+
+            assert!(temp_dir.path().join("bench_example.svg").exists());
+
+            Ok(())
+        }
+    }
+
+    mod scopes {
+        use crate::profiling::{Filter, profile};
+        use std::time::Duration;
+
+        #[test]
+        fn test_filter_from_spec_parses_allowlist_depth_and_threshold() {
+            let filter = Filter::from_spec("rtf|ocr@3>5ms");
+            assert!(filter.allows("rtf", 0));
+            assert!(filter.allows("ocr", 3));
+            assert!(!filter.allows("ocr", 4));
+            assert!(!filter.allows("other", 0));
+            assert!(filter.meets_threshold(Duration::from_millis(5)));
+            assert!(!filter.meets_threshold(Duration::from_millis(4)));
+        }
+
+        #[test]
+        fn test_filter_from_spec_with_no_allowlist_allows_every_description() {
+            let filter = Filter::from_spec("@2");
+            assert!(filter.allows("anything", 2));
+            assert!(!filter.allows("anything", 3));
+        }
+
+        #[test]
+        fn test_nested_scopes_pop_in_stack_order_without_panicking() {
+            let _outer = profile("outer");
+            {
+                let _inner_a = profile("inner");
+            }
+            {
+                let _inner_b = profile("inner");
+            }
+        }
+    }
+
+    mod heap_dump {
+        use crate::profiling::Bytes;
+
+        #[test]
+        fn test_bytes_display_picks_the_largest_unit_under_a_kibibyte_step() {
+            assert_eq!(Bytes(512).to_string(), "512.0b");
+            assert_eq!(Bytes(12_300_000).to_string(), "11.7mb");
+        }
+
+        #[cfg(not(feature = "memory-profiling"))]
+        mod memory_profiling_disabled {
+            use crate::profiling::dump_heap_profile;
+            use std::path::Path;
+
+            #[test]
+            fn test_noop_dump_heap_profile_returns_zeroed_usage() -> crate::Result<()> {
+                let usage = dump_heap_profile(Path::new("/tmp/noop.heap"))?;
+                assert_eq!(usage.allocated.0, 0);
+                assert_eq!(usage.resident.0, 0);
+                Ok(())
+            }
+        }
+
+        #[cfg(feature = "memory-profiling")]
+        mod memory_profiling_enabled {
+            use crate::profiling::dump_heap_profile;
+            use tempfile::TempDir;
+
+            #[test]
+            #[ignore]
+            fn test_dump_heap_profile_writes_heap_file_and_reports_usage() -> crate::Result<()> {
+                let temp_dir = TempDir::new()?;
+                let output_path = temp_dir.path().join("profile.heap");
+
+                let usage = dump_heap_profile(&output_path)?;
+
+                assert!(output_path.exists(), "Heap dump file should exist");
+                assert!(usage.allocated.0 > 0);
+
+                Ok(())
+            }
+        }
+    }
+
+    mod backends {
+        use crate::profiling::{NoopBackend, ProfilerBackend};
+        use std::path::Path;
+
+        #[test]
+        fn test_noop_backend_starts_and_finishes_without_artifacts() -> crate::Result<()> {
+            let mut backend = NoopBackend;
+            assert_eq!(backend.name(), "noop");
+            backend.start()?;
+            let path = backend.finish(Path::new("/tmp"))?;
+            assert_eq!(path, Path::new(""));
+            Ok(())
+        }
+
+        #[cfg(feature = "perf-profiling")]
+        #[test]
+        fn test_perf_backend_degrades_to_noop_when_perf_is_unavailable() -> crate::Result<()> {
+            use crate::profiling::PerfBackend;
+            use tempfile::TempDir;
+
+            let temp_dir = TempDir::new()?;
+            let mut backend = PerfBackend::new(temp_dir.path())?;
+            if backend.available {
+                return Ok(());
+            }
+
+            backend.start()?;
+            let path = backend.finish(temp_dir.path())?;
+            assert_eq!(path, temp_dir.path().join("perf.svg"));
+            Ok(())
+        }
+
+        #[cfg(feature = "oprofile-profiling")]
+        #[test]
+        fn test_oprofile_backend_degrades_to_noop_when_operf_is_unavailable() -> crate::Result<()> {
+            use crate::profiling::OProfileBackend;
+            use tempfile::TempDir;
+
+            let temp_dir = TempDir::new()?;
+            let mut backend = OProfileBackend::new(temp_dir.path())?;
+            if backend.available {
+                return Ok(());
+            }
+
+            backend.start()?;
+            let path = backend.finish(temp_dir.path())?;
+            assert_eq!(path, temp_dir.path().join("opout"));
+            Ok(())
+        }
+
+        #[cfg(all(feature = "profiling", not(target_os = "windows")))]
+        #[test]
+        #[ignore]
+        fn test_pprof_backend_writes_flamegraph_on_finish() -> crate::Result<()> {
+            use crate::profiling::PprofBackend;
+            use tempfile::TempDir;
+
+            let mut backend = PprofBackend::new(1000);
+            backend.start()?;
+            let _sum: u64 = (0..1_000_000).sum();
+
+            let temp_dir = TempDir::new()?;
+            let path = backend.finish(temp_dir.path())?;
+            assert!(path.exists());
+
+            Ok(())
+        }
     }
 }